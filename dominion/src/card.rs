@@ -1,110 +1,1051 @@
+use std::collections::RingBuf;
+
+use super::cost::{Cost, POTION};
+use super::phase;
+use super::event::Zone;
+use super::reaction::{ActionResolved, Attacked, Blocked, Bought, BuyPhaseEnded, Gained, NotBlocked, OtherGained, Outcome, Trigger,
+                      TurnStarted, WouldGain};
+use super::registry;
 use super::response;
-use super::{GameState, PendingPlay, PlayerHandle};
+use super::scoring::{ScoreSheet, ScoringRule};
+use super::{GameState, Player, PlayerHandle};
 
 macro_rules! defcards {
-    ($($card:ident [$($typ:expr),+]),+,) => {
-        #[deriving(Clone, Show, PartialEq, Eq, Hash)]
+    ($($card:ident ($cost:expr, $coins:expr, $vp:expr $(, $debt:expr)*) $(+ $potion:ident)* [$($typ:expr),+]),+,) => {
+        #[deriving(Clone, Show, PartialEq, Eq, Hash, Encodable, Decodable)]
         pub enum Card {
             $($card,)+
+
+            /// A card loaded at runtime, numbered in the order it was
+            /// loaded. See the `registry` module.
+            Custom(uint),
         }
 
         impl Card {
+            /// Every card built into the engine.
+            pub fn builtin() -> Vec<Card> {
+                vec![$($card),+]
+            }
+
             pub fn name(&self) -> &'static str {
                 match *self {
                     $($card => stringify!($card),)+
+                    Custom(id) => registry::get(id).name.as_slice(),
                 }
             }
 
-            pub fn is_action(&self) -> bool {
+            /// What the card costs, before anything makes it cheaper. See
+            /// `GameState::cost()`.
+            pub fn cost(&self) -> Cost {
                 match *self {
-                    $($card => [$($typ),+].iter().any(|t| *t == Action),)+
+                    $($card => Cost{coins: $cost, potions: 0 $(+ $potion)*, debt: 0 $(+ $debt)*},)+
+                    Custom(id) => registry::get(id).cost(),
                 }
             }
 
-            pub fn is_money(&self) -> bool {
+            /// How much buying power this card gives when it's played as a
+            /// treasure.
+            pub fn coin_value(&self) -> uint {
                 match *self {
-                    $($card => [$($typ),+].iter().any(|t| *t == Money),)+
+                    $($card => $coins,)+
+                    Custom(id) if self.is_money() => registry::get(id).coins.unwrap_or(0),
+                    Custom(_) => 0,
                 }
             }
 
-            pub fn is_victory(&self) -> bool {
+            /// The victory points printed on the card. Cards like Gardens,
+            /// whose points depend on the rest of the deck, are worth 0
+            /// here; see `victory_points()`.
+            pub fn printed_victory_points(&self) -> int {
                 match *self {
-                    $($card => [$($typ),+].iter().any(|t| *t == Victory),)+
+                    $($card => $vp,)+
+                    Custom(id) => registry::get(id).vp.unwrap_or(0),
                 }
             }
 
-            pub fn is_curse(&self) -> bool {
+            /// Every type the card has, e.g. Moat is both an Action and a
+            /// Reaction.
+            pub fn types(&self) -> Vec<CardType> {
                 match *self {
-                    $($card => [$($typ),+].iter().any(|t| *t == Curse),)+
+                    $($card => vec![$($typ),+],)+
+                    Custom(id) => registry::get(id).types.clone(),
+                }
+            }
+
+            pub fn has_type(&self, typ: CardType) -> bool {
+                match *self {
+                    $($card => [$($typ),+].iter().any(|t| *t == typ),)+
+                    Custom(id) => registry::get(id).types.contains(&typ),
                 }
             }
         }
     }
 }
 
-#[deriving(Show, PartialEq)]
+#[deriving(Clone, Show, PartialEq, Eq, Hash, Encodable, Decodable)]
 pub enum CardType {
     Action,
+    Attack,
+    CurseType,
+    Duration,
+
+    /// Events are bought like cards, but they're never in anyone's deck.
+    EventType,
+
+    /// Supply piles that collect victory point tokens, e.g. Temple.
+    Gathering,
+    Knight,
+
+    /// Landmarks change how the game is scored, or give points some other
+    /// way. Like Events, they're never in anyone's deck.
+    Landmark,
+    Looter,
     Money,
+    Night,
+    Prize,
+    Reaction,
+    Reserve,
+    Ruins,
+    Shelter,
+    Traveller,
     Victory,
-    Curse,
 }
 
 defcards! {
-    // Card [Types]
-    Copper [Money],
-    Silver [Money],
-    Gold [Money],
-
-    Cellar [Action],
-    Chapel [Action],
-    Moat [Action],
-    Militia [Action],
-
-    Estate [Victory],
-    Duchy [Victory],
-    Province [Victory],
+    // Card (Cost, Coins, VP[, Debt]) [+ POTION] [Types]
+    Copper (0, 1, 0) [Money],
+    Silver (3, 2, 0) [Money],
+    Gold (6, 3, 0) [Money],
+    Platinum (9, 5, 0) [Money],
+    Potion (4, 0, 0) [Money],
+
+    Cellar (2, 0, 0) [Action],
+    Chapel (2, 0, 0) [Action],
+    Moat (2, 0, 0) [Action, Reaction],
+    Chancellor (3, 0, 0) [Action],
+    Village (3, 0, 0) [Action],
+    Woodcutter (3, 0, 0) [Action],
+    Workshop (3, 0, 0) [Action],
+    Bureaucrat (4, 0, 0) [Action, Attack],
+    Feast (4, 0, 0) [Action],
+    Militia (4, 0, 0) [Action, Attack],
+    Moneylender (4, 0, 0) [Action],
+    Remodel (4, 0, 0) [Action],
+    Smithy (4, 0, 0) [Action],
+    Spy (4, 0, 0) [Action, Attack],
+    Thief (4, 0, 0) [Action, Attack],
+    ThroneRoom (4, 0, 0) [Action],
+    CouncilRoom (5, 0, 0) [Action],
+    Festival (5, 0, 0) [Action],
+    Laboratory (5, 0, 0) [Action],
+    Library (5, 0, 0) [Action],
+    Market (5, 0, 0) [Action],
+    Mine (5, 0, 0) [Action],
+    Witch (5, 0, 0) [Action, Attack],
+    Adventurer (6, 0, 0) [Action],
+
+    Estate (2, 0, 1) [Victory],
+    Duchy (5, 0, 3) [Victory],
+    Province (8, 0, 6) [Victory],
+    Colony (11, 0, 10) [Victory],
+    Gardens (4, 0, 0) [Victory],
+
+    Curse (0, 0, -1) [CurseType],
+
+    // Intrigue
+    Courtyard (2, 0, 0) [Action],
+    Pawn (2, 0, 0) [Action],
+    SecretChamber (2, 0, 0) [Action, Reaction],
+    GreatHall (3, 0, 1) [Action, Victory],
+    Masquerade (3, 0, 0) [Action],
+    ShantyTown (3, 0, 0) [Action],
+    Steward (3, 0, 0) [Action],
+    Swindler (3, 0, 0) [Action, Attack],
+    WishingWell (3, 0, 0) [Action],
+    Baron (4, 0, 0) [Action],
+    Bridge (4, 0, 0) [Action],
+    Conspirator (4, 0, 0) [Action],
+    Coppersmith (4, 0, 0) [Action],
+    Ironworks (4, 0, 0) [Action],
+    MiningVillage (4, 0, 0) [Action],
+    Scout (4, 0, 0) [Action],
+    Duke (5, 0, 0) [Victory],
+    Minion (5, 0, 0) [Action, Attack],
+    Saboteur (5, 0, 0) [Action, Attack],
+    Torturer (5, 0, 0) [Action, Attack],
+    TradingPost (5, 0, 0) [Action],
+    Tribute (5, 0, 0) [Action],
+    Upgrade (5, 0, 0) [Action],
+    Harem (6, 2, 2) [Money, Victory],
+    Nobles (6, 0, 2) [Action, Victory],
+
+    // Seaside
+    Embargo (2, 0, 0) [Action],
+    Lighthouse (2, 0, 0) [Action, Duration],
+    NativeVillage (2, 0, 0) [Action],
+    FishingVillage (3, 0, 0) [Action, Duration],
+    Caravan (4, 0, 0) [Action, Duration],
+    Island (4, 0, 2) [Action, Victory],
+    MerchantShip (5, 0, 0) [Action, Duration],
+    Outpost (5, 0, 0) [Action, Duration],
+    Tactician (5, 0, 0) [Action, Duration],
+    Wharf (5, 0, 0) [Action, Duration],
+
+    // Prosperity
+    Loan (3, 1, 0) [Money],
+    Watchtower (3, 0, 0) [Action, Reaction],
+    Talisman (4, 1, 0) [Money],
+    Quarry (4, 1, 0) [Money],
+    WorkersVillage (4, 0, 0) [Action],
+    Monument (4, 0, 0) [Action],
+    Bishop (4, 0, 0) [Action],
+    City (5, 0, 0) [Action],
+    Contraband (5, 3, 0) [Money],
+    CountingHouse (5, 0, 0) [Action],
+    Venture (5, 1, 0) [Money],
+    Mint (5, 0, 0) [Action],
+    Mountebank (5, 0, 0) [Action, Attack],
+    Rabble (5, 0, 0) [Action, Attack],
+    RoyalSeal (5, 2, 0) [Money],
+    Vault (5, 0, 0) [Action],
+    Bank (7, 0, 0) [Money],
+    Hoard (6, 2, 0) [Money],
+    Goons (6, 0, 0) [Action, Attack],
+    GrandMarket (6, 0, 0) [Action],
+    Expand (7, 0, 0) [Action],
+    Forge (7, 0, 0) [Action],
+    KingsCourt (7, 0, 0) [Action],
+    Peddler (8, 0, 0) [Action],
+
+    // Alchemy
+    Transmute (0, 0, 0) + POTION [Action],
+    Vineyard (0, 0, 0) + POTION [Victory],
+    Herbalist (2, 0, 0) [Action],
+    Apothecary (2, 0, 0) + POTION [Action],
+    ScryingPool (2, 0, 0) + POTION [Action, Attack],
+    University (2, 0, 0) + POTION [Action],
+    Alchemist (3, 0, 0) + POTION [Action],
+    Familiar (3, 0, 0) + POTION [Action, Attack],
+    PhilosophersStone (3, 0, 0) + POTION [Money],
+    Golem (4, 0, 0) + POTION [Action],
+    Apprentice (5, 0, 0) [Action],
+    Possession (6, 0, 0) + POTION [Action],
+
+    // Cornucopia
+    Hamlet (2, 0, 0) [Action],
+    FortuneTeller (3, 0, 0) [Action, Attack],
+    Menagerie (3, 0, 0) [Action],
+    FarmingVillage (4, 0, 0) [Action],
+    HorseTraders (4, 0, 0) [Action, Reaction],
+    Remake (4, 0, 0) [Action],
+    Tournament (4, 0, 0) [Action],
+    YoungWitch (4, 0, 0) [Action, Attack],
+    Harvest (5, 0, 0) [Action],
+    HornOfPlenty (5, 0, 0) [Money],
+    HuntingParty (5, 0, 0) [Action],
+    Jester (5, 0, 0) [Action, Attack],
+    Fairgrounds (6, 0, 0) [Victory],
+    BagOfGold (0, 0, 0) [Action, Prize],
+    Diadem (0, 2, 0) [Money, Prize],
+    Followers (0, 0, 0) [Action, Attack, Prize],
+    Princess (0, 0, 0) [Action, Prize],
+    TrustySteed (0, 0, 0) [Action, Prize],
+
+    // Guilds
+    CandlestickMaker (2, 0, 0) [Action],
+    Stonemason (2, 0, 0) [Action],
+    Doctor (3, 0, 0) [Action],
+    Masterpiece (3, 1, 0) [Money],
+    Advisor (4, 0, 0) [Action],
+    Plaza (4, 0, 0) [Action],
+    Taxman (4, 0, 0) [Action, Attack],
+    Herald (4, 0, 0) [Action],
+    Baker (5, 0, 0) [Action],
+    Butcher (5, 0, 0) [Action],
+    Journeyman (5, 0, 0) [Action],
+    MerchantGuild (5, 0, 0) [Action],
+    Soothsayer (5, 0, 0) [Action, Attack],
+
+    // Hinterlands
+    Crossroads (2, 0, 0) [Action],
+    Duchess (2, 0, 0) [Action],
+    FoolsGold (2, 0, 0) [Money, Reaction],
+    Develop (3, 0, 0) [Action],
+    Oasis (3, 0, 0) [Action],
+    Oracle (3, 0, 0) [Action, Attack],
+    Scheme (3, 0, 0) [Action],
+    JackOfAllTrades (4, 0, 0) [Action],
+    NomadCamp (4, 0, 0) [Action],
+    SilkRoad (4, 0, 0) [Victory],
+    SpiceMerchant (4, 0, 0) [Action],
+    Trader (4, 0, 0) [Action, Reaction],
+    Cache (5, 3, 0) [Money],
+    Cartographer (5, 0, 0) [Action],
+    Embassy (5, 0, 0) [Action],
+    Haggler (5, 0, 0) [Action],
+    Highway (5, 0, 0) [Action],
+    IllGottenGains (5, 1, 0) [Money],
+    Inn (5, 0, 0) [Action],
+    Mandarin (5, 0, 0) [Action],
+    Margrave (5, 0, 0) [Action, Attack],
+    Stables (5, 0, 0) [Action],
+    BorderVillage (6, 0, 0) [Action],
+    Farmland (6, 0, 2) [Victory],
+
+    // Dark Ages
+    PoorHouse (1, 0, 0) [Action],
+    Squire (2, 0, 0) [Action],
+    Vagrant (2, 0, 0) [Action],
+    Forager (3, 0, 0) [Action],
+    Hermit (3, 0, 0) [Action],
+    Sage (3, 0, 0) [Action],
+    Storeroom (3, 0, 0) [Action],
+    Urchin (3, 0, 0) [Action, Attack],
+    Armory (4, 0, 0) [Action],
+    DeathCart (4, 0, 0) [Action, Looter],
+    Feodum (4, 0, 0) [Victory],
+    Ironmonger (4, 0, 0) [Action],
+    Marauder (4, 0, 0) [Action, Attack, Looter],
+    Rats (4, 0, 0) [Action],
+    Scavenger (4, 0, 0) [Action],
+    WanderingMinstrel (4, 0, 0) [Action],
+    BanditCamp (5, 0, 0) [Action],
+    Catacombs (5, 0, 0) [Action],
+    Count (5, 0, 0) [Action],
+    Cultist (5, 0, 0) [Action, Attack, Looter],
+    Graverobber (5, 0, 0) [Action],
+    JunkDealer (5, 0, 0) [Action],
+    Mystic (5, 0, 0) [Action],
+    Pillage (5, 0, 0) [Action, Attack],
+    Rebuild (5, 0, 0) [Action],
+    Rogue (5, 0, 0) [Action, Attack],
+    Altar (6, 0, 0) [Action],
+    HuntingGrounds (6, 0, 0) [Action],
+    // Stands in for the Knights pile when the kingdom is chosen.
+    Knights (5, 0, 0) [Action, Attack, Knight],
+    DameAnna (5, 0, 0) [Action, Attack, Knight],
+    DameJosephine (5, 0, 2) [Action, Attack, Knight, Victory],
+    DameMolly (5, 0, 0) [Action, Attack, Knight],
+    DameNatalie (5, 0, 0) [Action, Attack, Knight],
+    DameSylvia (5, 0, 0) [Action, Attack, Knight],
+    SirBailey (5, 0, 0) [Action, Attack, Knight],
+    SirDestry (5, 0, 0) [Action, Attack, Knight],
+    SirMartin (4, 0, 0) [Action, Attack, Knight],
+    SirMichael (5, 0, 0) [Action, Attack, Knight],
+    SirVander (5, 0, 0) [Action, Attack, Knight],
+    AbandonedMine (0, 0, 0) [Action, Ruins],
+    RuinedLibrary (0, 0, 0) [Action, Ruins],
+    RuinedMarket (0, 0, 0) [Action, Ruins],
+    RuinedVillage (0, 0, 0) [Action, Ruins],
+    Survivors (0, 0, 0) [Action, Ruins],
+    Hovel (1, 0, 0) [Reaction, Shelter],
+    Necropolis (1, 0, 0) [Action, Shelter],
+    OvergrownEstate (1, 0, 0) [Victory, Shelter],
+    Spoils (0, 3, 0) [Money],
+    Madman (0, 0, 0) [Action],
+    Mercenary (0, 0, 0) [Action, Attack],
+
+    // Adventures
+    CoinOfTheRealm (2, 1, 0) [Money, Reserve],
+    Page (2, 0, 0) [Action, Traveller],
+    Peasant (2, 0, 0) [Action, Traveller],
+    Ratcatcher (2, 0, 0) [Action, Reserve],
+    Raze (2, 0, 0) [Action],
+    Guide (3, 0, 0) [Action, Reserve],
+    Duplicate (4, 0, 0) [Action, Reserve],
+    Magpie (4, 0, 0) [Action],
+    Miser (4, 0, 0) [Action],
+    Port (4, 0, 0) [Action],
+    Ranger (4, 0, 0) [Action],
+    Transmogrify (4, 0, 0) [Action, Reserve],
+    Artificer (5, 0, 0) [Action],
+    Giant (5, 0, 0) [Action, Attack],
+    LostCity (5, 0, 0) [Action],
+    Relic (5, 2, 0) [Money, Attack],
+    RoyalCarriage (5, 0, 0) [Action, Reserve],
+    Storyteller (5, 0, 0) [Action],
+    TreasureTrove (5, 2, 0) [Money],
+    WineMerchant (5, 0, 0) [Action, Reserve],
+    TreasureHunter (3, 0, 0) [Action, Traveller],
+    Warrior (4, 0, 0) [Action, Attack, Traveller],
+    Hero (5, 0, 0) [Action, Traveller],
+    Champion (6, 0, 0) [Action, Duration],
+    Soldier (3, 0, 0) [Action, Attack, Traveller],
+    Fugitive (4, 0, 0) [Action, Traveller],
+    Disciple (5, 0, 0) [Action, Traveller],
+    Teacher (6, 0, 0) [Action, Reserve],
+    Alms (0, 0, 0) [EventType],
+    Borrow (0, 0, 0) [EventType],
+    Save (1, 0, 0) [EventType],
+    ScoutingParty (2, 0, 0) [EventType],
+    TravellingFair (2, 0, 0) [EventType],
+    Bonfire (3, 0, 0) [EventType],
+    Expedition (3, 0, 0) [EventType],
+    Ferry (3, 0, 0) [EventType],
+    Plan (3, 0, 0) [EventType],
+    Pilgrimage (4, 0, 0) [EventType],
+    Ball (5, 0, 0) [EventType],
+    Raid (5, 0, 0) [EventType],
+    Seaway (5, 0, 0) [EventType],
+    Trade (5, 0, 0) [EventType],
+    LostArts (6, 0, 0) [EventType],
+    Training (6, 0, 0) [EventType],
+    Inheritance (7, 0, 0) [EventType],
+    Pathfinding (8, 0, 0) [EventType],
+
+    // Empires
+    Engineer (0, 0, 0, 4) [Action],
+    CityQuarter (0, 0, 0, 8) [Action],
+    RoyalBlacksmith (0, 0, 0, 8) [Action],
+    Patrician (2, 0, 0) [Action],
+    Settlers (2, 0, 0) [Action],
+    Catapult (3, 0, 0) [Action, Attack],
+    ChariotRace (3, 0, 0) [Action],
+    FarmersMarket (3, 0, 0) [Action, Gathering],
+    Gladiator (3, 0, 0) [Action],
+    Rocks (4, 1, 0) [Money],
+    Sacrifice (4, 0, 0) [Action],
+    Temple (4, 0, 0) [Action, Gathering],
+    Villa (4, 0, 0) [Action],
+    BustlingVillage (5, 0, 0) [Action],
+    Capital (5, 6, 0) [Money],
+    Emporium (5, 0, 0) [Action],
+    Forum (5, 0, 0) [Action],
+    Legionary (5, 0, 0) [Action, Attack],
+    WildHunt (5, 0, 0) [Action, Gathering],
+    Fortune (8, 0, 0, 8) [Money],
+    Advance (0, 0, 0) [EventType],
+    Delve (2, 0, 0) [EventType],
+    Ritual (4, 0, 0) [EventType],
+    SaltTheEarth (4, 0, 0) [EventType],
+    Wedding (4, 0, 0, 3) [EventType],
+    Windfall (5, 0, 0) [EventType],
+    Dominate (14, 0, 0) [EventType],
+    Triumph (0, 0, 0, 5) [EventType],
+    Annex (0, 0, 0, 8) [EventType],
+    BanditFort (0, 0, 0) [Landmark],
+    Fountain (0, 0, 0) [Landmark],
+    Keep (0, 0, 0) [Landmark],
+    Museum (0, 0, 0) [Landmark],
+    Obelisk (0, 0, 0) [Landmark],
+    Orchard (0, 0, 0) [Landmark],
+    Palace (0, 0, 0) [Landmark],
+    Tower (0, 0, 0) [Landmark],
+    TriumphalArch (0, 0, 0) [Landmark],
+    Wall (0, 0, 0) [Landmark],
+    WolfDen (0, 0, 0) [Landmark],
 }
 
 impl Card {
-    pub fn play<'a, T: Iterator<&'a mut PlayerHandle>>(&self, player: &mut PlayerHandle, state: &mut GameState, opponents: T, pending: Option<PendingPlay>) -> response::Response {
-        macro_rules! complete_when(
-            ($card:expr, $f:expr) => ({
-                let (play_complete_chan, play_complete_recv) = channel();
-                let (play_complete_resp_chan, play_complete_resp_recv) = channel();
-                let i = player.play_complete.len();
-                player.play_complete.push((play_complete_resp_chan, play_complete_recv));
-                response::Response::incomplete($card, PendingPlay::new(i), (play_complete_chan, play_complete_resp_recv), $f)
-            })
-        )
+    pub fn is_action(&self) -> bool {
+        self.has_type(Action)
+    }
 
-        if self.is_action() {
+    pub fn is_money(&self) -> bool {
+        self.has_type(Money)
+    }
+
+    pub fn is_victory(&self) -> bool {
+        self.has_type(Victory)
+    }
+
+    pub fn is_curse(&self) -> bool {
+        self.has_type(CurseType)
+    }
+
+    /// The number of victory points this card is worth at the end of the
+    /// game, given every card owned by the same player.
+    pub fn victory_points(&self, deck: &[Card]) -> int {
+        match *self {
+            Gardens => (deck.len() / 10) as int,
+            Duke => deck.iter().filter(|x| **x == Duchy).count() as int,
+            Vineyard => (deck.iter().filter(|x| x.is_action()).count() / 3) as int,
+            Fairgrounds => (2 * (::sets::cornucopia::differently_named(deck) / 5)) as int,
+            SilkRoad => (deck.iter().filter(|x| x.is_victory()).count() / 4) as int,
+            Feodum => (deck.iter().filter(|x| **x == Silver).count() / 3) as int,
+            _ => self.printed_victory_points(),
+        }
+    }
+
+    /// Play a card from the player's hand. Action cards can only be played
+    /// in the Action phase and use up one of the player's actions, and playing
+    /// a Money card moves on to the Buy phase.
+    pub fn play(&self, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> response::Response {
+        if !player.has_in_hand(*self) {
+            return response::NotInHand(*self);
+        }
+        if self.plays_as_action(player, state) {
+            if player.phase != phase::ActionPhase {
+                return response::WrongPhase(player.phase);
+            }
             if player.actions == 0 {
                 return response::NotEnoughActions;
             }
             player.actions -= 1;
         } else if self.is_money() {
-            player.actions = 0;
+            match player.phase {
+                phase::ActionPhase => player.enter_phase(phase::BuyPhase, state),
+                phase::BuyPhase => (),
+                phase => return response::WrongPhase(phase),
+            }
         } else {
             return response::DontUnderstand;
         }
+        player.put_in_play(*self);
+        self.play_in_place(player, state, opponents)
+    }
+
+    /// Whether the card is played as an Action. With the Estate token on a
+    /// pile, Estates are copies of that card.
+    fn plays_as_action(&self, player: &PlayerHandle, state: &GameState) -> bool {
+        self.is_action() || (*self == Estate && state.tokens.pile_of(player.id, ::token::EstateToken).is_some())
+    }
+
+    /// Play a card that's already in play, e.g. again for Throne Room, and
+    /// then offer whatever can be called once an Action card has resolved.
+    pub fn play_in_place(&self, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> response::Response {
+        let resp = self.play_effects(player, state, opponents);
+        if self.plays_as_action(player, state) && !resp.is_err() {
+            player.call_reserves(state, ActionResolved(*self));
+            ::sets::adventures::call_royal_carriages(*self, player, state, opponents);
+        }
+        resp
+    }
+
+    /// Give the coins for a card that's just been put into play, and then
+    /// carry out its effect. Cards that play other cards from somewhere
+    /// besides the hand, like Venture, use this directly.
+    pub fn play_effects(&self, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> response::Response {
+        if self.has_type(Attack) {
+            ::sets::dark_ages::attack_played(player, state, *self);
+        }
+        ::sets::adventures::before_play(player, state, *self);
+        let coins = player.buying_power;
+        player.buying_power += self.coin_value();
+        match *self {
+            Copper => player.buying_power += player.copper_bonus,
+            Potion => player.potions += 1,
+            _ => (),
+        }
+        let resp = self.resolve(player, state, opponents);
+        // The −$1 token takes $1 off the next coins the player gets.
+        if player.minus_coin && player.buying_power > coins {
+            player.buying_power -= 1;
+            player.minus_coin = false;
+        }
+        resp
+    }
 
+    /// Carry out the card's effect, without paying for it or moving it into
+    /// play. Cards that need input from a player ask for it with a
+    /// `Decision` as they go.
+    pub fn resolve(&self, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> response::Response {
+        if self.is_action() {
+            player.actions_played += 1;
+        }
+        player.played.push(*self);
         match *self {
-            Copper => { player.buying_power += 1; response::NoProblem },
-            Silver => { player.buying_power += 2; response::NoProblem },
-            Gold => { player.buying_power += 3; response::NoProblem },
-            Cellar => match pending {
-                Some(x) => ::sets::dominion::cellar(player, x.discarding.as_slice()),
-                None => complete_when!(Cellar, |x| -> bool { x.discarding.len() > 0 }),
+            // Treasures that only give coins have nothing else to do.
+            Copper | Silver | Gold | Platinum | Harem | Hoard | Talisman | Masterpiece | Cache | Rocks | RoyalSeal => response::NoProblem,
+            Cellar => ::sets::dominion::cellar(player, state),
+            Chapel => ::sets::dominion::chapel(player, state),
+            Chancellor => ::sets::dominion::chancellor(player, state),
+            Village => ::sets::dominion::village(player),
+            Woodcutter => ::sets::dominion::woodcutter(player),
+            Workshop => ::sets::dominion::workshop(player, state),
+            Bureaucrat => ::sets::dominion::bureaucrat(player, state, opponents.iter_mut()),
+            Feast => ::sets::dominion::feast(player, state),
+            Militia => ::sets::dominion::militia(player, state, opponents.iter_mut()),
+            Moat => ::sets::dominion::moat(player),
+            Moneylender => ::sets::dominion::moneylender(player, state),
+            Remodel => ::sets::dominion::remodel(player, state),
+            Smithy => ::sets::dominion::smithy(player),
+            Spy => ::sets::dominion::spy(player, state, opponents.iter_mut()),
+            Thief => ::sets::dominion::thief(player, state, opponents.iter_mut()),
+            ThroneRoom => ::sets::dominion::throne_room(player, state, opponents),
+            CouncilRoom => ::sets::dominion::council_room(player, opponents.iter_mut()),
+            Festival => ::sets::dominion::festival(player),
+            Laboratory => ::sets::dominion::laboratory(player),
+            Library => ::sets::dominion::library(player, state),
+            Market => ::sets::dominion::market(player),
+            Mine => ::sets::dominion::mine(player, state),
+            Witch => ::sets::dominion::witch(player, state, opponents.iter_mut()),
+            Adventurer => ::sets::dominion::adventurer(player),
+
+            Courtyard => ::sets::intrigue::courtyard(player, state),
+            Pawn => ::sets::intrigue::pawn(player, state),
+            SecretChamber => ::sets::intrigue::secret_chamber(player, state),
+            GreatHall => ::sets::intrigue::great_hall(player),
+            Masquerade => ::sets::intrigue::masquerade(player, state, opponents),
+            ShantyTown => ::sets::intrigue::shanty_town(player),
+            Steward => ::sets::intrigue::steward(player, state),
+            Swindler => ::sets::intrigue::swindler(player, state, opponents.iter_mut()),
+            WishingWell => ::sets::intrigue::wishing_well(player, state),
+            Baron => ::sets::intrigue::baron(player, state),
+            Bridge => ::sets::intrigue::bridge(player, state),
+            Conspirator => ::sets::intrigue::conspirator(player),
+            Coppersmith => ::sets::intrigue::coppersmith(player),
+            Ironworks => ::sets::intrigue::ironworks(player, state),
+            MiningVillage => ::sets::intrigue::mining_village(player, state),
+            Scout => ::sets::intrigue::scout(player, state),
+            Minion => ::sets::intrigue::minion(player, state, opponents.iter_mut()),
+            Saboteur => ::sets::intrigue::saboteur(state, opponents.iter_mut()),
+            Torturer => ::sets::intrigue::torturer(player, state, opponents.iter_mut()),
+            TradingPost => ::sets::intrigue::trading_post(player, state),
+            Tribute => ::sets::intrigue::tribute(player, opponents),
+            Upgrade => ::sets::intrigue::upgrade(player, state),
+            Nobles => ::sets::intrigue::nobles(player, state),
+
+            Embargo => ::sets::seaside::embargo(player, state),
+            Lighthouse => ::sets::seaside::lighthouse(player),
+            NativeVillage => ::sets::seaside::native_village(player, state),
+            FishingVillage => ::sets::seaside::fishing_village(player),
+            Caravan => ::sets::seaside::caravan(player),
+            Island => ::sets::seaside::island(player, state),
+            MerchantShip => ::sets::seaside::merchant_ship(player),
+            Outpost => ::sets::seaside::outpost(player),
+            Tactician => ::sets::seaside::tactician(player),
+            Wharf => ::sets::seaside::wharf(player),
+
+            Loan => ::sets::prosperity::loan(player, state),
+            Watchtower => ::sets::prosperity::watchtower(player),
+            Quarry => ::sets::prosperity::quarry(state),
+            Monument => ::sets::prosperity::monument(player),
+            Bishop => ::sets::prosperity::bishop(player, state, opponents.iter_mut()),
+            Contraband => ::sets::prosperity::contraband(player, state, opponents),
+            Venture => ::sets::prosperity::venture(player, state, opponents),
+            Mint => ::sets::prosperity::mint(player, state),
+            Mountebank => ::sets::prosperity::mountebank(player, state, opponents.iter_mut()),
+            Bank => ::sets::prosperity::bank(player),
+            Goons => ::sets::prosperity::goons(player, state, opponents.iter_mut()),
+            Peddler => ::sets::prosperity::peddler(player),
+            WorkersVillage => ::sets::prosperity::workers_village(player),
+            City => ::sets::prosperity::city(player, state),
+            CountingHouse => ::sets::prosperity::counting_house(player, state),
+            Rabble => ::sets::prosperity::rabble(player, state, opponents.iter_mut()),
+            Vault => ::sets::prosperity::vault(player, state, opponents.iter_mut()),
+            GrandMarket => ::sets::prosperity::grand_market(player),
+            Expand => ::sets::prosperity::expand(player, state),
+            Forge => ::sets::prosperity::forge(player, state),
+            KingsCourt => ::sets::prosperity::kings_court(player, state, opponents),
+
+            // Potion's potion is counted along with the coins.
+            Potion => response::NoProblem,
+            Transmute => ::sets::alchemy::transmute(player, state),
+            Herbalist => ::sets::alchemy::herbalist(player),
+            Apothecary => ::sets::alchemy::apothecary(player, state),
+            ScryingPool => ::sets::alchemy::scrying_pool(player, state, opponents.iter_mut()),
+            University => ::sets::alchemy::university(player, state),
+            Alchemist => ::sets::alchemy::alchemist(player),
+            Familiar => ::sets::alchemy::familiar(player, state, opponents.iter_mut()),
+            PhilosophersStone => ::sets::alchemy::philosophers_stone(player),
+            Golem => ::sets::alchemy::golem(player, state, opponents),
+            Apprentice => ::sets::alchemy::apprentice(player, state),
+            Possession => ::sets::alchemy::possession(player),
+
+            Hamlet => ::sets::cornucopia::hamlet(player, state),
+            FortuneTeller => ::sets::cornucopia::fortune_teller(player, state, opponents.iter_mut()),
+            Menagerie => ::sets::cornucopia::menagerie(player),
+            FarmingVillage => ::sets::cornucopia::farming_village(player),
+            HorseTraders => ::sets::cornucopia::horse_traders(player, state),
+            Remake => ::sets::cornucopia::remake(player, state),
+            Tournament => ::sets::cornucopia::tournament(player, state, opponents.iter_mut()),
+            YoungWitch => ::sets::cornucopia::young_witch(player, state, opponents.iter_mut()),
+            Harvest => ::sets::cornucopia::harvest(player),
+            HornOfPlenty => ::sets::cornucopia::horn_of_plenty(player, state),
+            HuntingParty => ::sets::cornucopia::hunting_party(player),
+            Jester => ::sets::cornucopia::jester(player, state, opponents.iter_mut()),
+            BagOfGold => ::sets::cornucopia::bag_of_gold(player, state),
+            Diadem => ::sets::cornucopia::diadem(player),
+            Followers => ::sets::cornucopia::followers(player, state, opponents.iter_mut()),
+            Princess => ::sets::cornucopia::princess(player, state),
+            TrustySteed => ::sets::cornucopia::trusty_steed(player, state),
+
+            CandlestickMaker => ::sets::guilds::candlestick_maker(player),
+            Stonemason => ::sets::guilds::stonemason(player, state),
+            Doctor => ::sets::guilds::doctor(player, state),
+            Advisor => ::sets::guilds::advisor(player, state, opponents),
+            Plaza => ::sets::guilds::plaza(player, state),
+            Taxman => ::sets::guilds::taxman(player, state, opponents.iter_mut()),
+            Herald => ::sets::guilds::herald(player, state, opponents),
+            Baker => ::sets::guilds::baker(player),
+            Butcher => ::sets::guilds::butcher(player, state),
+            Journeyman => ::sets::guilds::journeyman(player, state),
+            MerchantGuild => ::sets::guilds::merchant_guild(player),
+            Soothsayer => ::sets::guilds::soothsayer(player, state, opponents.iter_mut()),
+
+            Crossroads => ::sets::hinterlands::crossroads(player),
+            Duchess => ::sets::hinterlands::duchess(player, state, opponents.iter_mut()),
+            FoolsGold => ::sets::hinterlands::fools_gold(player),
+            Develop => ::sets::hinterlands::develop(player, state),
+            Oasis => ::sets::hinterlands::oasis(player, state),
+            Oracle => ::sets::hinterlands::oracle(player, state, opponents.iter_mut()),
+            Scheme => ::sets::hinterlands::scheme(player),
+            JackOfAllTrades => ::sets::hinterlands::jack_of_all_trades(player, state),
+            NomadCamp => ::sets::hinterlands::nomad_camp(player),
+            SpiceMerchant => ::sets::hinterlands::spice_merchant(player, state),
+            Trader => ::sets::hinterlands::trader(player, state),
+            Cartographer => ::sets::hinterlands::cartographer(player, state),
+            Embassy => ::sets::hinterlands::embassy(player, state),
+            Haggler => ::sets::hinterlands::haggler(player),
+            Highway => ::sets::hinterlands::highway(player, state),
+            IllGottenGains => ::sets::hinterlands::ill_gotten_gains(player, state),
+            Inn => ::sets::hinterlands::inn(player, state),
+            Mandarin => ::sets::hinterlands::mandarin(player, state),
+            Margrave => ::sets::hinterlands::margrave(player, state, opponents.iter_mut()),
+            Stables => ::sets::hinterlands::stables(player, state),
+            BorderVillage => ::sets::hinterlands::border_village(player),
+
+            PoorHouse => ::sets::dark_ages::poor_house(player),
+            Squire => ::sets::dark_ages::squire(player, state),
+            Vagrant => ::sets::dark_ages::vagrant(player),
+            Forager => ::sets::dark_ages::forager(player, state),
+            Hermit => ::sets::dark_ages::hermit(player, state),
+            Sage => ::sets::dark_ages::sage(player, state),
+            Storeroom => ::sets::dark_ages::storeroom(player, state),
+            Urchin => ::sets::dark_ages::urchin(player, state, opponents.iter_mut()),
+            Armory => ::sets::dark_ages::armory(player, state),
+            DeathCart => ::sets::dark_ages::death_cart(player, state),
+            Ironmonger => ::sets::dark_ages::ironmonger(player, state),
+            Marauder => ::sets::dark_ages::marauder(player, state, opponents.iter_mut()),
+            Rats => ::sets::dark_ages::rats(player, state),
+            Scavenger => ::sets::dark_ages::scavenger(player, state),
+            WanderingMinstrel => ::sets::dark_ages::wandering_minstrel(player, state),
+            BanditCamp => ::sets::dark_ages::bandit_camp(player, state),
+            Catacombs => ::sets::dark_ages::catacombs(player, state),
+            Count => ::sets::dark_ages::count(player, state),
+            Cultist => ::sets::dark_ages::cultist(player, state, opponents),
+            Graverobber => ::sets::dark_ages::graverobber(player, state),
+            JunkDealer => ::sets::dark_ages::junk_dealer(player, state),
+            Mystic => ::sets::dark_ages::mystic(player, state),
+            Pillage => ::sets::dark_ages::pillage(player, state, opponents.iter_mut()),
+            Rebuild => ::sets::dark_ages::rebuild(player, state),
+            Rogue => ::sets::dark_ages::rogue(player, state, opponents.iter_mut()),
+            Altar => ::sets::dark_ages::altar(player, state),
+            HuntingGrounds => ::sets::dark_ages::hunting_grounds(player),
+            DameAnna | DameJosephine | DameMolly | DameNatalie | DameSylvia | SirBailey | SirDestry | SirMartin |
+            SirMichael | SirVander => ::sets::dark_ages::knight(*self, player, state, opponents.iter_mut()),
+            AbandonedMine | RuinedLibrary | RuinedMarket | RuinedVillage | Survivors => ::sets::dark_ages::ruins(*self, player, state),
+            Necropolis => ::sets::dark_ages::necropolis(player),
+            Spoils => ::sets::dark_ages::spoils(player, state),
+            Madman => ::sets::dark_ages::madman(player, state),
+            Mercenary => ::sets::dark_ages::mercenary(player, state, opponents.iter_mut()),
+
+            CoinOfTheRealm | Duplicate | Teacher => ::sets::adventures::to_tavern(*self, player),
+            Page => ::sets::adventures::page(player),
+            Peasant => ::sets::adventures::peasant(player),
+            Ratcatcher | Guide => ::sets::adventures::ratcatcher(*self, player),
+            Raze => ::sets::adventures::raze(player, state),
+            Magpie => ::sets::adventures::magpie(player, state),
+            Miser => ::sets::adventures::miser(player, state),
+            Port => ::sets::adventures::port(player),
+            Ranger => ::sets::adventures::ranger(player),
+            Transmogrify => ::sets::adventures::transmogrify(player),
+            Artificer => ::sets::adventures::artificer(player, state),
+            Giant => ::sets::adventures::giant(player, state, opponents.iter_mut()),
+            LostCity => ::sets::adventures::lost_city(player),
+            Relic => ::sets::adventures::relic(state, opponents.iter_mut()),
+            RoyalCarriage => ::sets::adventures::royal_carriage(player),
+            Storyteller => ::sets::adventures::storyteller(player, state, opponents),
+            TreasureTrove => ::sets::adventures::treasure_trove(player, state),
+            WineMerchant => ::sets::adventures::wine_merchant(player),
+            TreasureHunter => ::sets::adventures::treasure_hunter(player, state, opponents),
+            Warrior => ::sets::adventures::warrior(player, state, opponents.iter_mut()),
+            Hero => ::sets::adventures::hero(player, state),
+            Champion => ::sets::adventures::champion(player),
+            Soldier => ::sets::adventures::soldier(player, state, opponents.iter_mut()),
+            Fugitive => ::sets::adventures::fugitive(player, state),
+            Disciple => ::sets::adventures::disciple(player, state, opponents),
+            Estate => ::sets::adventures::estate(player, state, opponents),
+
+            Engineer => ::sets::empires::engineer(player, state),
+            CityQuarter => ::sets::empires::city_quarter(player),
+            RoyalBlacksmith => ::sets::empires::royal_blacksmith(player),
+            Patrician => ::sets::empires::patrician(player, state),
+            Settlers => ::sets::empires::settlers(player, state),
+            Catapult => ::sets::empires::catapult(player, state, opponents.iter_mut()),
+            ChariotRace => ::sets::empires::chariot_race(player, state, opponents),
+            FarmersMarket => ::sets::empires::farmers_market(player, state),
+            Gladiator => ::sets::empires::gladiator(player, state, opponents),
+            Sacrifice => ::sets::empires::sacrifice(player, state),
+            Temple => ::sets::empires::temple(player, state),
+            Villa => ::sets::empires::villa(player),
+            BustlingVillage => ::sets::empires::bustling_village(player, state),
+            Capital => ::sets::empires::capital(player),
+            Emporium => ::sets::empires::emporium(player),
+            Forum => ::sets::empires::forum(player, state),
+            Legionary => ::sets::empires::legionary(player, state, opponents.iter_mut()),
+            WildHunt => ::sets::empires::wild_hunt(player, state),
+            Fortune => ::sets::empires::fortune(player),
+
+            Custom(id) => {
+                // Custom cards only have vanilla effects.
+                let def = registry::get(id);
+                player.draw_n(def.cards.unwrap_or(0));
+                player.actions += def.actions.unwrap_or(0);
+                player.buys += def.buys.unwrap_or(0);
+                if !self.is_money() {
+                    player.buying_power += def.coins.unwrap_or(0);
+                }
+                response::NoProblem
             },
-            Chapel => match pending {
-                Some(x) => ::sets::dominion::chapel(player, state, x.trashing.as_slice()),
-                None => complete_when!(Chapel, |x| -> bool { x.trashing.len() > 0 }),
+
+            Duchy | Province | Colony | Gardens | Duke | Vineyard | Fairgrounds | SilkRoad | Farmland | Feodum |
+            OvergrownEstate | Hovel | Curse => unreachable!(),
+            // Knights is only a placeholder, so it's never in anyone's hand.
+            Knights => unreachable!(),
+            // Events are never in anyone's hand either.
+            Alms | Borrow | ScoutingParty | Bonfire | Ferry | Plan | Pilgrimage | Ball | Raid | Seaway | Trade | LostArts |
+            Training | Inheritance | Pathfinding | Advance | Delve | Ritual | SaltTheEarth | Wedding | Windfall | Dominate |
+            Triumph | Annex => unreachable!(),
+            // Nor are Landmarks.
+            BanditFort | Fountain | Keep | Museum | Obelisk | Orchard | Palace | Tower | TriumphalArch | Wall |
+            WolfDen => unreachable!(),
+        }
+    }
+
+    /// Carry out what a Duration card does at the start of the turn after
+    /// it was played. Cards set aside until the next turn, like Horse
+    /// Traders, come back here too.
+    pub fn resolve_duration(&self, player: &mut PlayerHandle, _state: &mut GameState) -> response::Response {
+        match *self {
+            Lighthouse => ::sets::seaside::lighthouse_next_turn(player),
+            FishingVillage => ::sets::seaside::fishing_village_next_turn(player),
+            Caravan => ::sets::seaside::caravan_next_turn(player),
+            MerchantShip => ::sets::seaside::merchant_ship_next_turn(player),
+            Tactician => ::sets::seaside::tactician_next_turn(player),
+            Wharf => ::sets::seaside::wharf_next_turn(player),
+            HorseTraders => ::sets::cornucopia::horse_traders_next_turn(player),
+            Champion => ::sets::adventures::champion_next_turn(player),
+            // Outpost's extra turn is all it does.
+            _ => response::NoProblem,
+        }
+    }
+
+    /// What this card does when it's discarded from play in Cleanup, e.g.
+    /// Alchemist can go back on top of the deck instead.
+    pub fn on_discard_from_play(&self, player: &mut PlayerHandle, state: &mut GameState) {
+        match *self {
+            Herbalist => ::sets::alchemy::herbalist_discarded(player, state),
+            Alchemist => ::sets::alchemy::alchemist_discarded(player, state),
+            Scheme => ::sets::hinterlands::scheme_discarded(player, state),
+            Hermit => ::sets::dark_ages::hermit_discarded(player, state),
+            Capital => ::sets::empires::capital_discarded(player),
+            Page | TreasureHunter | Warrior | Hero | Peasant | Soldier | Fugitive | Disciple => {
+                ::sets::adventures::traveller_discarded(*self, player, state)
             },
-            Militia => ::sets::dominion::militia(player, opponents),
-            Moat => ::sets::dominion::moat(player),
+            _ => (),
+        }
+    }
+
+    /// What this card does when it's gained, once it's arrived in `to`.
+    /// `to` is None if a Reaction has moved or trashed it since.
+    pub fn on_gain(&self, player: &mut PlayerHandle, state: &mut GameState, to: Option<Zone>) {
+        match *self {
+            Duchy => ::sets::hinterlands::duchy_gained(player, state),
+            NomadCamp => ::sets::hinterlands::nomad_camp_gained(player, to),
+            Cache => ::sets::hinterlands::cache_gained(player, state),
+            Inn => ::sets::hinterlands::inn_gained(player, state),
+            Mandarin => ::sets::hinterlands::mandarin_gained(player, state),
+            BorderVillage => ::sets::hinterlands::border_village_gained(player, state),
+            DeathCart => ::sets::dark_ages::death_cart_gained(player, state),
+            Rocks => ::sets::empires::rocks_silver(player, state),
+            Temple => ::sets::empires::temple_gained(player, state),
+            Villa => ::sets::empires::villa_gained(player, state, to),
+            Emporium => ::sets::empires::emporium_gained(player),
+            Fortune => ::sets::empires::fortune_gained(player, state),
+            _ => (),
+        }
+    }
+
+    /// What this card does when it's trashed, e.g. Feodum gains Silvers.
+    pub fn on_trash(&self, player: &mut PlayerHandle, state: &mut GameState) {
+        match *self {
+            OvergrownEstate | Rats => { player.draw(); },
+            Cultist => player.draw_n(3),
+            Squire => ::sets::dark_ages::squire_trashed(player, state),
+            Feodum => ::sets::dark_ages::feodum_trashed(player, state),
+            Catacombs => ::sets::dark_ages::catacombs_trashed(player, state),
+            HuntingGrounds => ::sets::dark_ages::hunting_grounds_trashed(player, state),
+            SirVander => { player.gain(state, Gold); },
+            Rocks => ::sets::empires::rocks_silver(player, state),
+            _ => (),
+        }
+    }
+
+    /// What happens to each other player when someone gains this card,
+    /// e.g. Ill-Gotten Gains gives them a Curse.
+    pub fn on_gained_by_other(&self, other: &mut PlayerHandle, state: &mut GameState) {
+        match *self {
+            IllGottenGains => { other.gain(state, Curse); },
+            Embassy => { other.gain(state, Silver); },
+            LostCity => { other.draw(); },
+            _ => (),
+        }
+    }
+
+    /// What this card does when it's bought, after it's been gained.
+    pub fn on_buy(&self, player: &mut PlayerHandle, state: &mut GameState) {
+        match *self {
+            Mint => ::sets::prosperity::mint_bought(player, state),
+            Farmland => ::sets::hinterlands::farmland_bought(player, state),
+            Port => { player.gain(state, Port); },
+            Forum => player.buys += 1,
+            _ => (),
+        }
+    }
+
+    /// Whether this Event can only be bought once a turn.
+    pub fn once_per_turn(&self) -> bool {
+        match *self {
+            Alms | Borrow | Save | Pilgrimage => true,
+            _ => false,
+        }
+    }
+
+    /// What this Event does when it's bought.
+    pub fn resolve_event(&self, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) {
+        match *self {
+            Alms => ::sets::adventures::alms(player, state),
+            Borrow => ::sets::adventures::borrow(player),
+            Save => ::sets::adventures::save(player, state),
+            ScoutingParty => ::sets::adventures::scouting_party(player, state),
+            TravellingFair => ::sets::adventures::travelling_fair(player),
+            Bonfire => ::sets::adventures::bonfire(player, state),
+            Expedition => ::sets::adventures::expedition(player),
+            Ferry => ::sets::adventures::place_token(Ferry, player, state, ::token::MinusCost),
+            Plan => ::sets::adventures::place_token(Plan, player, state, ::token::Trashing),
+            LostArts => ::sets::adventures::place_token(LostArts, player, state, ::token::PlusAction),
+            Training => ::sets::adventures::place_token(Training, player, state, ::token::PlusCoin),
+            Pathfinding => ::sets::adventures::place_token(Pathfinding, player, state, ::token::PlusCard),
+            Pilgrimage => ::sets::adventures::pilgrimage(player, state),
+            Ball => ::sets::adventures::ball(player, state),
+            Raid => ::sets::adventures::raid(player, state, opponents.iter_mut()),
+            Seaway => ::sets::adventures::seaway(player, state),
+            Trade => ::sets::adventures::trade(player, state),
+            Inheritance => ::sets::adventures::inheritance(player, state),
+            Advance => ::sets::empires::advance(player, state),
+            Delve => ::sets::empires::delve(player, state),
+            Ritual => ::sets::empires::ritual(player, state),
+            SaltTheEarth => ::sets::empires::salt_the_earth(player, state),
+            Wedding => ::sets::empires::wedding(player, state),
+            Windfall => ::sets::empires::windfall(player, state),
+            Dominate => ::sets::empires::dominate(player, state),
+            Triumph => ::sets::empires::triumph(player, state),
+            Annex => ::sets::empires::annex(player, state),
+            _ => (),
+        }
+    }
+
+    /// What this card does while it's in play, when its owner buys `bought`.
+    pub fn on_buy_in_play(&self, player: &mut PlayerHandle, state: &mut GameState, bought: Card) {
+        match *self {
+            Talisman => ::sets::prosperity::talisman_buy(player, state, bought),
+            Hoard => ::sets::prosperity::hoard_buy(player, state, bought),
+            Goons => ::sets::prosperity::goons_buy(player),
+            MerchantGuild => ::sets::guilds::merchant_guild_buy(player),
+            Haggler => ::sets::hinterlands::haggler_buy(player, state, bought),
+            _ => (),
+        }
+    }
+
+    /// Whether the player can pay more than this card costs when they buy
+    /// it, for some extra effect.
+    pub fn can_overpay(&self) -> bool {
+        match *self {
+            Stonemason | Doctor | Masterpiece | Herald => true,
+            _ => false,
+        }
+    }
+
+    /// What this card does when it's bought for `amount` more than it
+    /// costs.
+    pub fn on_overpay(&self, player: &mut PlayerHandle, state: &mut GameState, amount: uint) {
+        match *self {
+            Stonemason => ::sets::guilds::stonemason_overpaid(player, state, amount),
+            Doctor => ::sets::guilds::doctor_overpaid(player, state, amount),
+            Masterpiece => ::sets::guilds::masterpiece_overpaid(player, state, amount),
+            Herald => ::sets::guilds::herald_overpaid(player, state, amount),
+            _ => (),
+        }
+    }
+
+    /// Whether this card can be revealed from hand in response to
+    /// `trigger`.
+    pub fn reacts_to(&self, trigger: &Trigger) -> bool {
+        match (*self, trigger) {
+            (Moat, &Attacked(_)) | (SecretChamber, &Attacked(_)) | (HorseTraders, &Attacked(_)) => true,
+            (Watchtower, &Gained(..)) | (FoolsGold, &OtherGained(Province)) => true,
+            (Hovel, &Bought(card)) => card.is_victory(),
+            // Trader would only swap a Silver for another Silver.
+            (Trader, &WouldGain(card)) => card != Silver,
+            _ => false,
+        }
+    }
+
+    /// Carry out this card's reaction, once it's been revealed.
+    pub fn react(&self, player: &mut PlayerHandle, state: &mut GameState, trigger: &Trigger) -> Outcome {
+        match (*self, trigger) {
+            (Moat, _) => Blocked,
+            (SecretChamber, _) => ::sets::intrigue::secret_chamber_react(player, state),
+            (HorseTraders, _) => ::sets::cornucopia::horse_traders_react(player),
+            (Watchtower, &Gained(card, zone)) => ::sets::prosperity::watchtower_react(player, state, card, zone),
+            (FoolsGold, _) => ::sets::hinterlands::fools_gold_react(player, state),
+            (Trader, _) => ::sets::hinterlands::trader_react(player, state),
+            (Hovel, _) => ::sets::dark_ages::hovel_react(player, state),
+            _ => NotBlocked,
+        }
+    }
+
+    /// Whether this card can be called from the Tavern mat in response to
+    /// `trigger`.
+    pub fn can_call(&self, player: &PlayerHandle, state: &GameState, trigger: &Trigger) -> bool {
+        match (*self, trigger) {
+            (CoinOfTheRealm, &ActionResolved(_)) => true,
+            (Ratcatcher, &TurnStarted) | (Guide, &TurnStarted) | (Transmogrify, &TurnStarted) | (Teacher, &TurnStarted) => true,
+            (Duplicate, &Gained(card, _)) => state.can_take(card) && state.cost(card) <= Cost::coins(6),
+            (WineMerchant, &BuyPhaseEnded) => player.buying_power >= 2,
+            _ => false,
+        }
+    }
+
+    /// What this card does when it's called from the Tavern mat. Most
+    /// cards go into play, to be discarded in Cleanup.
+    pub fn call(&self, player: &mut PlayerHandle, state: &mut GameState, trigger: &Trigger) {
+        match (*self, trigger) {
+            (CoinOfTheRealm, _) => ::sets::adventures::coin_of_the_realm_called(player),
+            (Ratcatcher, _) => ::sets::adventures::ratcatcher_called(player, state),
+            (Guide, _) => ::sets::adventures::guide_called(player),
+            (Transmogrify, _) => ::sets::adventures::transmogrify_called(player, state),
+            (Teacher, _) => ::sets::adventures::teacher_called(player, state),
+            (Duplicate, &Gained(card, _)) => ::sets::adventures::duplicate_called(player, state, card),
+            (WineMerchant, _) => ::sets::adventures::wine_merchant_called(player),
+            _ => (),
+        }
+    }
+}
 
-            Estate | Duchy | Province => unreachable!(),
+/// Landmarks that change how the game is scored are scoring rules
+/// themselves. Obelisk needs to know which pile it picked, so its rule comes
+/// from `sets::empires::scoring_rule()` instead.
+impl ScoringRule for Card {
+    fn score(&self, sheet: &ScoreSheet) -> int {
+        match *self {
+            BanditFort => ::sets::empires::bandit_fort(sheet.cards),
+            Fountain => ::sets::empires::fountain(sheet.cards),
+            Keep => ::sets::empires::keep(sheet),
+            Museum => ::sets::empires::museum(sheet.cards),
+            Orchard => ::sets::empires::orchard(sheet.cards),
+            Palace => ::sets::empires::palace(sheet.cards),
+            Tower => ::sets::empires::tower(sheet),
+            TriumphalArch => ::sets::empires::triumphal_arch(sheet.cards),
+            Wall => ::sets::empires::wall(sheet.cards),
+            WolfDen => ::sets::empires::wolf_den(sheet.cards),
+            _ => 0,
         }
     }
 }
+
+#[cfg(test)]
+#[path = "tests/card.rs"]
+mod tests;
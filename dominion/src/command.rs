@@ -2,6 +2,10 @@ use super::card::Card;
 
 pub enum Command {
     Buy(Card),
+    BuyEvent(Card),
+    EndPhase,
+    PayDebt(uint),
     Play(Card),
     PlayAllMoney,
+    SpendCoffers(uint),
 }
@@ -0,0 +1,297 @@
+//! Options for setting up a game.
+
+use std::collections::HashMap;
+use std::default::Default;
+use card::*;
+use cost::Cost;
+use rng::GameRng;
+use scoring::ScoringRule;
+use sets;
+
+/// The number of different kingdom cards used in each game.
+pub static KINGDOM_SIZE: uint = 10;
+
+/// The most players a game can have. Their starting decks all come out of
+/// the 60 Coppers.
+pub static MAX_PLAYERS: uint = 8;
+
+/// Game configuration. By default, games use a random kingdom chosen from
+/// the base set and a random seed.
+#[deriving(Clone)]
+pub struct GameConfig {
+    kingdom: Vec<Card>,
+    seed: Option<u64>,
+    sets: Vec<sets::Set>,
+    colonies: Option<bool>,
+    bane: Option<Card>,
+    shelters: Option<bool>,
+    events: Vec<Card>,
+    landmarks: Vec<Card>,
+}
+
+/// Everything that's laid out before a game starts.
+pub struct Setup {
+    /// The supply piles, and how many cards each one starts with.
+    pub supply: HashMap<Card, uint>,
+
+    /// Piles that aren't part of the supply, e.g. Tournament's Prizes.
+    pub non_supply: HashMap<Card, uint>,
+
+    /// The extra kingdom pile for Young Witch. It's also in `supply`.
+    pub bane: Option<Card>,
+
+    /// Supply piles made of different cards, e.g. Ruins and Knights, top
+    /// card first. Each one is in `supply` under its top card.
+    pub mixed_piles: Vec<Vec<Card>>,
+
+    /// Whether everyone starts with Shelters instead of Estates.
+    pub shelters: bool,
+
+    /// The Events that can be bought, e.g. Ball.
+    pub events: Vec<Card>,
+
+    /// The Landmarks in play, e.g. Wall.
+    pub landmarks: Vec<Card>,
+
+    /// The scoring rules for the Landmarks, e.g. Obelisk with the pile it
+    /// picked.
+    pub scoring: Vec<Box<ScoringRule + 'static>>,
+}
+
+/// Why a game couldn't be set up.
+#[deriving(PartialEq, Show)]
+pub enum SetupError {
+    /// Games need at least one player, and at most `MAX_PLAYERS`.
+    WrongPlayerCount(uint),
+
+    /// The chosen Bane doesn't cost $2 or $3, or it's in the kingdom
+    /// already.
+    BadBane(Card),
+}
+
+impl Default for GameConfig {
+    fn default() -> GameConfig {
+        GameConfig::new()
+    }
+}
+
+impl GameConfig {
+    pub fn new() -> GameConfig {
+        GameConfig{kingdom: Vec::new(), seed: None, sets: vec![sets::Dominion], colonies: None, bane: None, shelters: None,
+                   events: Vec::new(), landmarks: Vec::new()}
+    }
+
+    /// Include specific cards in the kingdom. If fewer than 10 cards are
+    /// supplied, then the rest will be randomly chosen from the enabled
+    /// sets. Anything over 10 will be ignored.
+    pub fn kingdom(mut self, cards: Vec<Card>) -> GameConfig {
+        self.kingdom = cards;
+        self
+    }
+
+    /// Seed the game's random number generator, which is used for every
+    /// shuffle and random choice. The same seed with the same players will
+    /// always play out the same game.
+    pub fn seed(mut self, seed: u64) -> GameConfig {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The seed to play with, if one was chosen.
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Choose which sets random kingdom cards are taken from.
+    pub fn sets(mut self, sets: Vec<sets::Set>) -> GameConfig {
+        self.sets = sets;
+        self
+    }
+
+    /// Choose whether Platinum and Colony are added to the supply. By
+    /// default, they're only used when the kingdom has a Prosperity card.
+    pub fn colonies(mut self, colonies: bool) -> GameConfig {
+        self.colonies = Some(colonies);
+        self
+    }
+
+    /// Choose whether players start with Shelters instead of Estates. By
+    /// default, they do when the kingdom has a Dark Ages card.
+    pub fn shelters(mut self, shelters: bool) -> GameConfig {
+        self.shelters = Some(shelters);
+        self
+    }
+
+    /// Choose the Events that can be bought this game. There aren't any
+    /// unless they're chosen here.
+    pub fn events(mut self, events: Vec<Card>) -> GameConfig {
+        self.events = events;
+        self
+    }
+
+    /// Choose the Landmarks for this game. Like Events, there aren't any
+    /// unless they're chosen here.
+    pub fn landmarks(mut self, landmarks: Vec<Card>) -> GameConfig {
+        self.landmarks = landmarks;
+        self
+    }
+
+    /// Choose the Bane card for Young Witch. It has to cost $2 or $3 and
+    /// can't be one of the other kingdom cards, or the game won't set up.
+    /// It's only used if Young Witch is in the kingdom. By default, it's
+    /// chosen at random from the enabled sets.
+    pub fn bane(mut self, card: Card) -> GameConfig {
+        self.bane = Some(card);
+        self
+    }
+
+    /// Pick the kingdom cards for a game, filling in any that weren't
+    /// chosen explicitly.
+    pub fn build_kingdom(&self, rng: &GameRng) -> Vec<Card> {
+        let mut kingdom: Vec<Card> = Vec::with_capacity(KINGDOM_SIZE);
+        for card in self.kingdom.iter() {
+            if kingdom.len() < KINGDOM_SIZE && !kingdom.contains(card) {
+                kingdom.push(*card);
+            }
+        }
+
+        let mut rest: Vec<Card> = self.sets.iter()
+            .flat_map(|set| set.kingdom().into_iter())
+            .filter(|card| !kingdom.contains(card))
+            .collect();
+        rng.shuffle(rest.as_mut_slice());
+
+        let needed = KINGDOM_SIZE - kingdom.len();
+        kingdom.extend(rest.into_iter().take(needed));
+        kingdom
+    }
+
+    /// Build the supply for a game with `num_players` players, following the
+    /// rulebook's pile sizes.
+    pub fn supply(&self, num_players: uint, rng: &GameRng) -> Result<HashMap<Card, uint>, SetupError> {
+        self.setup(num_players, rng).map(|x| x.supply)
+    }
+
+    /// Lay out everything for a game with `num_players` players: the supply,
+    /// and whatever the kingdom cards need besides.
+    pub fn setup(&self, num_players: uint, rng: &GameRng) -> Result<Setup, SetupError> {
+        if num_players == 0 || num_players > MAX_PLAYERS {
+            return Err(WrongPlayerCount(num_players));
+        }
+        let victory = if num_players > 2 { 12 } else { 8 };
+        let mut supply = HashMap::new();
+
+        // Starting decks come out of the Copper pile.
+        supply.insert(Copper, 60 - 7 * num_players);
+        supply.insert(Silver, 40);
+        supply.insert(Gold, 30);
+        supply.insert(Estate, victory);
+        supply.insert(Duchy, victory);
+        supply.insert(Province, victory);
+        supply.insert(Curse, 10 * (num_players - 1));
+
+        let mut kingdom = self.build_kingdom(rng);
+        let mut non_supply = HashMap::new();
+        let mut bane = None;
+        if kingdom.contains(&YoungWitch) {
+            bane = match self.bane {
+                Some(card) if !can_be_bane(card, kingdom.as_slice()) => return Err(BadBane(card)),
+                Some(card) => Some(card),
+                None => self.pick_bane(kingdom.as_slice(), rng),
+            };
+            kingdom.extend(bane.into_iter());
+        }
+        if kingdom.contains(&Tournament) {
+            for prize in sets::cornucopia::prizes().into_iter() {
+                non_supply.insert(prize, 1);
+            }
+        }
+        // Cards that come with piles of their own outside the supply.
+        for &(card, extra, count) in [(Marauder, Spoils, 15), (BanditCamp, Spoils, 15), (Pillage, Spoils, 15),
+                                      (Hermit, Madman, 10), (Urchin, Mercenary, 10)].iter() {
+            if kingdom.contains(&card) {
+                non_supply.insert(extra, count);
+            }
+        }
+        // Travellers come with the rest of their line, outside the supply.
+        for &(card, ref line) in [(Page, sets::adventures::page_line()), (Peasant, sets::adventures::peasant_line())].iter() {
+            if kingdom.contains(&card) {
+                for traveller in line.iter() {
+                    non_supply.insert(*traveller, 5);
+                }
+            }
+        }
+        let dark_ages = sets::dark_ages::kingdom();
+        let shelters = self.shelters.unwrap_or_else(|| kingdom.iter().any(|x| dark_ages.contains(x)));
+        let mut mixed_piles = Vec::new();
+        if kingdom.iter().any(|x| x.has_type(Looter)) {
+            mixed_piles.push(sets::dark_ages::ruins_pile(num_players, rng));
+        }
+        // Knights stands in for its pile, which has one of each Knight.
+        if let Some(i) = kingdom.iter().position(|x| *x == Knights) {
+            kingdom.remove(i);
+            let mut knights = sets::dark_ages::knights();
+            rng.shuffle(knights.as_mut_slice());
+            mixed_piles.push(knights);
+        }
+        // So does the top card of each split pile.
+        for &(top, bottom) in sets::empires::split_piles().iter() {
+            if let Some(i) = kingdom.iter().position(|x| *x == top) {
+                kingdom.remove(i);
+                let mut pile = Vec::from_elem(5, top);
+                pile.push_all(Vec::from_elem(5, bottom).as_slice());
+                mixed_piles.push(pile);
+            }
+        }
+        for pile in mixed_piles.iter() {
+            if let Some(top) = pile.first() {
+                supply.insert(*top, pile.len());
+            }
+        }
+
+        let prosperity = sets::prosperity::kingdom();
+        if self.colonies.unwrap_or_else(|| kingdom.iter().any(|x| prosperity.contains(x))) {
+            supply.insert(Platinum, 12);
+            supply.insert(Colony, victory);
+        }
+        if kingdom.iter().any(|x| x.cost().potions > 0) {
+            supply.insert(Potion, 16);
+        }
+
+        // Obelisk picks one of the Action piles, whatever's in it.
+        let mut action_piles: Vec<Vec<Card>> = kingdom.iter().filter(|x| x.is_action()).map(|x| vec![*x]).collect();
+        for pile in mixed_piles.iter().filter(|x| x.first().map_or(false, |top| top.is_action())) {
+            let mut cards = pile.clone();
+            cards.sort_by(|a, b| a.name().cmp(b.name()));
+            cards.dedup();
+            action_piles.push(cards);
+        }
+        let scoring = self.landmarks.iter().map(|x| sets::empires::scoring_rule(*x, action_piles.as_slice(), rng)).collect();
+
+        for card in kingdom.into_iter() {
+            supply.insert(card, if card.is_victory() { victory } else { 10 });
+        }
+        Ok(Setup{supply: supply, non_supply: non_supply, bane: bane, mixed_piles: mixed_piles, shelters: shelters,
+                 events: self.events.clone(), landmarks: self.landmarks.clone(), scoring: scoring})
+    }
+
+    /// Pick a random Bane card from the enabled sets that isn't already in
+    /// the kingdom.
+    fn pick_bane(&self, kingdom: &[Card], rng: &GameRng) -> Option<Card> {
+        let mut candidates: Vec<Card> = self.sets.iter()
+            .flat_map(|set| set.kingdom().into_iter())
+            .filter(|card| can_be_bane(*card, kingdom))
+            .collect();
+        rng.shuffle(candidates.as_mut_slice());
+        candidates.into_iter().next()
+    }
+}
+
+/// Whether `card` can be Young Witch's Bane with this kingdom.
+fn can_be_bane(card: Card, kingdom: &[Card]) -> bool {
+    !kingdom.contains(&card) && (card.cost() == Cost::coins(2) || card.cost() == Cost::coins(3))
+}
+
+#[cfg(test)]
+#[path = "tests/config.rs"]
+mod tests;
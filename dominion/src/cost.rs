@@ -0,0 +1,77 @@
+//! What cards cost.
+//!
+//! A cost is made of coins, potions and debt, so costs can't always be
+//! compared: $3 and a potion is more than $3, but it's neither more nor less
+//! than $4. Anything "costing up to" some amount has to cost no more in every
+//! part, which is what `<=` checks.
+
+use std::cmp::{Equal, Greater, Less, Ordering};
+
+#[deriving(Clone, Show, PartialEq, Eq, Hash, Default, Encodable, Decodable)]
+pub struct Cost {
+    pub coins: uint,
+    pub potions: uint,
+    pub debt: uint,
+}
+
+/// A single potion, for the card table, e.g. `Golem (4, 0, 0) + POTION`.
+pub static POTION: uint = 1;
+
+impl Cost {
+    pub fn new(coins: uint, potions: uint, debt: uint) -> Cost {
+        Cost{coins: coins, potions: potions, debt: debt}
+    }
+
+    /// A cost of only coins.
+    pub fn coins(coins: uint) -> Cost {
+        Cost::new(coins, 0, 0)
+    }
+
+    /// More than anything costs, for choosing from any pile at all.
+    pub fn unlimited() -> Cost {
+        Cost::new(::std::uint::MAX, ::std::uint::MAX, ::std::uint::MAX)
+    }
+
+    /// The same cost with `coins` more coins, e.g. for Remodel's "costing
+    /// up to $2 more".
+    pub fn plus(&self, coins: uint) -> Cost {
+        Cost{coins: self.coins + coins, ..*self}
+    }
+
+    /// The same cost with `coins` fewer coins. The coins never go below 0.
+    pub fn minus(&self, coins: uint) -> Cost {
+        Cost{coins: if self.coins > coins { self.coins - coins } else { 0 }, ..*self}
+    }
+
+    /// Returns true if this cost can be paid with `coins` and `potions`.
+    /// Debt is never paid up front; the buyer takes it on instead.
+    pub fn can_pay(&self, coins: uint, potions: uint) -> bool {
+        self.coins <= coins && self.potions <= potions
+    }
+
+    /// A key for sorting costs from cheapest to most expensive, e.g. to
+    /// pick the most expensive card. Coins count first, so it only agrees
+    /// with `<` for costs that can be compared.
+    pub fn sort_key(&self) -> (uint, uint, uint) {
+        (self.coins, self.potions, self.debt)
+    }
+}
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Cost) -> Option<Ordering> {
+        let parts = [self.coins.cmp(&other.coins), self.potions.cmp(&other.potions), self.debt.cmp(&other.debt)];
+        if parts.iter().all(|x| *x == Equal) {
+            Some(Equal)
+        } else if parts.iter().all(|x| *x != Greater) {
+            Some(Less)
+        } else if parts.iter().all(|x| *x != Less) {
+            Some(Greater)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/cost.rs"]
+mod tests;
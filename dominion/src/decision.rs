@@ -0,0 +1,266 @@
+//! Choices the game asks players to make.
+//!
+//! Whenever a card needs input from a player, whether it's the player whose
+//! turn it is or an opponent being attacked, the game sends a `Decision` and
+//! waits for a `Choice`. Every answer is checked here before the card sees
+//! it, so card code can trust whatever it gets back, and new cards can ask
+//! for input without adding anything to the protocol.
+//!
+//! ~~~ignore
+//! match conn.play(Cellar) {
+//!     Decide(ChooseCards{..}) => conn.choose(Cards(vec![Estate, Estate])),
+//!     resp => resp,
+//! };
+//! ~~~
+
+use std::cmp;
+
+use card::{Card, CardType};
+use cost::Cost;
+use response;
+use response::Response;
+use super::GameState;
+
+/// What a player is being asked to do.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Decision {
+    /// Choose between `min` and `max` of the cards in `from`, e.g. the
+    /// cards to discard for Cellar. A card can be chosen as many times as
+    /// it appears in `from`.
+    ChooseCards { card: Card, purpose: Purpose, from: Vec<Card>, min: uint, max: uint },
+
+    /// Answer yes or no, e.g. whether Chancellor should put your deck into
+    /// your discard pile. `subject` is the card being asked about, if
+    /// there is one, and `default` is the answer the game goes with if you
+    /// leave it up to the game.
+    YesNo { card: Card, purpose: Purpose, subject: Option<Card>, default: bool },
+
+    /// Pick one of `options` by its index.
+    ChooseOption { card: Card, options: Vec<String> },
+
+    /// Choose a supply pile whose card costs between `min_cost` and
+    /// `max_cost`, and is of type `only` if that's set, e.g. the card to
+    /// gain with Workshop. The pile can't be empty. Costs with potions
+    /// can't always be compared, so the card has to cost no less than
+    /// `min_cost` and no more than `max_cost` in coins, potions and debt.
+    ChoosePile { card: Card, purpose: Purpose, min_cost: Cost, max_cost: Cost, only: Option<CardType> },
+
+    /// Put all of `cards` in order, e.g. to put them back on your deck.
+    /// The first card ends up on top.
+    OrderCards { card: Card, purpose: Purpose, cards: Vec<Card> },
+}
+
+/// What will happen to whatever's chosen.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Purpose {
+    Calling,
+    Discarding,
+    Embargoing,
+    Gaining,
+    Naming,
+    Passing,
+    PlacingToken,
+    Playing,
+    Revealing,
+    SettingAside,
+    TopDecking,
+    Trashing,
+}
+
+/// A player's answer to a `Decision`.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Choice {
+    /// For ChooseCards.
+    Cards(Vec<Card>),
+
+    /// For YesNo.
+    Yes,
+    No,
+
+    /// For ChooseOption, the index of the chosen option.
+    Pick(uint),
+
+    /// For ChoosePile.
+    Pile(Card),
+
+    /// For OrderCards, from top to bottom.
+    Order(Vec<Card>),
+
+    /// Leave it up to the game. This is always accepted, and the game goes
+    /// with the decision's default.
+    Undecided,
+
+    /// What the game goes with when a decision has no legal answer, e.g.
+    /// when every pile cheap enough to gain is empty. It's only accepted
+    /// then.
+    NoChoice,
+}
+
+impl Decision {
+    /// Choose cards out of `from`. `min` and `max` are capped at the number
+    /// of cards there are to choose from.
+    pub fn cards(card: Card, purpose: Purpose, from: Vec<Card>, min: uint, max: uint) -> Decision {
+        let n = from.len();
+        ChooseCards{card: card, purpose: purpose, from: from, min: cmp::min(min, n), max: cmp::min(max, n)}
+    }
+
+    pub fn yes_no(card: Card, purpose: Purpose, subject: Option<Card>, default: bool) -> Decision {
+        YesNo{card: card, purpose: purpose, subject: subject, default: default}
+    }
+
+    pub fn option(card: Card, options: Vec<&str>) -> Decision {
+        ChooseOption{card: card, options: options.iter().map(|x| x.to_string()).collect()}
+    }
+
+    /// Choose a pile costing up to `max_cost`.
+    pub fn pile(card: Card, purpose: Purpose, max_cost: Cost) -> Decision {
+        Decision::pile_between(card, purpose, Cost::coins(0), max_cost)
+    }
+
+    /// Choose any pile at all, whatever it costs.
+    pub fn any_pile(card: Card, purpose: Purpose) -> Decision {
+        Decision::pile(card, purpose, Cost::unlimited())
+    }
+
+    /// Choose a pile costing between `min_cost` and `max_cost`.
+    pub fn pile_between(card: Card, purpose: Purpose, min_cost: Cost, max_cost: Cost) -> Decision {
+        ChoosePile{card: card, purpose: purpose, min_cost: min_cost, max_cost: max_cost, only: None}
+    }
+
+    pub fn order(card: Card, purpose: Purpose, cards: Vec<Card>) -> Decision {
+        OrderCards{card: card, purpose: purpose, cards: cards}
+    }
+
+    /// Only allow piles of one type, e.g. Treasures for Mine. Does nothing
+    /// to other kinds of decision.
+    pub fn only(self, typ: CardType) -> Decision {
+        match self {
+            ChoosePile{card, purpose, min_cost, max_cost, ..} => ChoosePile{card: card, purpose: purpose, min_cost: min_cost, max_cost: max_cost, only: Some(typ)},
+            decision => decision,
+        }
+    }
+
+    /// The card that's asking.
+    pub fn card(&self) -> Card {
+        match *self {
+            ChooseCards{card, ..} | YesNo{card, ..} | ChooseOption{card, ..} | ChoosePile{card, ..} | OrderCards{card, ..} => card,
+        }
+    }
+
+    /// Check that `choice` answers this decision, given what's left in the
+    /// supply and what everything costs. Illegal answers get
+    /// `IllegalChoice`.
+    pub fn check(&self, choice: &Choice, state: &GameState) -> Response {
+        let legal = match (self, choice) {
+            (_, &Undecided) => true,
+            (_, &NoChoice) => self.default_choice(state) == NoChoice,
+            (&ChooseCards{ref from, min, max, ..}, &Cards(ref cards)) => {
+                cards.len() >= min && cards.len() <= max && is_subset(cards.as_slice(), from.as_slice())
+            },
+            (&YesNo{..}, &Yes) | (&YesNo{..}, &No) => true,
+            (&ChooseOption{ref options, ..}, &Pick(i)) => i < options.len(),
+            (&ChoosePile{..}, &Pile(card)) => self.allows_pile(card, state),
+            (&OrderCards{ref cards, ..}, &Order(ref order)) => {
+                order.len() == cards.len() && is_subset(order.as_slice(), cards.as_slice())
+            },
+            _ => false,
+        };
+        if legal { response::NoProblem } else { response::IllegalChoice(choice.clone()) }
+    }
+
+    /// What the game goes with when a player leaves the decision up to it,
+    /// or doesn't answer at all. This is `NoChoice` if there's no legal
+    /// answer.
+    pub fn default_choice(&self, state: &GameState) -> Choice {
+        match *self {
+            // Cards being discarded or given away are the least useful ones.
+            ChooseCards{purpose: Discarding, ref from, min, ..} | ChooseCards{purpose: Passing, ref from, min, ..} => {
+                Cards(least_useful(from.as_slice()).into_iter().take(min).collect())
+            },
+            ChooseCards{ref from, min, ..} => Cards(from.iter().take(min).map(|x| *x).collect()),
+            YesNo{default, ..} => if default { Yes } else { No },
+            ChooseOption{ref options, ..} => if options.is_empty() { NoChoice } else { Pick(0) },
+            // The most expensive pile allowed, with ties going in order of name.
+            ChoosePile{..} => {
+                let mut piles: Vec<Card> = state.kingdom.keys().filter(|x| self.allows_pile(**x, state)).map(|x| *x).collect();
+                piles.sort_by(|a, b| (state.cost(*b).sort_key(), a.name()).cmp(&(state.cost(*a).sort_key(), b.name())));
+                match piles.first() {
+                    Some(card) => Pile(*card),
+                    None => NoChoice,
+                }
+            },
+            OrderCards{ref cards, ..} => Order(cards.clone()),
+        }
+    }
+
+    /// Whether `card` can be taken for a `ChoosePile` decision.
+    fn allows_pile(&self, card: Card, state: &GameState) -> bool {
+        match *self {
+            ChoosePile{ref min_cost, ref max_cost, only, ..} => {
+                let cost = state.cost(card);
+                state.count(card).map_or(false, |x| x > 0)
+                    && cost >= *min_cost && cost <= *max_cost
+                    && only.map_or(true, |typ| card.has_type(typ))
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Choice {
+    /// The cards chosen, for ChooseCards or OrderCards.
+    pub fn cards(self) -> Vec<Card> {
+        match self {
+            Cards(cards) | Order(cards) => cards,
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn is_yes(&self) -> bool {
+        *self == Yes
+    }
+
+    /// The index of the option picked, for ChooseOption.
+    pub fn option(&self) -> Option<uint> {
+        match *self {
+            Pick(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// The pile chosen, for ChoosePile.
+    pub fn pile(&self) -> Option<Card> {
+        match *self {
+            Pile(card) => Some(card),
+            _ => None,
+        }
+    }
+}
+
+/// Sort cards from least to most useful: Curses and cards that are only
+/// worth points come first, followed by everything else from cheapest to
+/// most expensive.
+pub fn least_useful(cards: &[Card]) -> Vec<Card> {
+    let mut cards = cards.to_vec();
+    cards.sort_by(|a, b| usefulness(a).cmp(&usefulness(b)));
+    cards
+}
+
+fn usefulness(card: &Card) -> (bool, (uint, uint, uint)) {
+    let is_junk = (card.is_victory() || card.is_curse()) && !card.is_action() && !card.is_money();
+    (!is_junk, card.cost().sort_key())
+}
+
+/// Returns true if every card in `chosen` can be taken out of `from`,
+/// counting duplicates.
+fn is_subset(chosen: &[Card], from: &[Card]) -> bool {
+    let mut left = from.to_vec();
+    chosen.iter().all(|card| match left.iter().position(|x| x == card) {
+        Some(i) => { left.remove(i); true },
+        None => false,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/decision.rs"]
+mod tests;
@@ -0,0 +1,428 @@
+//! A structured log of everything that happens during a game.
+//!
+//! Every time a card moves, the game records an `Event` and hands it to the
+//! game's `EventSink`, if it has one. Events describe where each card came
+//! from and where it went, and shuffles record the new order of the deck, so
+//! a `Replay` can rebuild the exact final state of a game from its log alone.
+//!
+//! ~~~ignore
+//! let mut game = Game::with_config(GameConfig::new().seed(42));
+//! game.log_to(box JsonLines::new(File::create(&Path::new("game.jsonl"))));
+//! ~~~
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::default::Default;
+use std::io::{Buffer, IoError, Writer};
+use std::rc::Rc;
+use serialize::json;
+
+use card::Card;
+
+/// Something that happened during the game.
+#[deriving(Clone, Show, PartialEq, Encodable, Decodable)]
+pub enum Event {
+    /// The game started with `players` players and these supply piles.
+    GameStarted { seed: u64, players: uint, supply: Vec<(Card, uint)> },
+
+    /// A player's turn started.
+    TurnStarted { player: uint, turn: uint },
+
+    /// A player's turn ended, after cleanup.
+    TurnEnded { player: uint },
+
+    /// A player's discard pile was shuffled into their deck, which is now
+    /// `deck`, from top to bottom. Also used for the shuffle before the
+    /// first turn.
+    Shuffled { player: uint, deck: Vec<Card> },
+
+    /// A player's deck was shuffled on its own, leaving the discard pile
+    /// alone, e.g. by Inn. The deck is now `deck`, from top to bottom.
+    ShuffledDeck { player: uint, deck: Vec<Card> },
+
+    /// A card was drawn from the top of the deck into the player's hand.
+    Drew { player: uint, card: Card },
+
+    /// A card was played from the player's hand.
+    Played { player: uint, card: Card },
+
+    /// A card was bought. The card itself arrives with the following `Gained`.
+    Bought { player: uint, card: Card },
+
+    /// A card was gained, either from the supply or from the trash.
+    Gained { player: uint, card: Card, from: Zone, to: Zone },
+
+    /// A card was trashed.
+    Trashed { player: uint, card: Card, from: Zone },
+
+    /// A card was put into the discard pile.
+    Discarded { player: uint, card: Card, from: Zone },
+
+    /// A card was revealed for everyone to see. It doesn't move.
+    Revealed { player: uint, card: Card },
+
+    /// A player reacted to an attack by revealing a Reaction card.
+    Reacted { player: uint, card: Card },
+
+    /// A player took victory point tokens, e.g. from Monument.
+    TookVictoryTokens { player: uint, amount: uint },
+
+    /// A card was passed from one player's hand to another's, e.g. for
+    /// Masquerade.
+    Passed { player: uint, card: Card, to: uint },
+
+    /// A card moved between two of the player's zones in some other way,
+    /// e.g. being put back on top of the deck.
+    Moved { player: uint, card: Card, from: Zone, to: Zone },
+
+    /// The top card of the player's deck was set aside face down, e.g. on
+    /// the Native Village mat. Only the player knows what it is, so it isn't
+    /// named.
+    SetAsideFaceDown { player: uint, to: Zone },
+
+    /// A card was put back on its pile, e.g. Spoils once it's been played.
+    Returned { player: uint, card: Card, from: Zone },
+
+    /// A card was taken out of the supply without anyone gaining it, e.g.
+    /// set aside by Inheritance.
+    TookFromSupply { player: uint, card: Card },
+}
+
+impl Event {
+    /// Encode the event as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        json::encode(self)
+    }
+
+    /// Decode an event from a line of JSON.
+    pub fn from_json(s: &str) -> Result<Event, json::DecoderError> {
+        json::decode(s)
+    }
+}
+
+/// Places a card can be. The top of the deck is always the front.
+#[deriving(Clone, Show, PartialEq, Eq, Encodable, Decodable)]
+pub enum Zone {
+    Hand,
+    Deck,
+    DiscardPile,
+    InPlay,
+    SetAside,
+    Supply,
+    TrashPile,
+
+    /// Piles outside the supply, e.g. Tournament's Prizes.
+    NonSupply,
+    NativeVillageMat,
+    IslandMat,
+
+    /// Set aside until the start of the player's next turn.
+    NextTurn,
+    TavernMat,
+
+    /// Set aside until the end of the player's turn, e.g. with Save.
+    EndOfTurn,
+}
+
+/// Somewhere to send events as they happen.
+pub trait EventSink {
+    fn record(&mut self, event: &Event);
+}
+
+/// Keeps every event in memory.
+impl EventSink for Vec<Event> {
+    fn record(&mut self, event: &Event) {
+        self.push(event.clone());
+    }
+}
+
+/// Like `Vec<Event>`, but the events can still be read after the sink has
+/// been handed to the game.
+impl EventSink for Rc<RefCell<Vec<Event>>> {
+    fn record(&mut self, event: &Event) {
+        self.borrow_mut().push(event.clone());
+    }
+}
+
+/// Writes each event as a line of JSON, which `Replay::from_json_lines` can
+/// read back.
+pub struct JsonLines<W> {
+    writer: W,
+}
+
+impl<W: Writer> JsonLines<W> {
+    pub fn new(writer: W) -> JsonLines<W> {
+        JsonLines{writer: writer}
+    }
+
+    /// Stop logging and get the writer back.
+    pub fn unwrap(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Writer> EventSink for JsonLines<W> {
+    fn record(&mut self, event: &Event) {
+        // A failing log shouldn't take the game down with it.
+        let _ = self.writer.write_line(event.to_json().as_slice());
+    }
+}
+
+/// The game's handle to its sink, shared by every player.
+#[deriving(Clone)]
+pub struct EventLog {
+    sink: Rc<RefCell<Option<Box<EventSink + 'static>>>>,
+}
+
+impl EventLog {
+    pub fn new() -> EventLog {
+        EventLog{sink: Rc::new(RefCell::new(None))}
+    }
+
+    /// Send all future events to `sink`, replacing the old one.
+    pub fn set_sink(&self, sink: Box<EventSink + 'static>) {
+        *self.sink.borrow_mut() = Some(sink);
+    }
+
+    pub fn record(&self, event: Event) {
+        if let Some(ref mut sink) = *self.sink.borrow_mut() {
+            sink.record(&event);
+        }
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> EventLog {
+        EventLog::new()
+    }
+}
+
+/// Where every card is, as rebuilt from an event log.
+#[deriving(Clone, Show, PartialEq)]
+pub struct Replay {
+    pub seed: u64,
+    pub players: Vec<ReplayPlayer>,
+    pub supply: HashMap<Card, uint>,
+    pub trash: Vec<Card>,
+}
+
+/// One player's cards, as rebuilt from an event log.
+#[deriving(Clone, Show, PartialEq, Default)]
+pub struct ReplayPlayer {
+    pub hand: Vec<Card>,
+    pub deck: Vec<Card>,
+    pub discard: Vec<Card>,
+    pub in_play: Vec<Card>,
+    pub set_aside: Vec<Card>,
+    pub native_village: Vec<Card>,
+    pub island: Vec<Card>,
+    pub next_turn: Vec<Card>,
+    pub tavern: Vec<Card>,
+    pub end_of_turn: Vec<Card>,
+    pub vp_tokens: uint,
+    pub turns: uint,
+}
+
+impl ReplayPlayer {
+    /// Every card the player owns, wherever it is.
+    pub fn all_cards(&self) -> Vec<Card> {
+        self.deck.iter().chain(self.hand.iter()).chain(self.discard.iter()).chain(self.in_play.iter()).chain(self.set_aside.iter())
+            .chain(self.native_village.iter()).chain(self.island.iter()).chain(self.next_turn.iter()).chain(self.tavern.iter())
+            .chain(self.end_of_turn.iter()).map(|x| *x).collect()
+    }
+
+    /// Count up the victory points from all of the player's cards and
+    /// tokens.
+    pub fn score(&self) -> int {
+        let cards = self.all_cards();
+        cards.iter().fold(self.vp_tokens as int, |total, card| total + card.victory_points(cards.as_slice()))
+    }
+
+    fn zone(&mut self, zone: Zone) -> Result<&mut Vec<Card>, ReplayError> {
+        Ok(match zone {
+            Hand => &mut self.hand,
+            Deck => &mut self.deck,
+            DiscardPile => &mut self.discard,
+            InPlay => &mut self.in_play,
+            SetAside => &mut self.set_aside,
+            NativeVillageMat => &mut self.native_village,
+            IslandMat => &mut self.island,
+            NextTurn => &mut self.next_turn,
+            TavernMat => &mut self.tavern,
+            EndOfTurn => &mut self.end_of_turn,
+            _ => return Err(WrongZone(zone)),
+        })
+    }
+
+    fn take(&mut self, zone: Zone, card: Card) -> Result<(), ReplayError> {
+        let cards = try!(self.zone(zone));
+        let found = cards.iter().position(|x| *x == card);
+        match found {
+            Some(i) => { cards.remove(i); Ok(()) },
+            None => Err(NotThere(card, zone)),
+        }
+    }
+
+    fn put(&mut self, zone: Zone, card: Card) -> Result<(), ReplayError> {
+        match zone {
+            Deck => self.deck.insert(0, card),
+            _ => try!(self.zone(zone)).push(card),
+        }
+        Ok(())
+    }
+}
+
+impl Replay {
+    pub fn new() -> Replay {
+        Replay{seed: 0, players: Vec::new(), supply: HashMap::new(), trash: Vec::new()}
+    }
+
+    /// Rebuild a game from its events.
+    pub fn from_events(events: &[Event]) -> Result<Replay, ReplayError> {
+        let mut replay = Replay::new();
+        for event in events.iter() {
+            try!(replay.apply(event));
+        }
+        Ok(replay)
+    }
+
+    /// Rebuild a game from a log written by `JsonLines`.
+    pub fn from_json_lines<B: Buffer>(reader: &mut B) -> Result<Replay, ReplayError> {
+        let mut replay = Replay::new();
+        for line in reader.lines() {
+            let line = try!(line.map_err(ReadFailed));
+            let line = line.as_slice().trim();
+            if line.is_empty() {
+                continue;
+            }
+            try!(replay.apply(&try!(Event::from_json(line).map_err(BadEvent))));
+        }
+        Ok(replay)
+    }
+
+    /// Apply a single event on top of the current state. Events that don't
+    /// fit the state so far, e.g. from a truncated log, are errors, and
+    /// leave the state as it was.
+    pub fn apply(&mut self, event: &Event) -> Result<(), ReplayError> {
+        match *event {
+            GameStarted{seed, players, ref supply} => {
+                self.seed = seed;
+                self.players = Vec::from_fn(players, |_| Default::default());
+                self.supply = supply.iter().map(|x| *x).collect();
+                self.trash.clear();
+            },
+            TurnStarted{player, turn} => try!(self.player(player)).turns = turn,
+            Shuffled{player, ref deck} => {
+                let p = try!(self.player(player));
+                p.discard.clear();
+                p.deck = deck.clone();
+            },
+            ShuffledDeck{player, ref deck} => try!(self.player(player)).deck = deck.clone(),
+            Drew{player, card} => try!(self.move_card(player, card, Deck, Hand)),
+            Played{player, card} => try!(self.move_card(player, card, Hand, InPlay)),
+            Gained{player, card, from, to} => {
+                try!(try!(self.player(player)).zone(to));
+                match from {
+                    Supply => try!(self.take_from_supply(card)),
+                    TrashPile => {
+                        let found = self.trash.iter().position(|x| *x == card);
+                        match found {
+                            Some(i) => { self.trash.remove(i); },
+                            None => return Err(NotThere(card, TrashPile)),
+                        }
+                    },
+                    // Only the supply is logged at the start of the game.
+                    NonSupply => (),
+                    zone => return Err(WrongZone(zone)),
+                }
+                try!(try!(self.player(player)).put(to, card));
+            },
+            Trashed{card, from: Supply, ..} => {
+                try!(self.take_from_supply(card));
+                self.trash.push(card);
+            },
+            Trashed{player, card, from} => {
+                try!(try!(self.player(player)).take(from, card));
+                self.trash.push(card);
+            },
+            Discarded{player, card, from} => try!(self.move_card(player, card, from, DiscardPile)),
+            Moved{player, card, from, to} => try!(self.move_card(player, card, from, to)),
+            // The replay knows the deck, so it knows which card it was.
+            SetAsideFaceDown{player, to} => {
+                let card = match try!(self.player(player)).deck.first() {
+                    Some(card) => *card,
+                    None => return Err(EmptyDeck(player)),
+                };
+                try!(self.move_card(player, card, Deck, to));
+            },
+            Returned{player, card, from} => {
+                try!(try!(self.player(player)).take(from, card));
+                // Only the supply is logged at the start of the game.
+                if let Some(count) = self.supply.get_mut(&card) {
+                    *count += 1;
+                }
+            },
+            TookFromSupply{card, ..} => try!(self.take_from_supply(card)),
+            TookVictoryTokens{player, amount} => try!(self.player(player)).vp_tokens += amount,
+            Passed{player, card, to} => {
+                try!(self.player(to));
+                try!(try!(self.player(player)).take(Hand, card));
+                try!(try!(self.player(to)).put(Hand, card));
+            },
+            TurnEnded{..} | Bought{..} | Revealed{..} | Reacted{..} => (),
+        }
+        Ok(())
+    }
+
+    /// Each player's score, in player order. Scoring rules like Landmarks
+    /// aren't logged, so they're left out.
+    pub fn scores(&self) -> Vec<int> {
+        self.players.iter().map(|p| p.score()).collect()
+    }
+
+    fn player(&mut self, player: uint) -> Result<&mut ReplayPlayer, ReplayError> {
+        if player >= self.players.len() {
+            return Err(NoSuchPlayer(player));
+        }
+        Ok(&mut self.players[player])
+    }
+
+    fn take_from_supply(&mut self, card: Card) -> Result<(), ReplayError> {
+        match self.supply.get_mut(&card) {
+            Some(count) if *count > 0 => { *count -= 1; Ok(()) },
+            _ => Err(NotThere(card, Supply)),
+        }
+    }
+
+    fn move_card(&mut self, player: uint, card: Card, from: Zone, to: Zone) -> Result<(), ReplayError> {
+        let p = try!(self.player(player));
+        // Check where it's going before taking it out.
+        try!(p.zone(to));
+        try!(p.take(from, card));
+        p.put(to, card)
+    }
+}
+
+/// Why a log couldn't be replayed.
+#[deriving(Show)]
+pub enum ReplayError {
+    ReadFailed(IoError),
+    BadEvent(json::DecoderError),
+
+    /// An event names a player who isn't in the game, e.g. because the log
+    /// doesn't start with `GameStarted`.
+    NoSuchPlayer(uint),
+
+    /// An event moves a card out of somewhere it isn't.
+    NotThere(Card, Zone),
+
+    /// An event moves a card somewhere it can't go.
+    WrongZone(Zone),
+
+    /// An event takes the top card of a player's deck, but it's empty.
+    EmptyDeck(uint),
+}
+
+#[cfg(test)]
+#[path = "tests/event.rs"]
+mod tests;
@@ -1,25 +1,44 @@
 #![feature(macro_rules, globs, struct_variant, unboxed_closure_sugar, if_let)]
 #![allow(dead_code)]
 
-use std::any::Any;
-use std::boxed::BoxAny;
+extern crate serialize;
+
+use std::cell::Cell;
 use std::collections::{HashMap, RingBuf};
 use std::default::Default;
-use std::rand::{task_rng, Rng};
+use std::rc::Rc;
 
 use card::Card;
 use command::Command;
+use config::{GameConfig, SetupError};
+use cost::Cost;
+use decision::{Choice, Decision};
+use event::{Event, EventLog, EventSink, Zone};
 use notify::Notification;
-use query::Query;
-use reaction::Reaction;
+use phase::Phase;
+use query::{Answer, Opponent, Query, QueryResult};
 use response::Response;
+use rng::GameRng;
+use scoring::{ScoreSheet, ScoringRule};
+use strategy::Strategy;
 
 mod card;
 mod command;
+pub mod config;
+pub mod cost;
+pub mod decision;
+pub mod event;
 mod notify;
+mod phase;
 mod query;
 mod reaction;
+pub mod registry;
 mod response;
+pub mod rng;
+pub mod scoring;
+pub mod simulation;
+pub mod strategy;
+mod token;
 
 #[doc(hidden)]
 mod sets;
@@ -28,23 +47,38 @@ mod strats;
 /// The `Connection` contains the channels that need
 /// to be passed to the player for actions to be taken.
 pub struct Connection {
+    choice_chan: SyncSender<Choice>,
     cmd_chan: SyncSender<Command>,
     done_chan: SyncSender<()>,
     notify_port: Receiver<Notification>,
-    query_a_port: Receiver<Answer>,
+    query_a_port: Receiver<QueryResult<Answer>>,
     query_q_chan: SyncSender<Query>,
-    react_chan: SyncSender<Reaction>,
     resp_port: Receiver<Response>,
+
+    /// Whether the game is waiting on a `Choice`.
+    choice_expected: Cell<bool>,
 }
 
+/// Send a query and unpack its answer, which should be `$variant`.
+macro_rules! ask(($conn:expr, $query:expr, $variant:ident) => (
+    match $conn.query($query) {
+        Ok(query::$variant(x)) => Ok(x),
+        Ok(_) => Err(query::WrongAnswer),
+        Err(e) => Err(e),
+    }
+))
+
 impl Connection {
-    /// Play a card and return its response. If the card requires doing other
-    /// things, e.g. Cellar asks you to discard cards, those should be done via
-    /// fluently chaining the call like:
+    /// Play a card and return its response. If the card needs you to decide
+    /// something, e.g. Cellar asks which cards to discard, the response is
+    /// `Decide`, and the card is finished once you've answered:
     ///
     /// ~~~ignore
     /// use card::*;
-    /// let resp = conn.play(Cellar).discarding(vec![Estate, Duchy]);
+    /// let resp = match conn.play(Cellar) {
+    ///     Decide(_) => conn.choose(Cards(vec![Estate, Duchy])),
+    ///     resp => resp,
+    /// };
     /// ~~~
     pub fn play(&self, card: Card) -> Response {
         self.do_action(command::Play(card))
@@ -58,64 +92,232 @@ impl Connection {
         self.do_action(command::Buy(card))
     }
 
+    /// Buy an Event. It takes a Buy and costs coins like a card, but
+    /// nothing is gained unless the Event says so.
+    pub fn buy_event(&self, card: Card) -> Response {
+        self.do_action(command::BuyEvent(card))
+    }
+
+    /// Pay off `amount` of your debt with $1 each. You can't buy anything
+    /// while you're in debt. This moves on to the Buy phase, like playing a
+    /// treasure.
+    pub fn pay_debt(&self, amount: uint) -> Response {
+        self.do_action(command::PayDebt(amount))
+    }
+
+    /// Turn Coffers into buying power, $1 each. This moves on to the Buy
+    /// phase, like playing a treasure.
+    pub fn spend_coffers(&self, amount: uint) -> Response {
+        self.do_action(command::SpendCoffers(amount))
+    }
+
+    /// Move on to the next phase of your turn, e.g. to start buying cards
+    /// without playing any treasures.
+    pub fn end_phase(&self) -> Response {
+        self.do_action(command::EndPhase)
+    }
+
     pub fn recv_notification(&self) -> Notification {
-        self.notify_port.recv_opt().unwrap_or(notify::GameOver)
+        let notification = self.notify_port.recv_opt().unwrap_or(notify::GameOver);
+        self.choice_expected.set(notification.expects_choice());
+        notification
     }
 
+    /// Let the game fall back on its default choice. This is safe to call
+    /// for any notification, since it does nothing if the game isn't waiting
+    /// on anything.
     pub fn not_implemented(&self) {
-        self.react_chan.send(reaction::NotImplemented);
+        if self.choice_expected.get() {
+            self.choose(decision::Undecided);
+        }
     }
 
-    pub fn react(&self, action: Reaction) {
-        self.react_chan.send(action);
+    /// Answer the `Decision` the game is waiting on, whether it came as a
+    /// `Decide` response or a notification. Returns whatever the game says
+    /// next, which is `IllegalChoice` if the game is still waiting on a
+    /// legal answer, or `NotDeciding` if it wasn't waiting on anything.
+    pub fn choose(&self, choice: Choice) -> Response {
+        if !self.choice_expected.get() {
+            return response::NotDeciding;
+        }
+        if self.choice_chan.send_opt(choice).is_err() {
+            self.choice_expected.set(false);
+            return response::NotDeciding;
+        }
+        self.recv_response()
     }
 
     pub fn done(&self) {
         self.done_chan.send(());
     }
 
-    fn query<T: 'static>(&self, q: Query) -> Option<T> {
-        self.query_q_chan.send(q);
-        match self.query_a_port.recv().downcast() {
-            Ok(val) => Some(*val),
-            Err(_) => None,
-        }
+    /// The number of copies of a card left in the supply. Fails with
+    /// `NotInSupply` if the card isn't part of this game.
+    pub fn count(&self, card: Card) -> QueryResult<uint> {
+        ask!(self, query::Count(card), Number)
     }
 
-    fn do_action(&self, cmd: Command) -> Response {
-        self.cmd_chan.send(cmd); self.resp_port.recv()
+    /// The number of actions you have left this turn.
+    pub fn get_actions(&self) -> QueryResult<uint> {
+        ask!(self, query::Actions, Number)
     }
-}
 
-impl Player for Connection {
-    fn get_buying_power(&self) -> uint {
-        self.query(query::BuyingPower).expect("get_buying_power() query returned an invalid response")
+    /// Every card you own, wherever it is.
+    pub fn get_all_cards(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::AllCards, CardList)
     }
 
-    fn get_hand(&self) -> Vec<Card> {
-        self.query(query::Hand).expect("get_hand() query returned an invalid response")
+    /// The total value of the treasures in your hand.
+    pub fn get_available_money(&self) -> QueryResult<uint> {
+        ask!(self, query::AvailableMoney, Number)
     }
 
-    fn get_hand_size(&self) -> uint {
-        self.query(query::HandSize).expect("get_hand_size() query returned an invalid response")
+    /// The number of buys you have left this turn.
+    pub fn get_buys(&self) -> QueryResult<uint> {
+        ask!(self, query::Buys, Number)
     }
 
-    fn has_in_hand(&self, card: Card) -> bool {
-        self.query(query::HasInHand(card)).expect("has_in_hand() query returned an invalid response")
+    /// How much you have to spend, from the treasures and actions you've played.
+    pub fn get_buying_power(&self) -> QueryResult<uint> {
+        ask!(self, query::BuyingPower, Number)
+    }
+
+    /// The number of Coffers you have saved up, e.g. from Baker.
+    pub fn get_coffers(&self) -> QueryResult<uint> {
+        ask!(self, query::Coffers, Number)
+    }
+
+    /// The debt you owe, e.g. from buying Engineer.
+    pub fn get_debt(&self) -> QueryResult<uint> {
+        ask!(self, query::Debt, Number)
+    }
+
+    /// The number of potions you have to spend, from Potions you've played.
+    pub fn get_potions(&self) -> QueryResult<uint> {
+        ask!(self, query::Potions, Number)
+    }
+
+    /// The cards in your discard pile.
+    pub fn get_discard(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::Discard, CardList)
+    }
+
+    /// The cards in your hand.
+    pub fn get_hand(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::Hand, CardList)
+    }
+
+    /// The number of cards in your hand.
+    pub fn get_hand_size(&self) -> QueryResult<uint> {
+        ask!(self, query::HandSize, Number)
+    }
+
+    /// The cards you have in play.
+    pub fn get_in_play(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::InPlay, CardList)
+    }
+
+    /// The cards set aside on your Native Village mat.
+    pub fn get_native_village_mat(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::NativeVillageMat, CardList)
+    }
+
+    /// The cards on your Tavern mat, e.g. Reserve cards waiting to be
+    /// called.
+    pub fn get_tavern_mat(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::TavernMat, CardList)
+    }
+
+    /// The number of Embargo tokens on a card's supply pile.
+    pub fn embargo_tokens(&self, card: Card) -> QueryResult<uint> {
+        ask!(self, query::EmbargoTokens(card), Number)
+    }
+
+    /// The Events that can be bought this game.
+    pub fn get_events(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::Events, CardList)
+    }
+
+    /// The Landmarks in this game.
+    pub fn get_landmarks(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::Landmarks, CardList)
+    }
+
+    /// The number of victory point tokens on a card's supply pile, e.g.
+    /// Temple's.
+    pub fn pile_victory_tokens(&self, card: Card) -> QueryResult<uint> {
+        ask!(self, query::PileVictoryTokens(card), Number)
+    }
+
+    /// What you can see of each opponent, starting with whoever plays next.
+    pub fn get_opponents(&self) -> QueryResult<Vec<Opponent>> {
+        ask!(self, query::Opponents, OpponentList)
+    }
+
+    /// The phase of the turn you're in.
+    pub fn get_phase(&self) -> QueryResult<Phase> {
+        ask!(self, query::CurrentPhase, PhaseIs)
+    }
+
+    /// How many copies of each card are left in the supply.
+    pub fn get_supply(&self) -> QueryResult<HashMap<Card, uint>> {
+        ask!(self, query::Supply, SupplyPiles)
+    }
+
+    /// The victory points you would have if the game ended now.
+    pub fn get_total_points(&self) -> QueryResult<int> {
+        ask!(self, query::TotalPoints, Points)
+    }
+
+    /// Everything that's been trashed so far.
+    pub fn get_trash(&self) -> QueryResult<Vec<Card>> {
+        ask!(self, query::Trash, CardList)
+    }
+
+    /// Returns true only if you have the card in your hand.
+    pub fn has_in_hand(&self, card: Card) -> QueryResult<bool> {
+        ask!(self, query::HasInHand(card), Truth)
+    }
+
+    /// How many copies of a card you own.
+    pub fn number_of(&self, card: Card) -> QueryResult<uint> {
+        ask!(self, query::NumberOf(card), Number)
+    }
+
+    /// Ask the game something. If the game has already ended, there's no
+    /// one left to answer.
+    fn query(&self, q: Query) -> QueryResult<Answer> {
+        if self.query_q_chan.send_opt(q).is_err() {
+            return Err(query::GameOver);
+        }
+        self.query_a_port.recv_opt().unwrap_or(Err(query::GameOver))
+    }
+
+    fn do_action(&self, cmd: Command) -> Response {
+        self.cmd_chan.send(cmd);
+        self.recv_response()
+    }
+
+    fn recv_response(&self) -> Response {
+        let resp = self.resp_port.recv();
+        self.choice_expected.set(resp.needs_choice());
+        resp
     }
 }
 
 enum LoopOption {
     LoopCommand(Command),
     LoopQuery(Query),
-    LoopPending((Card, PendingPlay), Sender<Response>),
     LoopDone,
 }
 
 #[deriving(Default)]
 pub struct Game {
+    config: GameConfig,
     playing: bool, // could potentially use a status enum here instead
     players: Vec<PlayerHandle>,
+    log: EventLog,
+    rng: GameRng,
     state: GameState,
 }
 
@@ -129,70 +331,93 @@ impl Game {
         Game{players: Vec::with_capacity(capacity), ..Default::default()}
     }
 
-    /// Add a player.
-    pub fn add_player(&mut self) -> Connection {
-        use std::comm::sync_channel;
-
-        // So many channels!
-        let (cmd_chan, cmd_port)         = sync_channel(0);
-        let (done_chan, done_port)       = sync_channel(0);
-        let (notify_chan, notify_port)   = sync_channel(0);
-        let (query_q_chan, query_q_port) = sync_channel(0);
-        let (query_a_chan, query_a_port) = sync_channel(0);
-        let (react_chan, react_port)     = sync_channel(0);
-        let (resp_chan, resp_port)       = sync_channel(0);
+    /// Initialize a new game object that will be set up according to
+    /// `config`.
+    pub fn with_config(config: GameConfig) -> Game {
+        let rng = match config.get_seed() {
+            Some(seed) => GameRng::new(seed),
+            None => GameRng::random(),
+        };
+        Game{config: config, rng: rng, ..Default::default()}
+    }
 
-        self.players.push(PlayerHandle{
-            cmd_port: cmd_port,
-            done_port: done_port,
-            notify_chan: notify_chan,
-            play_complete: Vec::new(),
-            query_a_chan: query_a_chan,
-            query_q_port: query_q_port,
-            react_port: react_port,
-            resp_chan: resp_chan,
+    /// Add a player. Players are numbered in the order they're added,
+    /// starting from 0, and take their turns in that order.
+    pub fn add_player(&mut self) -> Connection {
+        let (mut handle, conn) = PlayerHandle::new();
+        handle.id = self.players.len();
+        handle.deck = Game::new_deck(false);
+        handle.rng = self.rng.clone();
+        handle.log = self.log.clone();
+        self.players.push(handle);
+        conn
+    }
 
-            actions: 0,
-            buys: 0,
-            buying_power: 0,
-            hand: vec![],
-            deck: Game::new_deck(),
-            discard: vec![],
-            in_play: vec![],
-        });
+    /// Add a player whose `Strategy` runs on the game thread, instead of
+    /// talking to the game over a `Connection`. Returns the player's number.
+    pub fn add_strategy(&mut self, strategy: Box<Strategy + 'static>) -> uint {
+        let id = self.players.len();
+        // Nobody is listening on the other end of this connection.
+        drop(self.add_player());
+        self.players[id].strategy = Some(Rc::new(strategy));
+        id
+    }
 
-        Connection {
-            cmd_chan: cmd_chan,
-            done_chan: done_chan,
-            notify_port: notify_port,
-            query_a_port: query_a_port,
-            query_q_chan: query_q_chan,
-            react_chan: react_chan,
-            resp_port: resp_port,
-        }
+    /// Add a rule for scoring the game, on top of what each player's cards
+    /// and tokens are worth and any rules from the game's Landmarks. See the
+    /// `scoring` module.
+    pub fn add_scoring_rule(&mut self, rule: Box<ScoringRule + 'static>) {
+        self.state.scoring.push(rule);
     }
 
-    fn new_deck() -> Vec<Card> {
-        use card::*;
-        vec![Estate, Estate, Estate, Copper, Copper, Copper, Copper, Copper, Copper, Copper]
+    /// Send a record of everything that happens in the game to `sink`.
+    /// See the `event` module for the events and how to replay them.
+    pub fn log_to(&mut self, sink: Box<EventSink + 'static>) {
+        self.log.set_sink(sink);
     }
 
-    /// Play the game. It loops forever until the game is over.
-    pub fn play(mut self) {
+    /// A starting deck. With Shelters, they replace the three Estates.
+    fn new_deck(shelters: bool) -> Vec<Card> {
         use card::*;
+        let mut deck = if shelters { vec![Hovel, Necropolis, OvergrownEstate] } else { vec![Estate, Estate, Estate] };
+        deck.push_all(&[Copper, Copper, Copper, Copper, Copper, Copper, Copper]);
+        deck
+    }
 
+    /// Play the game. It loops forever until the game is over, which is
+    /// when either the Province pile or enough other supply piles run out.
+    /// It fails straight away if the game can't be set up, e.g. because
+    /// there are too many players.
+    pub fn play(mut self) -> Result<GameResult, SetupError> {
         self.playing = true;
         let num_players = self.players.len();
+        let empty_limit = Game::empty_limit(num_players);
         let mut handles = RingBuf::new();
 
-        // Populate the kingdom. Need to find a way to customize this.
-        for card in vec![Copper, Silver, Gold, Estate, Duchy, Province].into_iter() {
-            self.state.kingdom.insert(card, 10);
-        }
+        let setup = try!(self.config.setup(num_players, &self.rng));
+        self.state.kingdom = setup.supply;
+        self.state.non_supply = setup.non_supply;
+        self.state.bane = setup.bane;
+        self.state.mixed_pile_contents = setup.mixed_piles.clone();
+        self.state.mixed_piles = setup.mixed_piles;
+        self.state.events = setup.events;
+        self.state.landmarks = setup.landmarks;
+        self.state.scoring.extend(setup.scoring.into_iter());
+        let mut supply: Vec<(Card, uint)> = self.state.supply_cards().into_iter().collect();
+        supply.sort_by(|&(a, _), &(b, _)| a.name().cmp(b.name()));
+        self.log.record(event::GameStarted{seed: self.rng.seed(), players: num_players, supply: supply});
 
         for mut p in self.players.into_iter() {
-            task_rng().shuffle(p.deck.as_mut_slice());
+            if setup.shelters {
+                p.deck = Game::new_deck(true);
+            }
+            self.rng.shuffle(p.deck.as_mut_slice());
+            p.record(event::Shuffled{player: p.id, deck: p.deck.clone()});
             p.draw_n(5); // start with 5 cards
+            // With Baker in the kingdom, everyone starts with a Coffers.
+            if self.state.kingdom.contains_key(&card::Baker) {
+                p.coffers = 1;
+            }
             handles.push(p);
         }
 
@@ -201,37 +426,25 @@ impl Game {
 
         'game: loop {
             let mut player = handles.pop_front().expect("no players found!");
-            player.actions = 1;
-            player.buys = 1;
-            player.buying_power = 0;
-
-            // Signal the player that it's their turn.
-            player.notify_chan.send(notify::YourTurn(round));
+            Game::play_turn(&mut player, &mut self.state, &mut handles, round, false);
 
-            'player: loop {
-                match player.wait() {
-                    LoopCommand(cmd) => {
-                        let resp = player.handle_cmd(cmd, &mut self.state, &mut handles, None);
-                        player.resp_chan.send(resp);
-                    },
-                    LoopQuery(query) => {
-                        let a = player.answer_query(query, &mut self.state);
-                        player.query_a_chan.send(a);
-                    },
-                    LoopPending((card, pending), resp_chan) => {
-                        let resp = player.handle_cmd(command::Play(card), &mut self.state, &mut handles, Some(pending));
-                        resp_chan.send(resp);
-                    },
-                    LoopDone => break 'player,
-                }
+            // Outpost gives the player another turn straight away, but never
+            // two extra turns in a row. It's cleared by the extra turn.
+            if player.outpost && !self.state.is_over(empty_limit) {
+                Game::play_turn(&mut player, &mut self.state, &mut handles, round, true);
             }
+            player.outpost = false;
 
-            // Refresh the hand.
-            player.discard_hand();
-            player.draw_n(5);
-
-            // Add the player to the end of the list.
+            // Add the player to the end of the list. Each Possession they
+            // played gives the next player a turn that they control.
+            let possessions = std::mem::replace(&mut player.possessions, 0);
             handles.push(player);
+            for _ in range(0, possessions) {
+                if handles.len() < 2 || self.state.is_over(empty_limit) {
+                    break;
+                }
+                Game::play_possessed_turn(&mut self.state, &mut handles, round);
+            }
 
             // Keep track of the turn. Once the turn number hits the number of
             // players, we've gone full circle and begun a new round.
@@ -241,48 +454,325 @@ impl Game {
                 round += 1;
             }
 
-            // Play for ten rounds.
-            if round > 10 {
+            if self.state.is_over(empty_limit) {
                 break 'game;
             }
         }
 
-        // Tell everyone to quit.
+        // Tell everyone to quit. Players with a strategy aren't listening.
         for player in handles.iter() {
-            player.notify_chan.send(notify::GameOver);
+            let _ = player.notify_chan.send_opt(notify::GameOver);
+        }
+
+        let results = handles.iter().map(|p| PlayerResult{
+            player: p.id,
+            place: 0,
+            vp: p.final_score(&self.state, handles.iter()),
+            turns: p.turns,
+        }).collect();
+
+        Ok(GameResult::new(results, self.rng.seed()))
+    }
+
+    /// Play one of `player`'s turns, from the start of the Action phase
+    /// through Cleanup. Extra turns, e.g. from Outpost, don't count towards
+    /// the number of turns the player has taken.
+    fn play_turn(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>, round: uint, extra: bool) {
+        if !extra {
+            player.turns += 1;
+        }
+        player.phase = phase::ActionPhase;
+        player.actions = 1;
+        player.buys = 1;
+        player.buying_power = 0;
+        player.potions = 0;
+        state.cost_reduction = 0;
+        state.action_cost_reduction = 0;
+        state.actions_in_play = 0;
+        state.princess = false;
+        state.active_player = player.id;
+        player.turn_gains = 0;
+        player.record(event::TurnStarted{player: player.id, turn: player.turns});
+        player.resolve_durations(state);
+        player.call_reserves(state, reaction::TurnStarted);
+        player.resolve_gains(state, opponents);
+        player.taking_turn = true;
+
+        if player.strategy.is_some() {
+            player.take_turn(state, opponents);
+        } else {
+            // Signal the player that it's their turn, which starts with
+            // the Action phase.
+            player.notify_chan.send(notify::YourTurn(round));
+            loop {
+                match player.wait() {
+                    LoopCommand(cmd) => {
+                        let resp = player.handle_cmd(cmd, state, opponents);
+                        player.resp_chan.send(resp);
+                    },
+                    LoopQuery(query) => {
+                        let a = player.answer_query(query, state, opponents);
+                        player.query_a_chan.send(a);
+                    },
+                    LoopDone => break,
+                }
+            }
+        }
+        player.taking_turn = false;
+        // Outpost doesn't give another extra turn after an extra turn, so
+        // the player draws a full hand for their next real one.
+        if extra {
+            player.outpost = false;
+        }
+        player.call_reserves(state, reaction::BuyPhaseEnded);
+        player.enter_phase(phase::CleanupPhase, state);
+        player.discard_from_play(state);
+        player.cleanup();
+        player.record(event::TurnEnded{player: player.id});
+    }
+
+    /// Play an extra turn for the player at the front of `handles`, with
+    /// the player at the back making every decision, as for Possession.
+    /// Everything the possessed player would gain goes to the possessing
+    /// player's discard pile, and anything they'd trash is set aside and
+    /// discarded once the turn is over.
+    ///
+    /// The two players swap connections for the turn, so anything the
+    /// possessing player is asked as an opponent, e.g. whether to reveal
+    /// Moat, goes to the possessed player.
+    fn play_possessed_turn(state: &mut GameState, handles: &mut RingBuf<PlayerHandle>, round: uint) {
+        let mut possessed = handles.pop_front().expect("no players found!");
+        let possessor = handles.back().expect("no one to possess the player").id;
+        possessed.swap_controls(handles.back_mut().unwrap());
+        possessed.possessed_by = Some(possessor);
+        Game::play_turn(&mut possessed, state, handles, round, true);
+        possessed.possessed_by = None;
+        possessed.swap_controls(handles.back_mut().unwrap());
+
+        let gains = std::mem::replace(&mut possessed.possessed_gains, Vec::new());
+        for &(card, from) in gains.iter() {
+            handles.back_mut().unwrap().gain_possessed(state, card, from);
+        }
+        possessed.resolve_gains(state, handles);
+        possessed.discard_all(event::SetAside);
+        handles.push_front(possessed);
+    }
+
+    /// The number of empty supply piles that ends the game.
+    fn empty_limit(num_players: uint) -> uint {
+        match num_players {
+            0...4 => 3,
+            _     => 4,
+        }
+    }
+}
+
+/// The result of a finished game.
+pub struct GameResult {
+    /// Results for each player, ordered from first place to last.
+    pub players: Vec<PlayerResult>,
+
+    /// The number of rounds played.
+    pub rounds: uint,
+
+    /// The seed the game was played with. Playing another game with the
+    /// same seed and the same players will play out exactly the same way.
+    pub seed: u64,
+}
+
+impl GameResult {
+    /// Rank the players. Whoever has the most victory points wins, and ties
+    /// go to whoever took fewer turns. Players tied on both share a place.
+    fn new(mut players: Vec<PlayerResult>, seed: u64) -> GameResult {
+        let rounds = players.iter().map(|x| x.turns).max().unwrap_or(0);
+        players.sort_by(|a, b| (b.vp, a.turns).cmp(&(a.vp, b.turns)));
+        for i in range(0, players.len()) {
+            players[i].place = if i > 0 && players[i].vp == players[i-1].vp && players[i].turns == players[i-1].turns {
+                players[i-1].place
+            } else {
+                i + 1
+            };
         }
+        GameResult{players: players, rounds: rounds, seed: seed}
+    }
+
+    /// The players who finished in first place.
+    pub fn winners(&self) -> Vec<uint> {
+        self.players.iter().filter(|x| x.place == 1).map(|x| x.player).collect()
+    }
 
-        // Game is done.
+    /// Returns true if more than one player finished in first place.
+    pub fn is_tie(&self) -> bool {
+        self.players.iter().filter(|x| x.place == 1).count() > 1
     }
 }
 
+/// A single player's result.
+#[deriving(Clone, Show)]
+pub struct PlayerResult {
+    /// The player's number, in the order they were added to the game.
+    pub player: uint,
+
+    /// The player's final placement, starting from 1.
+    pub place: uint,
+
+    /// Total victory points over all of the player's cards and tokens,
+    /// and from the game's scoring rules.
+    pub vp: int,
+
+    /// The number of turns the player took.
+    pub turns: uint,
+}
+
 /// Player handle. It contains a definition of the player trait,
 /// as well as several "pipes" that act as two-way communication
 /// channels.
 struct PlayerHandle {
+    choice_port: Receiver<Choice>,
     cmd_port: Receiver<Command>,
     done_port: Receiver<()>,
-    notify_chan: SyncSender<Notification>,
-    play_complete: Vec<(Sender<Response>, Receiver<(Card, PendingPlay)>)>,
-    query_a_chan: SyncSender<Answer>,
+    notify_chan: Sender<Notification>,
+    query_a_chan: SyncSender<QueryResult<Answer>>,
     query_q_port: Receiver<Query>,
-    react_port: Receiver<Reaction>,
     resp_chan: SyncSender<Response>,
 
+    id: uint,
+    log: EventLog,
+    rng: GameRng,
+
+    /// Set for players who are played by a `Strategy` on the game thread
+    /// rather than through their `Connection`.
+    strategy: Option<Rc<Box<Strategy + 'static>>>,
+
+    /// Set while it's the player's turn, when a `Connection` is waiting on
+    /// a `Response` rather than a notification.
+    taking_turn: bool,
+
+    turns: uint,
+    phase: Phase,
     actions: uint,
     buys: uint,
     buying_power: uint,
+
+    /// Potions to spend on cards that cost them, from playing Potion.
+    potions: uint,
+
+    /// How many Action cards have been played this turn, counting every
+    /// time one is played again by something like Throne Room.
+    actions_played: uint,
+
+    /// Every card played this turn, in order. A card played again by
+    /// something like Throne Room is listed again.
+    played: Vec<Card>,
+
+    /// Every card bought this turn, e.g. for Hermit.
+    bought: Vec<Card>,
+
+    /// Every Event bought this turn. Some can only be bought once a turn.
+    events_bought: Vec<Card>,
+
+    /// How many cards the player has gained since their turn started, and
+    /// how many they gained on their last turn, e.g. for Treasure Hunter.
+    turn_gains: uint,
+    gained_last_turn: uint,
+
+    /// Extra buying power for each Copper played this turn, e.g. from
+    /// Coppersmith.
+    copper_bonus: uint,
+
+    /// Duration cards played this turn, which stay in play and do something
+    /// at the start of the player's next turn. A card played twice, e.g. by
+    /// Throne Room, is listed twice, and the Throne Room is listed too so
+    /// that it stays in play with it.
+    durations: Vec<Card>,
+
+    /// Set when the player plays Outpost, for an extra turn after this one.
+    outpost: bool,
+
+    /// Set when the player buys Travelling Fair. For the rest of the turn,
+    /// they can put the cards they gain onto their deck.
+    travelling_fair: bool,
+
+    /// Extra cards the player draws for their next hand, from Expedition.
+    extra_cards: uint,
+
+    /// Cards the player can't buy this turn, named for Contraband.
+    contraband: Vec<Card>,
+
+    /// Victory point tokens, e.g. from Monument.
+    vp_tokens: uint,
+
+    /// Coffers the player has saved up, which can be spent for $1 each.
+    coffers: uint,
+
+    /// Debt the player owes, e.g. from buying Engineer. They can't buy
+    /// anything until it's paid off, at $1 each.
+    debt: uint,
+
+    /// Set while the player's −1 Card token is on their deck. The next
+    /// time they draw, they draw one card fewer.
+    minus_card: bool,
+
+    /// Set while the player has their −$1 token. The next time they get
+    /// coins from a card, they get $1 less.
+    minus_coin: bool,
+
+    /// Which way up the player's Journey token is, e.g. for Ranger. It
+    /// starts face up.
+    journey_face_up: bool,
+
+    /// The number of Possessions played this turn. The next player takes
+    /// that many turns afterwards, controlled by this player.
+    possessions: uint,
+
+    /// Set during a turn that another player controls with Possession. The
+    /// cards gained that turn go to that player, and are kept in
+    /// `possessed_gains` with where they came from until the turn is over.
+    possessed_by: Option<uint>,
+    possessed_gains: Vec<(Card, Zone)>,
+
     hand: Vec<Card>,
     deck: Vec<Card>,
     discard: Vec<Card>,
     in_play: Vec<Card>,
+    set_aside: Vec<Card>,
+
+    /// Cards set aside on the player's Native Village and Island mats.
+    native_village: Vec<Card>,
+    island: Vec<Card>,
+
+    /// Cards set aside until the start of the player's next turn, e.g.
+    /// Horse Traders. They come back through `durations`.
+    next_turn: Vec<Card>,
+
+    /// Cards on the player's Tavern mat, e.g. Reserve cards waiting to be
+    /// called.
+    tavern: Vec<Card>,
+
+    /// Cards set aside until the end of the player's turn, e.g. with Save.
+    /// They go back to their hand after Cleanup's draw.
+    end_of_turn: Vec<Card>,
 }
 
+/// What the game knows about a player. Bots ask the same things through
+/// their `Connection`, where the answers can fail to arrive.
 trait Player {
+    fn get_actions(&self) -> uint;
+    fn get_all_cards(&self) -> Vec<Card>;
+    fn get_available_money(&self) -> uint;
+    fn get_buys(&self) -> uint;
     fn get_buying_power(&self) -> uint;
+    fn get_coffers(&self) -> uint;
+    fn get_debt(&self) -> uint;
+    fn get_potions(&self) -> uint;
+    fn get_discard(&self) -> Vec<Card>;
     fn get_hand(&self) -> Vec<Card>;
     fn get_hand_size(&self) -> uint;
+    fn get_in_play(&self) -> Vec<Card>;
+    fn get_phase(&self) -> Phase;
+    fn get_total_points(&self) -> int;
     fn has_in_hand(&self, card: Card) -> bool;
+    fn number_of(&self, card: Card) -> uint;
 
     fn has_or_else(&self, card: Card, f: ||) {
         if !self.has_in_hand(card) {
@@ -292,23 +782,98 @@ trait Player {
 }
 
 impl PlayerHandle {
+    /// Create a new player handle with an empty deck, along with the
+    /// connection used to talk to it.
+    fn new() -> (PlayerHandle, Connection) {
+        use std::comm::sync_channel;
+
+        // So many channels! Notifications are buffered, unlike the rest:
+        // the game sends them mid-turn, e.g. a new phase while it's carrying
+        // out a command, and the player is waiting on the command's response
+        // rather than reading notifications. Without a buffer, both sides
+        // would wait on each other forever.
+        let (choice_chan, choice_port)   = sync_channel(0);
+        let (cmd_chan, cmd_port)         = sync_channel(0);
+        let (done_chan, done_port)       = sync_channel(0);
+        let (notify_chan, notify_port)   = channel();
+        let (query_q_chan, query_q_port) = sync_channel(0);
+        let (query_a_chan, query_a_port) = sync_channel(0);
+        let (resp_chan, resp_port)       = sync_channel(0);
+
+        let handle = PlayerHandle{
+            choice_port: choice_port,
+            cmd_port: cmd_port,
+            done_port: done_port,
+            notify_chan: notify_chan,
+            query_a_chan: query_a_chan,
+            query_q_port: query_q_port,
+            resp_chan: resp_chan,
+
+            id: 0,
+            log: EventLog::new(),
+            rng: GameRng::random(),
+            strategy: None,
+            taking_turn: false,
+            turns: 0,
+            phase: phase::ActionPhase,
+            actions: 0,
+            buys: 0,
+            buying_power: 0,
+            potions: 0,
+            actions_played: 0,
+            played: vec![],
+            bought: vec![],
+            events_bought: vec![],
+            turn_gains: 0,
+            gained_last_turn: 0,
+            copper_bonus: 0,
+            durations: vec![],
+            outpost: false,
+            travelling_fair: false,
+            extra_cards: 0,
+            contraband: vec![],
+            vp_tokens: 0,
+            coffers: 0,
+            debt: 0,
+            minus_card: false,
+            minus_coin: false,
+            journey_face_up: true,
+            possessions: 0,
+            possessed_by: None,
+            possessed_gains: vec![],
+            hand: vec![],
+            deck: vec![],
+            discard: vec![],
+            in_play: vec![],
+            set_aside: vec![],
+            native_village: vec![],
+            island: vec![],
+            next_turn: vec![],
+            tavern: vec![],
+            end_of_turn: vec![],
+        };
+
+        let conn = Connection {
+            choice_chan: choice_chan,
+            cmd_chan: cmd_chan,
+            done_chan: done_chan,
+            notify_port: notify_port,
+            query_a_port: query_a_port,
+            query_q_chan: query_q_chan,
+            resp_port: resp_port,
+            choice_expected: Cell::new(false),
+        };
+
+        (handle, conn)
+    }
+
     fn wait(&mut self) -> LoopOption {
         let sel = std::comm::Select::new();
 
         let mut cmd = sel.handle(&self.cmd_port);
         let mut query = sel.handle(&self.query_q_port);
         let mut done = sel.handle(&self.done_port);
-        let mut all_pending: Vec<(&Sender<Response>, std::comm::Handle<(Card, PendingPlay)>)> = Vec::new();
-        let mut pending_iter = self.play_complete.iter_mut();
-
-        unsafe {
-            for &(ref resp_chan, ref pending_port) in pending_iter {
-                let mut pending = sel.handle(pending_port);
-                pending.add();
-                all_pending.push((resp_chan, pending));
-            }
-            cmd.add(); query.add(); done.add();
-        }
+        unsafe { cmd.add(); query.add(); done.add(); }
 
         let id = sel.wait();
 
@@ -319,17 +884,19 @@ impl PlayerHandle {
         } else if id == done.id() {
             LoopDone
         } else {
-            for &(ref resp_chan, ref mut pending) in all_pending.iter_mut() {
-                if id == pending.id() {
-                    return LoopPending(pending.recv(), (*resp_chan).clone());
-                }
-            }
             unreachable!()
         }
     }
 
-    /// Handle a command from the player.
-    fn handle_cmd(&mut self, cmd: Command, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>, pending: Option<PendingPlay>) -> Response {
+    /// Handle a command from the player. Once it's done, everyone else gets
+    /// to respond to whatever was gained along the way.
+    fn handle_cmd(&mut self, cmd: Command, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+        let resp = self.run_cmd(cmd, state, opponents);
+        self.resolve_gains(state, opponents);
+        resp
+    }
+
+    fn run_cmd(&mut self, cmd: Command, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
         use command::*;
         macro_rules! try(($e:expr) => ({
             let resp = $e;
@@ -337,55 +904,287 @@ impl PlayerHandle {
         }))
         match cmd {
             Buy(card) => {
-                use std::collections::hash_map::{Vacant, Occupied};
-                match state.kingdom.entry(card) {
-                    Vacant(_) => return response::NotInKingdom(card),
-                    Occupied(ref entry) if *entry.get() == 0 => response::PileEmpty(card),
-                    Occupied(entry) => {
-                        *entry.into_mut() -= 1;
-                        self.discard.push(card);
-                        response::NoProblem
-                    },
+                match self.phase {
+                    phase::ActionPhase => self.enter_phase(phase::BuyPhase, state),
+                    phase::BuyPhase => (),
+                    phase => return response::WrongPhase(phase),
+                }
+                match state.count(card) {
+                    None => return response::NotInKingdom(card),
+                    Some(0) => return response::PileEmpty(card),
+                    Some(_) => (),
+                }
+                if self.buys == 0 {
+                    return response::NoBuys;
+                }
+                if self.debt > 0 {
+                    return response::InDebt(self.debt);
+                }
+                if self.contraband.contains(&card) || (card == card::GrandMarket && self.in_play.contains(&card::Copper)) {
+                    return response::Forbidden(card);
+                }
+                let cost = state.cost(card);
+                if !cost.can_pay(self.buying_power, self.potions) {
+                    return response::NotEnoughMoney{need: cost, have: Cost::new(self.buying_power, self.potions, 0)};
                 }
+                self.record(event::Bought{player: self.id, card: card});
+                try!(self.gain(state, card));
+                self.bought.push(card);
+                self.react(state, reaction::Bought(card));
+                // Each Embargo token on the pile comes with a Curse.
+                for _ in range(0, state.embargo_tokens(card)) {
+                    self.gain(state, card::Curse);
+                }
+                for in_play in self.in_play.clone().iter() {
+                    in_play.on_buy_in_play(self, state, card);
+                }
+                card.on_buy(self, state);
+                if state.tokens.on(self.id, card).contains(&token::Trashing) {
+                    ::sets::adventures::trashing_token(self, state);
+                }
+                self.buys -= 1;
+                self.buying_power -= cost.coins;
+                self.potions -= cost.potions;
+                self.debt += cost.debt;
+                if card.can_overpay() && self.buying_power > 0 {
+                    self.overpay(state, card);
+                }
+                response::NoProblem
             },
-            Play(card) => {
-                let resp = try!(card.play(self, state, opponents.iter_mut(), pending));
-                self.put_in_play(card);
-                resp
+            BuyEvent(card) => {
+                match self.phase {
+                    phase::ActionPhase => self.enter_phase(phase::BuyPhase, state),
+                    phase::BuyPhase => (),
+                    phase => return response::WrongPhase(phase),
+                }
+                if !state.events.contains(&card) {
+                    return response::NotInKingdom(card);
+                }
+                if self.buys == 0 {
+                    return response::NoBuys;
+                }
+                if self.debt > 0 {
+                    return response::InDebt(self.debt);
+                }
+                if card.once_per_turn() && self.events_bought.contains(&card) {
+                    return response::Forbidden(card);
+                }
+                // Events aren't cards, so nothing makes them cheaper.
+                let cost = card.cost();
+                if !cost.can_pay(self.buying_power, self.potions) {
+                    return response::NotEnoughMoney{need: cost, have: Cost::new(self.buying_power, self.potions, 0)};
+                }
+                self.record(event::Bought{player: self.id, card: card});
+                self.buys -= 1;
+                self.buying_power -= cost.coins;
+                self.debt += cost.debt;
+                self.events_bought.push(card);
+                card.resolve_event(self, state, opponents);
+                response::NoProblem
             },
-            PlayAllMoney => {
-                let money: Vec<Card> = self.hand.iter().filter_map(|x| if x.is_money() && !x.is_action() { Some(*x) } else { None }).collect();
-                for card in money.iter() {
-                    try!(card.play(self, state, opponents.iter_mut(), None));
-                    self.put_in_play(*card);
+            EndPhase => match self.phase.next() {
+                Some(phase) => {
+                    self.enter_phase(phase, state);
+                    response::NoProblem
+                },
+                None => response::WrongPhase(self.phase),
+            },
+            PayDebt(amount) => {
+                match self.phase {
+                    phase::ActionPhase => self.enter_phase(phase::BuyPhase, state),
+                    phase::BuyPhase => (),
+                    phase => return response::WrongPhase(phase),
+                }
+                if amount > self.buying_power {
+                    return response::NotEnoughMoney{need: Cost::coins(amount), have: Cost::coins(self.buying_power)};
                 }
+                // Paying more than is owed only pays what's owed.
+                let paid = std::cmp::min(amount, self.debt);
+                self.debt -= paid;
+                self.buying_power -= paid;
                 response::NoProblem
             },
+            Play(card) => card.play(self, state, opponents),
+            SpendCoffers(amount) => {
+                match self.phase {
+                    phase::ActionPhase => self.enter_phase(phase::BuyPhase, state),
+                    phase::BuyPhase => (),
+                    phase => return response::WrongPhase(phase),
+                }
+                if amount > self.coffers {
+                    return response::NotEnoughCoffers{need: amount, have: self.coffers};
+                }
+                self.coffers -= amount;
+                self.buying_power += amount;
+                response::NoProblem
+            },
+            PlayAllMoney => {
+                // Treasures can do more than give coins, so the hand is
+                // checked again after each one. Bank is worth more the more
+                // treasure is already in play, so it goes last.
+                loop {
+                    let next = self.hand.iter().filter(|x| x.is_money() && !x.is_action()).min_by(|x| **x == card::Bank).map(|x| *x);
+                    match next {
+                        Some(card) => { try!(card.play(self, state, opponents)); },
+                        None => return response::NoProblem,
+                    }
+                }
+            },
         }
     }
 
-    fn answer_query(&self, q: Query, _: &mut GameState) -> Answer {
+    fn answer_query(&self, q: Query, state: &GameState, opponents: &RingBuf<PlayerHandle>) -> QueryResult<Answer> {
         use query::*;
-        macro_rules! answer (($e:expr) => (box $e as Answer))
-        match q {
-            BuyingPower => answer!(self.get_buying_power()),
-            Hand => answer!(self.get_hand()),
-            HandSize => answer!(self.get_hand_size()),
-            HasInHand(card) => answer!(self.has_in_hand(card)),
+        Ok(match q {
+            Actions => Number(self.get_actions()),
+            AllCards => CardList(self.get_all_cards()),
+            AvailableMoney => Number(self.get_available_money()),
+            Buys => Number(self.get_buys()),
+            BuyingPower => Number(self.get_buying_power()),
+            Coffers => Number(self.get_coffers()),
+            Debt => Number(self.get_debt()),
+            Potions => Number(self.get_potions()),
+            Count(card) => match state.count(card) {
+                Some(n) => Number(n),
+                None => return Err(NotInSupply(card)),
+            },
+            CurrentPhase => PhaseIs(self.get_phase()),
+            Discard => CardList(self.get_discard()),
+            EmbargoTokens(card) => Number(state.embargo_tokens(card)),
+            Events => CardList(state.events.clone()),
+            Hand => CardList(self.get_hand()),
+            HandSize => Number(self.get_hand_size()),
+            HasInHand(card) => Truth(self.has_in_hand(card)),
+            InPlay => CardList(self.get_in_play()),
+            Landmarks => CardList(state.landmarks.clone()),
+            NativeVillageMat => CardList(self.native_village.clone()),
+            NumberOf(card) => Number(self.number_of(card)),
+            Opponents => OpponentList(opponents.iter().map(|p| Opponent{
+                player: p.id,
+                score: p.final_score(state, Some(self).into_iter().chain(opponents.iter())),
+                deck_size: p.all_cards().len(),
+            }).collect()),
+            PileVictoryTokens(card) => Number(state.pile_vp(card)),
+            Supply => SupplyPiles(state.kingdom.clone()),
+            TavernMat => CardList(self.tavern.clone()),
+            TotalPoints => Points(self.final_score(state, opponents.iter())),
+            Trash => CardList(state.trash.clone()),
+        })
+    }
+
+    /// Ask the player to make a decision, and return their choice once it's
+    /// legal. A player whose turn it is gets the decision as a response to
+    /// their command, and anyone else gets it as a notification.
+    ///
+    /// Players who leave it up to the game, or who stop answering, get the
+    /// decision's default. Illegal choices from a `Connection` are answered
+    /// with `IllegalChoice` and asked again, while a `Strategy` can't be
+    /// asked again, so it gets the default instead.
+    fn decide(&mut self, state: &GameState, decision: Decision) -> Choice {
+        let fallback = decision.default_choice(state);
+        if fallback == decision::NoChoice {
+            return fallback;
+        }
+        let choice = match self.strategy.clone() {
+            Some(strategy) => match strategy.decide(&decision) {
+                decision::Undecided => ::strategy::ask_hooks(&**strategy, &decision, self.hand.as_slice(), self.actions),
+                choice => choice,
+            },
+            None => return self.decide_remotely(state, decision, fallback),
+        };
+        match choice {
+            decision::Undecided => fallback,
+            choice if decision.check(&choice, state).is_err() => fallback,
+            choice => choice,
         }
     }
 
-    /// Draw a card from the top of the player's deck and put it into their hand. If the deck
-    /// is empty, then the discard needs to be shuffled and turned into the new deck.
-    fn draw(&mut self) -> Option<Card> {
+    /// Ask for a decision over the player's `Connection`.
+    fn decide_remotely(&mut self, state: &GameState, decision: Decision, fallback: Choice) -> Choice {
+        let asked = if self.taking_turn {
+            self.resp_chan.send_opt(response::Decide(decision.clone())).is_ok()
+        } else {
+            self.notify_chan.send_opt(notify::Decide(decision.clone())).is_ok()
+        };
+        if !asked {
+            return fallback;
+        }
+        loop {
+            let choice = match self.choice_port.recv_opt() {
+                Ok(decision::Undecided) => fallback.clone(),
+                Ok(choice) => choice,
+                Err(()) => return fallback,
+            };
+            let resp = decision.check(&choice, state);
+            if !resp.is_err() {
+                // The player whose turn it is hears back once their command
+                // is done.
+                if !self.taking_turn {
+                    self.resp_chan.send(resp);
+                }
+                return choice;
+            }
+            if self.resp_chan.send_opt(resp).is_err() {
+                return fallback;
+            }
+        }
+    }
+
+    /// Record something that happened to this player.
+    fn record(&self, event: Event) {
+        self.log.record(event);
+    }
+
+    /// Take the top card off of the player's deck. If the deck is empty, then
+    /// the discard needs to be shuffled and turned into the new deck.
+    fn next_card(&mut self) -> Option<Card> {
         if self.deck.is_empty() && !self.discard.is_empty() {
             self.deck.push_all(self.discard.as_slice());
-            task_rng().shuffle(self.deck.as_mut_slice());
+            self.rng.shuffle(self.deck.as_mut_slice());
             self.discard.clear();
+            self.record(event::Shuffled{player: self.id, deck: self.deck.clone()});
+        }
+        self.deck.remove(0)
+    }
+
+    /// Shuffle the player's deck where it is, e.g. after Inn puts cards
+    /// into it.
+    fn shuffle_deck(&mut self) {
+        self.rng.shuffle(self.deck.as_mut_slice());
+        self.record(event::ShuffledDeck{player: self.id, deck: self.deck.clone()});
+    }
+
+    /// Reveal the top card of the player's deck and set it aside. Whatever
+    /// revealed it decides where it goes next.
+    fn reveal(&mut self) -> Option<Card> {
+        let revealed = self.next_card();
+        if let Some(card) = revealed {
+            self.set_aside.push(card);
+            self.record(event::Revealed{player: self.id, card: card});
+            self.record(event::Moved{player: self.id, card: card, from: event::Deck, to: event::SetAside});
         }
-        let drew = self.deck.remove(0);
+        revealed
+    }
+
+    /// Whether there's a card left to draw, counting the discard pile. The
+    /// −1 Card token doesn't count, so drawing until a hand is full should
+    /// check this instead of stopping when `draw` returns `None`.
+    fn can_draw(&self) -> bool {
+        !self.deck.is_empty() || !self.discard.is_empty()
+    }
+
+    /// Draw a card from the top of the player's deck and put it into their
+    /// hand. With the −1 Card token on their deck, the token is removed
+    /// instead.
+    fn draw(&mut self) -> Option<Card> {
+        if self.minus_card {
+            self.minus_card = false;
+            return None;
+        }
+        let drew = self.next_card();
         if let Some(card) = drew {
             self.hand.push(card);
+            self.record(event::Drew{player: self.id, card: card});
         }
         drew
     }
@@ -397,18 +1196,290 @@ impl PlayerHandle {
         }
     }
 
+    /// Move on to a new phase of the turn, and let the player know.
+    /// Action cards can only be played in the Action phase, so this is
+    /// also where Peddler's discount is counted up, or dropped again.
+    fn enter_phase(&mut self, phase: Phase, state: &mut GameState) {
+        self.phase = phase;
+        state.actions_in_play = match phase {
+            phase::BuyPhase => self.in_play.iter().filter(|x| x.is_action()).count(),
+            _ => 0,
+        };
+        // The player may have stopped listening, which is fine.
+        let _ = self.notify_chan.send_opt(notify::NewPhase(phase));
+    }
+
+    /// Carry out the start-of-turn effects of the Duration cards played last
+    /// turn. They're discarded in this turn's Cleanup.
+    fn resolve_durations(&mut self, state: &mut GameState) {
+        let durations = std::mem::replace(&mut self.durations, Vec::new());
+        for card in durations.iter() {
+            card.resolve_duration(self, state);
+        }
+    }
+
+    /// Keep `by`, e.g. Throne Room, in play for as long as the Duration
+    /// cards it played, if it played any. `before` is how many Duration
+    /// cards there were before it did.
+    fn stay_with_durations(&mut self, by: Card, before: uint) {
+        if self.durations.len() > before {
+            self.durations.push(by);
+        }
+    }
+
+    /// Carry out what cards in play do when they're discarded, e.g.
+    /// Alchemist can go back on top of the deck instead. This happens at the
+    /// start of Cleanup, before anything is discarded.
+    fn discard_from_play(&mut self, state: &mut GameState) {
+        for card in self.in_play.clone().iter() {
+            card.on_discard_from_play(self, state);
+        }
+    }
+
+    /// Clean up after a turn. Everything in play and in hand is discarded,
+    /// apart from Duration cards that still have something to do, and a new
+    /// hand is drawn. It's only 3 cards after playing Outpost.
+    fn cleanup(&mut self) {
+        let mut staying = self.durations.clone();
+        let in_play = std::mem::replace(&mut self.in_play, Vec::new());
+        for card in in_play.into_iter() {
+            match staying.iter().position(|x| *x == card) {
+                Some(i) => {
+                    staying.remove(i);
+                    self.in_play.push(card);
+                },
+                None => {
+                    self.discard.push(card);
+                    self.record(event::Discarded{player: self.id, card: card, from: event::InPlay});
+                },
+            }
+        }
+        self.discard_hand();
+        let extra = std::mem::replace(&mut self.extra_cards, 0);
+        self.draw_n(if self.outpost { 3 } else { 5 } + extra);
+        for card in self.end_of_turn.clone().into_iter() {
+            self.move_card(card, event::EndOfTurn, event::Hand);
+        }
+        self.actions = 0;
+        self.buys = 0;
+        self.buying_power = 0;
+        self.potions = 0;
+        self.actions_played = 0;
+        self.played.clear();
+        self.bought.clear();
+        self.events_bought.clear();
+        self.gained_last_turn = self.turn_gains;
+        self.travelling_fair = false;
+        self.copper_bonus = 0;
+        self.contraband.clear();
+    }
+
+    /// Ask how much more than `card`'s cost the player wants to pay, out of
+    /// what they have left, and carry out what overpaying does.
+    fn overpay(&mut self, state: &mut GameState, card: Card) {
+        let options: Vec<String> = range(0, self.buying_power + 1).map(|x| format!("Overpay ${}", x)).collect();
+        let decision = Decision::option(card, options.iter().map(|x| x.as_slice()).collect());
+        match self.decide(state, decision).option() {
+            Some(amount) if amount > 0 => {
+                self.buying_power -= amount;
+                card.on_overpay(self, state, amount);
+            },
+            _ => (),
+        }
+    }
+
+    /// Give the player victory point tokens.
+    fn take_vp_tokens(&mut self, amount: uint) {
+        self.vp_tokens += amount;
+        self.record(event::TookVictoryTokens{player: self.id, amount: amount});
+    }
+
     /// Discard your hand.
     fn discard_hand(&mut self) {
-        self.discard.push_all(self.hand.as_slice());
-        self.hand.clear();
+        self.discard_all(event::Hand);
+    }
+
+    /// Put your entire deck into the discard pile, e.g. for Chancellor.
+    fn discard_deck(&mut self) {
+        self.discard_all(event::Deck);
+    }
+
+    /// Discard everything in one of the player's zones.
+    fn discard_all(&mut self, from: Zone) {
+        let cards = std::mem::replace(self.zone(from), Vec::new());
+        for card in cards.into_iter() {
+            self.discard.push(card);
+            self.record(event::Discarded{player: self.id, card: card, from: from});
+        }
+    }
+
+    /// Gain a card from the supply, putting it into the discard pile.
+    fn gain(&mut self, state: &mut GameState, card: Card) -> Response {
+        self.gain_to(state, card, event::DiscardPile)
+    }
+
+    /// Like gain(), but the card goes on top of the player's deck.
+    fn gain_to_deck(&mut self, state: &mut GameState, card: Card) -> Response {
+        self.gain_to(state, card, event::Deck)
+    }
+
+    /// Like gain(), but the card goes into the player's hand.
+    fn gain_to_hand(&mut self, state: &mut GameState, card: Card) -> Response {
+        self.gain_to(state, card, event::Hand)
+    }
+
+    /// Every gain from a pile comes through here. Before the card is
+    /// taken, Reactions like Trader can replace the gain; afterwards,
+    /// Reactions like Watchtower can move the card, and then the card's own
+    /// `on_gain()` happens. Other players respond later, in
+    /// `resolve_gains()`.
+    fn gain_to(&mut self, state: &mut GameState, card: Card, to: Zone) -> Response {
+        // The possessor gets to react once the turn is over, in
+        // `gain_possessed()`.
+        if self.possessed_by.is_none() && state.can_take(card) && self.react(state, reaction::WouldGain(card)) == reaction::Blocked {
+            return response::NoProblem;
+        }
+        let from = if state.non_supply.contains_key(&card) { event::NonSupply } else { event::Supply };
+        let resp = state.take(card);
+        if resp.is_err() {
+            return resp;
+        }
+        self.receive(card, from, to);
+        self.after_gain(state, card, to);
+        resp
+    }
+
+    /// Whatever happens once a card has been gained, wherever it came from.
+    fn after_gain(&mut self, state: &mut GameState, card: Card, to: Zone) {
+        // It's the possessor's gain, which happens once the turn is over.
+        if self.possessed_by.is_some() {
+            return;
+        }
+        state.gained.push((self.id, card));
+        self.turn_gains += 1;
+        let copies = self.copies_in(to, card);
+        self.react(state, reaction::Gained(card, to));
+        // If Watchtower has moved the card, it's not where it landed any
+        // more, and there could be another copy there instead.
+        let mut landed = if self.copies_in(to, card) < copies { None } else { Some(to) };
+        // Royal Seal in play and Travelling Fair both let the player put it
+        // onto their deck.
+        let seal = if self.in_play.contains(&card::RoyalSeal) { Some(card::RoyalSeal) } else if self.travelling_fair { Some(card::TravellingFair) } else { None };
+        match (landed, seal) {
+            (Some(zone), Some(by)) if zone != event::Deck => {
+                let default = !card.is_victory() && !card.is_curse();
+                if self.decide(state, Decision::yes_no(by, decision::TopDecking, Some(card), default)).is_yes() {
+                    self.move_card(card, zone, event::Deck);
+                    landed = Some(event::Deck);
+                }
+            },
+            _ => (),
+        }
+        self.call_reserves(state, reaction::Gained(card, to));
+        card.on_gain(self, state, landed);
+    }
+
+    /// The number of copies of `card` in one of the player's zones.
+    fn copies_in(&mut self, zone: Zone, card: Card) -> uint {
+        self.zone(zone).iter().filter(|x| **x == card).count()
+    }
+
+    /// Give everyone but the player who gained each card a chance to
+    /// respond to it, e.g. with Fool's Gold, or to be hit by it, e.g. by
+    /// Ill-Gotten Gains. Anything gained in response is handled too.
+    fn resolve_gains(&mut self, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) {
+        loop {
+            let gained = std::mem::replace(&mut state.gained, Vec::new());
+            if gained.is_empty() {
+                break;
+            }
+            for &(gainer, card) in gained.iter() {
+                for other in Some(&mut *self).into_iter().chain(opponents.iter_mut()).filter(|x| x.id != gainer) {
+                    card.on_gained_by_other(other, state);
+                    other.react(state, reaction::OtherGained(card));
+                }
+            }
+        }
+    }
+
+    /// Gain a card out of the trash into `to`, e.g. for Thief. It's like
+    /// any other gain once it's out. Returns false if the card isn't there.
+    fn gain_from_trash(&mut self, state: &mut GameState, card: Card, to: Zone) -> bool {
+        let i = match state.trash.iter().position(|x| *x == card) {
+            Some(i) => i,
+            None => return false,
+        };
+        state.trash.remove(i);
+        self.receive(card, event::TrashPile, to);
+        self.after_gain(state, card, to);
+        true
+    }
+
+    /// Exchange a Traveller in play for the next card in its line, e.g.
+    /// Page for Treasure Hunter. The Traveller goes back to its pile, and
+    /// the new card goes into the discard pile without being gained.
+    /// Returns false if either card can't be moved.
+    fn exchange(&mut self, state: &mut GameState, card: Card, for_card: Card) -> bool {
+        if !state.can_take(for_card) || !self.return_to_pile(state, card, event::InPlay) {
+            return false;
+        }
+        state.take(for_card);
+        self.receive(for_card, event::NonSupply, event::DiscardPile);
+        true
+    }
+
+    /// Trash the top card of a supply pile, e.g. for Salt the Earth.
+    /// Returns false if `card` isn't on top of its pile.
+    fn trash_from_supply(&mut self, state: &mut GameState, card: Card) -> bool {
+        if state.count(card).unwrap_or(0) == 0 || state.take(card).is_err() {
+            return false;
+        }
+        state.trash.push(card);
+        self.record(event::Trashed{player: self.id, card: card, from: event::Supply});
+        true
+    }
+
+    /// Put a card back on its pile, e.g. Spoils once it's been played.
+    /// Returns false if the card wasn't in `from`.
+    fn return_to_pile(&mut self, state: &mut GameState, card: Card, from: Zone) -> bool {
+        if !self.take(from, card) {
+            return false;
+        }
+        state.put_back(card);
+        self.record(event::Returned{player: self.id, card: card, from: from});
+        true
+    }
+
+    /// Put a card the player has just gained into `to`, and record it.
+    /// While another player has possessed them, it's that player's card
+    /// instead, and it's kept aside for `gain_possessed()`.
+    fn receive(&mut self, card: Card, from: Zone, to: Zone) {
+        if self.possessed_by.is_some() {
+            self.possessed_gains.push((card, from));
+            return;
+        }
+        self.put(to, card);
+        self.record(event::Gained{player: self.id, card: card, from: from, to: to});
+    }
+
+    /// Gain a card that the player this one possessed would have gained.
+    /// It's been taken from `from` already, so if Trader replaces it, it
+    /// goes back on its pile if it can. Otherwise it's gained into the
+    /// discard pile like any other card.
+    fn gain_possessed(&mut self, state: &mut GameState, card: Card, from: Zone) {
+        if from != event::TrashPile && state.can_put_back(card) && self.react(state, reaction::WouldGain(card)) == reaction::Blocked {
+            state.put_back(card);
+            return;
+        }
+        self.receive(card, from, event::DiscardPile);
+        self.after_gain(state, card, event::DiscardPile);
     }
 
     /// Discard a card from the player's hand. It fails if that card isn't
     /// in the player's hand.
     fn discard(&mut self, card: Card) {
-        match self.remove_from_hand(card) {
-            true => self.discard.push(card),
-            false => panic!("player tried to discard {}, but doesn't have it!", card),
+        if !self.move_card(card, event::Hand, event::DiscardPile) {
+            panic!("player tried to discard {}, but doesn't have it!", card);
         }
     }
 
@@ -416,7 +1487,10 @@ impl PlayerHandle {
     /// discard pile.
     fn put_in_play(&mut self, card: Card) {
         match self.remove_from_hand(card) {
-            true => self.in_play.push(card),
+            true => {
+                self.in_play.push(card);
+                self.record(event::Played{player: self.id, card: card});
+            },
             false => panic!("player tried to put {} in play, but doesn't have it!", card),
         }
     }
@@ -424,27 +1498,174 @@ impl PlayerHandle {
     /// Trash a card from the player's hand. It fails if that card isn't
     /// in the player's hand.
     fn trash(&mut self, state: &mut GameState, card: Card) {
-        match self.remove_from_hand(card) {
-            true => state.trash.push(card),
-            false => panic!("player tried to trash {}, but doesn't have it!", card),
+        if !self.trash_from(state, card, event::Hand) {
+            panic!("player tried to trash {}, but doesn't have it!", card);
+        }
+    }
+
+    /// Like trash(), but the card is taken from the playing area, e.g.
+    /// for Feast. Does nothing if the card isn't in play.
+    fn trash_from_play(&mut self, state: &mut GameState, card: Card) {
+        self.trash_from(state, card, event::InPlay);
+    }
+
+    /// Trash a card from any of the player's zones. Returns false if the
+    /// card wasn't there. While the player is possessed, the card is set
+    /// aside instead, to be discarded once the turn is over.
+    fn trash_from(&mut self, state: &mut GameState, card: Card, from: Zone) -> bool {
+        if !self.take(from, card) {
+            return false;
         }
+        if self.possessed_by.is_some() {
+            self.set_aside.push(card);
+            self.record(event::Moved{player: self.id, card: card, from: from, to: event::SetAside});
+        } else {
+            state.trash.push(card);
+            self.record(event::Trashed{player: self.id, card: card, from: from});
+        }
+        card.on_trash(self, state);
+        true
+    }
+
+    /// Move a card between two of the player's zones. Returns false if the
+    /// card wasn't where it was supposed to be.
+    fn move_card(&mut self, card: Card, from: Zone, to: Zone) -> bool {
+        if !self.take(from, card) {
+            return false;
+        }
+        self.put(to, card);
+        self.record(match to {
+            event::DiscardPile => event::Discarded{player: self.id, card: card, from: from},
+            _ => event::Moved{player: self.id, card: card, from: from, to: to},
+        });
+        true
+    }
+
+    /// The cards in one of the player's zones. The top of the deck is
+    /// the front.
+    fn zone(&mut self, zone: Zone) -> &mut Vec<Card> {
+        match zone {
+            event::Hand => &mut self.hand,
+            event::Deck => &mut self.deck,
+            event::DiscardPile => &mut self.discard,
+            event::InPlay => &mut self.in_play,
+            event::SetAside => &mut self.set_aside,
+            event::NativeVillageMat => &mut self.native_village,
+            event::IslandMat => &mut self.island,
+            event::NextTurn => &mut self.next_turn,
+            event::TavernMat => &mut self.tavern,
+            event::EndOfTurn => &mut self.end_of_turn,
+            _ => panic!("{} doesn't belong to a player", zone),
+        }
+    }
+
+    /// Take a card out of one of the player's zones without recording it.
+    fn take(&mut self, zone: Zone, card: Card) -> bool {
+        let cards = self.zone(zone);
+        match cards.iter().position(|x| *x == card) {
+            Some(i) => cards.remove(i).is_some(),
+            None => false,
+        }
+    }
+
+    /// Put a card into one of the player's zones without recording it.
+    fn put(&mut self, zone: Zone, card: Card) {
+        match zone {
+            event::Deck => self.deck.insert(0, card),
+            _ => self.zone(zone).push(card),
+        }
+    }
+
+    /// Every card the player owns, wherever it is.
+    fn all_cards(&self) -> Vec<Card> {
+        self.deck.iter().chain(self.hand.iter()).chain(self.discard.iter()).chain(self.in_play.iter()).chain(self.set_aside.iter())
+            .chain(self.native_village.iter()).chain(self.island.iter()).chain(self.next_turn.iter()).chain(self.tavern.iter())
+            .chain(self.end_of_turn.iter()).map(|x| *x).collect()
+    }
+
+    /// Count up the victory points from all of the player's cards and
+    /// tokens.
+    fn score(&self) -> int {
+        let cards = self.all_cards();
+        cards.iter().fold(self.vp_tokens as int, |total, card| total + card.victory_points(cards.as_slice()))
+    }
+
+    /// The player's score with the game's scoring rules added on, e.g. from
+    /// Landmarks. `players` can include this player too; they're skipped.
+    fn final_score<'a, T: Iterator<&'a PlayerHandle>>(&self, state: &GameState, players: T) -> int {
+        let cards = self.all_cards();
+        let others: Vec<Vec<Card>> = players.filter(|x| x.id != self.id).map(|x| x.all_cards()).collect();
+        let empty_piles = state.empty_piles();
+        let sheet = ScoreSheet{cards: cards.as_slice(), others: others.as_slice(), empty_piles: empty_piles.as_slice()};
+        state.scoring.iter().fold(self.score(), |total, rule| total + rule.score(&sheet))
+    }
+
+    /// Swap everything that decides what the player does with `other`, so
+    /// that `other`'s connection or strategy plays this player's turn.
+    fn swap_controls(&mut self, other: &mut PlayerHandle) {
+        use std::mem::swap;
+        swap(&mut self.choice_port, &mut other.choice_port);
+        swap(&mut self.cmd_port, &mut other.cmd_port);
+        swap(&mut self.done_port, &mut other.done_port);
+        swap(&mut self.notify_chan, &mut other.notify_chan);
+        swap(&mut self.query_a_chan, &mut other.query_a_chan);
+        swap(&mut self.query_q_port, &mut other.query_q_port);
+        swap(&mut self.resp_chan, &mut other.resp_chan);
+        swap(&mut self.strategy, &mut other.strategy);
     }
 
     /// Utility method used for actions like discarding and trashing. Returns true
     /// if the card was successfully removed from the hand, otherwise false.
     fn remove_from_hand(&mut self, card: Card) -> bool {
-        match self.hand.iter().position(|x| *x == card) {
-            Some(i) => self.hand.remove(i).is_some(),
-            None => false,
-        }
+        self.take(event::Hand, card)
     }
 }
 
 impl Player for PlayerHandle {
+    /// Returns the number of actions the player has left this turn.
+    fn get_actions(&self) -> uint {
+        self.actions
+    }
+
+    /// Returns every card the player owns, wherever it is.
+    fn get_all_cards(&self) -> Vec<Card> {
+        self.all_cards()
+    }
+
+    /// Returns the total value of the treasures in the player's hand.
+    fn get_available_money(&self) -> uint {
+        self.hand.iter().filter(|x| x.is_money()).fold(0, |total, card| total + card.coin_value())
+    }
+
+    /// Returns the number of buys the player has left this turn.
+    fn get_buys(&self) -> uint {
+        self.buys
+    }
+
     fn get_buying_power(&self) -> uint {
         self.buying_power
     }
 
+    /// Returns the number of Coffers the player has saved up.
+    fn get_coffers(&self) -> uint {
+        self.coffers
+    }
+
+    /// Returns the debt the player owes.
+    fn get_debt(&self) -> uint {
+        self.debt
+    }
+
+    /// Returns the number of potions the player has to spend.
+    fn get_potions(&self) -> uint {
+        self.potions
+    }
+
+    /// Returns a clone of the player's discard pile.
+    fn get_discard(&self) -> Vec<Card> {
+        self.discard.clone()
+    }
+
     /// Returns a clone of the player's hand.
     fn get_hand(&self) -> Vec<Card> {
         self.hand.clone()
@@ -455,35 +1676,259 @@ impl Player for PlayerHandle {
         self.hand.len()
     }
 
+    /// Returns a clone of the cards the player has in play.
+    fn get_in_play(&self) -> Vec<Card> {
+        self.in_play.clone()
+    }
+
+    /// Returns the phase of the turn the player is in.
+    fn get_phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// Returns the victory points from the player's cards and tokens, not
+    /// counting the game's scoring rules. See `final_score()`.
+    fn get_total_points(&self) -> int {
+        self.score()
+    }
+
     /// Returns true only if the provided card is currently held in the
     /// player's hand.
     fn has_in_hand(&self, card: Card) -> bool {
         self.hand.iter().any(|x| *x == card)
     }
-}
 
-struct PendingPlay {
-    index: uint,
-    discarding: Vec<Card>,
-    trashing: Vec<Card>,
-}
-
-impl PendingPlay {
-    fn new(index: uint) -> PendingPlay {
-        PendingPlay{
-            index: index,
-            discarding: Vec::new(),
-            trashing: Vec::new(),
-        }
+    /// Returns how many copies of a card the player owns.
+    fn number_of(&self, card: Card) -> uint {
+        self.all_cards().iter().filter(|x| **x == card).count()
     }
 }
 
 /// Game state which keeps track of things like how many cards are
 /// in each pile, what's in the trash, etc.
 #[deriving(Default)]
-struct GameState {
+pub struct GameState {
     kingdom: HashMap<Card, uint>,
     trash: Vec<Card>,
+
+    /// How much cheaper cards are this turn, e.g. from Bridge.
+    cost_reduction: uint,
+
+    /// How much cheaper Action cards are this turn, e.g. from Quarry.
+    action_cost_reduction: uint,
+
+    /// The number of Action cards the active player has in play while
+    /// they're buying, which makes Peddler cheaper. It's counted when the
+    /// Buy phase starts and is zero in the other phases.
+    actions_in_play: uint,
+
+    /// Embargo tokens on each supply pile.
+    embargo: HashMap<Card, uint>,
+
+    /// Piles that aren't part of the supply, e.g. Tournament's Prizes.
+    /// Cards can be gained from them, but not bought.
+    non_supply: HashMap<Card, uint>,
+
+    /// The extra kingdom pile for Young Witch, if she's in the game.
+    bane: Option<Card>,
+
+    /// Set while Princess is in play, which makes everything $2 cheaper.
+    princess: bool,
+
+    /// Cards gained that the other players haven't responded to yet, and
+    /// who gained them. See `PlayerHandle::resolve_gains()`.
+    gained: Vec<(uint, Card)>,
+
+    /// Supply piles made of different cards, e.g. Ruins, top card first.
+    /// Each one is in `kingdom` under its top card, with the size of the
+    /// whole pile, so only the top card can be bought or gained.
+    mixed_piles: Vec<Vec<Card>>,
+
+    /// What each mixed pile started with, which is still known once the
+    /// pile has run out.
+    mixed_pile_contents: Vec<Vec<Card>>,
+
+    /// The Events that can be bought this game, e.g. Ball.
+    events: Vec<Card>,
+
+    /// The Landmarks in this game, e.g. Wall.
+    landmarks: Vec<Card>,
+
+    /// Rules that add to everyone's score at the end of the game, e.g.
+    /// from Landmarks.
+    scoring: Vec<Box<ScoringRule + 'static>>,
+
+    /// Victory point tokens on supply piles, e.g. Temple's.
+    vp_piles: HashMap<Card, uint>,
+
+    /// Players' tokens on supply piles, e.g. from Ferry.
+    tokens: token::PileTokens,
+
+    /// The player whose turn it is.
+    active_player: uint,
+}
+
+impl GameState {
+    /// What a card costs right now, after any reductions. Only the coins
+    /// are reduced, and they never go below zero.
+    fn cost(&self, card: Card) -> Cost {
+        let mut reduction = self.cost_reduction;
+        if card.is_action() {
+            reduction += self.action_cost_reduction;
+        }
+        if card == card::Peddler {
+            reduction += 2 * self.actions_in_play;
+        }
+        if self.princess {
+            reduction += 2;
+        }
+        // The −$2 token only counts on its owner's turns.
+        if self.tokens.on(self.active_player, card).contains(&token::MinusCost) {
+            reduction += 2;
+        }
+        card.cost().minus(reduction)
+    }
+
+    /// Returns the number of copies of a card left in the supply, or None
+    /// if it isn't part of this game.
+    fn count(&self, card: Card) -> Option<uint> {
+        self.kingdom.get(&card).map(|x| *x)
+    }
+
+    /// The number of copies of a card left in its pile outside the supply,
+    /// e.g. a Prize.
+    fn non_supply_count(&self, card: Card) -> uint {
+        self.non_supply.get(&card).map_or(0, |x| *x)
+    }
+
+    /// The number of victory point tokens on a card's supply pile.
+    fn pile_vp(&self, card: Card) -> uint {
+        self.vp_piles.get(&card).map_or(0, |x| *x)
+    }
+
+    fn add_pile_vp(&mut self, card: Card, amount: uint) {
+        use std::collections::hash_map::{Vacant, Occupied};
+        match self.vp_piles.entry(card) {
+            Vacant(entry) => { entry.set(amount); },
+            Occupied(entry) => { *entry.into_mut() += amount; },
+        }
+    }
+
+    /// Take every victory point token off a card's supply pile. Returns
+    /// how many there were.
+    fn take_pile_vp(&mut self, card: Card) -> uint {
+        self.vp_piles.remove(&card).unwrap_or(0)
+    }
+
+    /// The number of Embargo tokens on a card's supply pile.
+    fn embargo_tokens(&self, card: Card) -> uint {
+        self.embargo.get(&card).map_or(0, |x| *x)
+    }
+
+    fn add_embargo_token(&mut self, card: Card) {
+        use std::collections::hash_map::{Vacant, Occupied};
+        match self.embargo.entry(card) {
+            Vacant(entry) => { entry.set(1); },
+            Occupied(entry) => { *entry.into_mut() += 1; },
+        }
+    }
+
+    /// Returns true if there's a copy of `card` left to take, in the supply
+    /// or outside it.
+    fn can_take(&self, card: Card) -> bool {
+        self.count(card).unwrap_or(0) + self.non_supply_count(card) > 0
+    }
+
+    /// Take a card from its supply pile, or its pile outside the supply,
+    /// failing if it isn't in the game or the pile is empty.
+    fn take(&mut self, card: Card) -> Response {
+        use std::collections::hash_map::{Vacant, Occupied};
+        if let Some(i) = self.mixed_piles.iter().position(|x| x.first() == Some(&card)) {
+            // The next card down is on top now, and the pile goes by its
+            // name. An empty pile keeps the name of its last card.
+            let pile = &mut self.mixed_piles[i];
+            pile.remove(0);
+            self.kingdom.remove(&card);
+            self.kingdom.insert(pile.first().map_or(card, |x| *x), pile.len());
+            return response::NoProblem;
+        }
+        let piles = if self.non_supply.contains_key(&card) { &mut self.non_supply } else { &mut self.kingdom };
+        match piles.entry(card) {
+            Vacant(_) => response::NotInKingdom(card),
+            Occupied(ref entry) if *entry.get() == 0 => response::PileEmpty(card),
+            Occupied(entry) => {
+                *entry.into_mut() -= 1;
+                response::NoProblem
+            },
+        }
+    }
+
+    /// Whether `put_back` can return a card to its pile. Cards can't go
+    /// back into mixed piles.
+    fn can_put_back(&self, card: Card) -> bool {
+        (self.kingdom.contains_key(&card) || self.non_supply.contains_key(&card))
+            && !self.mixed_piles.iter().any(|x| x.contains(&card))
+    }
+
+    /// Put a card back on top of its pile, e.g. Spoils once it's been
+    /// played.
+    fn put_back(&mut self, card: Card) {
+        let piles = if self.non_supply.contains_key(&card) { &mut self.non_supply } else { &mut self.kingdom };
+        *piles.get_mut(&card).expect("returned a card without a pile") += 1;
+    }
+
+    /// The top card of the mixed pile made of cards of type `typ`, e.g.
+    /// the Ruins pile. Returns None if the pile is empty or isn't in the
+    /// game.
+    fn top_of(&self, typ: card::CardType) -> Option<Card> {
+        self.mixed_piles.iter().filter_map(|x| x.first()).find(|x| x.has_type(typ)).map(|x| *x)
+    }
+
+    /// How many of each card are in the supply, counting every card in a
+    /// mixed pile rather than only the top one.
+    fn supply_cards(&self) -> HashMap<Card, uint> {
+        use std::collections::hash_map::{Vacant, Occupied};
+        let mut cards = self.kingdom.clone();
+        for pile in self.mixed_piles.iter() {
+            if let Some(top) = pile.first() {
+                cards.remove(top);
+            }
+        }
+        for card in self.mixed_piles.iter().flat_map(|x| x.iter()) {
+            match cards.entry(*card) {
+                Vacant(entry) => { entry.set(1); },
+                Occupied(entry) => { *entry.into_mut() += 1; },
+            }
+        }
+        cards
+    }
+
+    /// The cards whose supply piles have run out. That's every card a mixed
+    /// pile started with, not only the last one on it.
+    fn empty_piles(&self) -> Vec<Card> {
+        let mut empty: Vec<Card> = self.kingdom.iter().filter(|&(_, count)| *count == 0).map(|(card, _)| *card).collect();
+        for pile in self.mixed_pile_contents.iter() {
+            if pile.iter().any(|x| empty.contains(x)) {
+                for card in pile.iter() {
+                    if !empty.contains(card) {
+                        empty.push(*card);
+                    }
+                }
+            }
+        }
+        empty
+    }
+
+    /// Returns true if the game should end, either because the Province or
+    /// Colony pile is empty or because `empty_limit` supply piles are.
+    fn is_over(&self, empty_limit: uint) -> bool {
+        if self.kingdom.get(&card::Province) == Some(&0) || self.kingdom.get(&card::Colony) == Some(&0) {
+            return true;
+        }
+        self.kingdom.values().filter(|x| **x == 0).count() >= empty_limit
+    }
 }
 
-type Answer = Box<Any + Send>;
+#[cfg(test)]
+#[path = "tests/game.rs"]
+mod tests;
@@ -1,5 +1,24 @@
+use super::decision::Decision;
+use super::phase::Phase;
+
 pub enum Notification {
     YourTurn(uint),
-    Militia,
+    NewPhase(Phase),
+
+    /// Something another player did needs you to decide something, e.g.
+    /// whether to reveal a Reaction to their attack. Answer it with
+    /// `Connection::choose()`.
+    Decide(Decision),
     GameOver,
 }
+
+impl Notification {
+    /// Returns true if the game is waiting on a `Choice` for this
+    /// notification.
+    pub fn expects_choice(&self) -> bool {
+        match *self {
+            Decide(_) => true,
+            YourTurn(_) | NewPhase(_) | GameOver => false,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+/// The phases of a turn, in the order they're played.
+#[deriving(Clone, PartialEq, Show)]
+pub enum Phase {
+    ActionPhase,
+    BuyPhase,
+    NightPhase,
+    CleanupPhase,
+}
+
+impl Phase {
+    /// The phase that follows this one, if the player can move on to it
+    /// themselves. Cleanup only happens once the player says they're done.
+    pub fn next(&self) -> Option<Phase> {
+        match *self {
+            ActionPhase => Some(BuyPhase),
+            BuyPhase => Some(NightPhase),
+            NightPhase | CleanupPhase => None,
+        }
+    }
+}
@@ -1,7 +1,90 @@
+use std::collections::HashMap;
+
+use card::Card;
+use phase::Phase;
+
 #[deriving(Show)]
 pub enum Query {
+    Actions,
+    AllCards,
+    AvailableMoney,
+    Buys,
     BuyingPower,
+    Coffers,
+    Count(Card),
+    CurrentPhase,
+    Debt,
+    Discard,
+    EmbargoTokens(Card),
+    Events,
     Hand,
     HandSize,
-    HasInHand(::card::Card),
+    HasInHand(Card),
+    InPlay,
+    Landmarks,
+    NativeVillageMat,
+    NumberOf(Card),
+    Opponents,
+    PileVictoryTokens(Card),
+    Potions,
+    Supply,
+    TavernMat,
+    TotalPoints,
+    Trash,
+}
+
+/// The game's answer to a `Query`. Each query always gets the same kind of
+/// answer, and the methods on `Connection` unpack it into the right type.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Answer {
+    /// For Hand, Discard, InPlay, NativeVillageMat, TavernMat, AllCards,
+    /// Events, Landmarks and Trash.
+    CardList(Vec<Card>),
+
+    /// For Actions, Buys, BuyingPower, AvailableMoney, Coffers, Count, Debt,
+    /// EmbargoTokens, HandSize, NumberOf, PileVictoryTokens and Potions.
+    Number(uint),
+
+    /// For TotalPoints.
+    Points(int),
+
+    /// For HasInHand.
+    Truth(bool),
+
+    /// For CurrentPhase.
+    PhaseIs(Phase),
+
+    /// For Opponents.
+    OpponentList(Vec<Opponent>),
+
+    /// For Supply.
+    SupplyPiles(HashMap<Card, uint>),
+}
+
+/// Why a query couldn't be answered.
+#[deriving(Clone, Show, PartialEq)]
+pub enum QueryError {
+    /// The game is over, so there's nobody left to ask.
+    GameOver,
+
+    /// The card doesn't have a pile in this game's supply.
+    NotInSupply(Card),
+
+    /// The game answered with the wrong kind of `Answer` for the query.
+    WrongAnswer,
+}
+
+pub type QueryResult<T> = Result<T, QueryError>;
+
+/// What a player can find out about one of their opponents.
+#[deriving(Clone, Show, PartialEq)]
+pub struct Opponent {
+    /// The opponent's player number.
+    pub player: uint,
+
+    /// The victory points the opponent would have if the game ended now.
+    pub score: int,
+
+    /// The number of cards the opponent owns.
+    pub deck_size: uint,
 }
@@ -1,9 +1,127 @@
-use super::card::Card;
-
-#[deriving(Show)]
-pub enum Reaction {
-    NotImplemented,
-    MilitiaDiscard(Card),
-    RevealMoat,
-    OtherReaction,
+//! Reaction cards, which players can reveal from their hand when something
+//! happens to them, and Reserve cards, which they can call from their
+//! Tavern mat.
+//!
+//! Attacks go through `attack()`, which gives each opponent a chance to
+//! react before the attack hits them. Gaining a card gives its owner a
+//! chance to react before and after, and everyone else once the command it
+//! happened in is done. A Reaction card says what it responds to in
+//! `Card::reacts_to()`, and what it does in `Card::react()`. Reserve cards
+//! do the same in `Card::can_call()` and `Card::call()`.
+
+use card::{Card, Champion, Lighthouse};
+use decision;
+use decision::Decision;
+use event;
+use event::Zone;
+use super::{GameState, PlayerHandle};
+
+/// Something a Reaction card can respond to.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Trigger {
+    /// Another player played this Attack card.
+    Attacked(Card),
+
+    /// The player is about to gain this card. Blocking it means they
+    /// don't, e.g. Trader gains a Silver instead.
+    WouldGain(Card),
+
+    /// The player has just gained this card, which went to the zone given.
+    Gained(Card, Zone),
+
+    /// Another player gained this card.
+    OtherGained(Card),
+
+    /// The player bought this card, which they've already gained.
+    Bought(Card),
+
+    /// The player's turn has started, and their Duration cards are done.
+    TurnStarted,
+
+    /// The player has just finished resolving this Action card.
+    ActionResolved(Card),
+
+    /// The player's Buy phase is over, before Cleanup.
+    BuyPhaseEnded,
+}
+
+impl Trigger {
+    /// The card that set off the trigger, if there is one.
+    pub fn subject(&self) -> Option<Card> {
+        match *self {
+            Attacked(card) | WouldGain(card) | Gained(card, _) | OtherGained(card) | Bought(card) | ActionResolved(card) => Some(card),
+            TurnStarted | BuyPhaseEnded => None,
+        }
+    }
+}
+
+/// What revealing a Reaction card did.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Outcome {
+    /// The player is unaffected by whatever triggered the reaction, e.g.
+    /// Moat blocking an attack.
+    Blocked,
+    NotBlocked,
+}
+
+/// Play an attack against each opponent, in turn order. Each of them first
+/// gets a chance to reveal Reaction cards, and `f` only hits those who are
+/// still affected afterwards. Players with a Lighthouse or Champion in play
+/// can still react, but are never affected.
+pub fn attack<'a, T: Iterator<&'a mut PlayerHandle>>(card: Card, state: &mut GameState, mut opponents: T, f: |&mut PlayerHandle, &mut GameState|) {
+    for opponent in opponents {
+        let immune = opponent.in_play.contains(&Lighthouse) || opponent.in_play.contains(&Champion);
+        if opponent.react(state, Attacked(card)) == NotBlocked && !immune {
+            f(opponent, state);
+        }
+    }
+}
+
+impl PlayerHandle {
+    /// Offer each Reaction card in the player's hand that responds to
+    /// `trigger`, one at a time, and carry out the ones they reveal. Each
+    /// card can only be revealed once.
+    pub fn react(&mut self, state: &mut GameState, trigger: Trigger) -> Outcome {
+        let mut outcome = NotBlocked;
+        let mut offered: Vec<Card> = Vec::new();
+        loop {
+            let card = match self.hand.iter().find(|x| x.reacts_to(&trigger) && !offered.contains(*x)) {
+                Some(card) => *card,
+                None => break,
+            };
+            offered.push(card);
+            // Nobody swaps a card they're gaining for a Silver unless it's a
+            // Curse.
+            let default = match trigger {
+                WouldGain(gained) => gained.is_curse(),
+                _ => true,
+            };
+            if !self.decide(state, Decision::yes_no(card, decision::Revealing, trigger.subject(), default)).is_yes() {
+                continue;
+            }
+            self.record(event::Reacted{player: self.id, card: card});
+            if card.react(self, state, &trigger) == Blocked {
+                outcome = Blocked;
+            }
+        }
+        outcome
+    }
+
+    /// Offer each card on the player's Tavern mat that can be called for
+    /// `trigger`, one copy at a time, and carry out the ones they call.
+    pub fn call_reserves(&mut self, state: &mut GameState, trigger: Trigger) {
+        for card in self.tavern.clone().into_iter() {
+            if !self.tavern.contains(&card) || !card.can_call(self, state, &trigger) {
+                continue;
+            }
+            // Nobody copies a Curse with Duplicate.
+            let default = match trigger {
+                Gained(gained, _) => !gained.is_curse(),
+                _ => true,
+            };
+            if self.decide(state, Decision::yes_no(card, decision::Calling, trigger.subject(), default)).is_yes() {
+                card.call(self, state, &trigger);
+            }
+        }
+    }
 }
@@ -0,0 +1,241 @@
+//! Cards defined at runtime.
+//!
+//! Besides the cards built into the engine, games can use custom cards
+//! loaded from a JSON file. Custom cards can't do anything fancy, but they
+//! can be any mix of "vanilla" effects: +Cards, +Actions, +Buys and +Coins.
+//! That's enough for playtesting variants without recompiling.
+//!
+//! ~~~ignore
+//! {"cards": [
+//!     {"name": "Hamlet Lite", "cost": 2, "types": ["Action"], "set": "Playtest",
+//!      "text": "+1 Card, +1 Action, +1 Buy", "cards": 1, "actions": 1, "buys": 1}
+//! ]}
+//! ~~~
+//!
+//! Cards that cost potions or debt as well as coins list them separately,
+//! e.g. `"cost": 3, "potions": 1`.
+//!
+//! Once they're loaded, custom cards are looked up by name like any other
+//! card, e.g. `from_str::<Card>("Hamlet Lite")`.
+//!
+//! Files can also name kingdoms, made of built-in cards, custom cards from
+//! the same file, or both. They're found with `kingdom()`:
+//!
+//! ~~~ignore
+//! {"kingdoms": [
+//!     {"name": "First Game", "cards": ["Cellar", "Market", "Militia", "Mine", "Moat",
+//!                                      "Remodel", "Smithy", "Village", "Woodcutter", "Workshop"]}
+//! ]}
+//!
+//! let config = GameConfig::new().kingdom(registry::kingdom("First Game").unwrap());
+//! ~~~
+//!
+//! Custom cards are numbered in the order they're loaded, so event logs that
+//! mention them can only be replayed after loading the same files in the
+//! same order.
+
+use std::from_str::FromStr;
+use std::io::{File, IoError};
+use std::mem;
+use std::sync::{Mutex, Once, ONCE_INIT};
+use serialize::json;
+
+use card::{Card, CardType, Custom};
+use cost::Cost;
+
+/// Everything there is to know about a custom card.
+#[deriving(Clone, Show, PartialEq, Decodable)]
+pub struct CardDef {
+    pub name: String,
+
+    /// The coins the card costs. See `cost()` for the whole cost.
+    pub cost: uint,
+    pub potions: Option<uint>,
+    pub debt: Option<uint>,
+
+    pub types: Vec<CardType>,
+    pub set: Option<String>,
+    pub text: Option<String>,
+
+    /// How many cards to draw when it's played.
+    pub cards: Option<uint>,
+    pub actions: Option<uint>,
+    pub buys: Option<uint>,
+
+    /// +Coins when it's played. For Treasures, this is their value.
+    pub coins: Option<uint>,
+
+    /// Victory points, for Victory cards.
+    pub vp: Option<int>,
+}
+
+impl CardDef {
+    /// What the card costs, counting potions and debt.
+    pub fn cost(&self) -> Cost {
+        Cost::new(self.cost, self.potions.unwrap_or(0), self.debt.unwrap_or(0))
+    }
+}
+
+/// A named kingdom, e.g. one of the rulebook's recommended sets. Cards are
+/// given by name, and can be custom cards from the same file.
+#[deriving(Clone, Show, PartialEq, Decodable)]
+pub struct KingdomDef {
+    pub name: String,
+    pub cards: Vec<String>,
+}
+
+/// The layout of a card file. Either part can be left out.
+#[deriving(Decodable)]
+struct CardFile {
+    cards: Option<Vec<CardDef>>,
+    kingdoms: Option<Vec<KingdomDef>>,
+}
+
+/// Why a card file couldn't be loaded. Nothing from a file is loaded
+/// unless all of it is.
+#[deriving(Show)]
+pub enum RegistryError {
+    IoFailed(IoError),
+    BadFile(json::DecoderError),
+
+    /// A card or kingdom with this name already exists.
+    DuplicateName(String),
+
+    /// The card doesn't have any types.
+    NoTypes(String),
+
+    /// A kingdom names a card that doesn't exist.
+    UnknownCard(String),
+}
+
+/// Everything loaded so far. Card definitions are never removed, so each
+/// one is kept for the rest of the program as soon as it's loaded.
+struct Registry {
+    cards: Vec<&'static CardDef>,
+    kingdoms: Vec<(String, Vec<Card>)>,
+}
+
+impl Registry {
+    /// Find a custom card by its normalized name.
+    fn find(&self, name: &str) -> Option<Card> {
+        self.cards.iter().position(|x| normalize(x.name.as_slice()) == name).map(|i| Custom(i))
+    }
+}
+
+static INIT: Once = ONCE_INIT;
+static mut REGISTRY: *const Mutex<Registry> = 0 as *const Mutex<Registry>;
+
+/// The registry, created the first time it's needed. `REGISTRY` is only
+/// ever written by `INIT`, before anything reads it.
+fn registry() -> &'static Mutex<Registry> {
+    unsafe {
+        INIT.doit(|| REGISTRY = leak(box Mutex::new(Registry{cards: Vec::new(), kingdoms: Vec::new()})) as *const _);
+        &*REGISTRY
+    }
+}
+
+/// Keep `x` for the rest of the program.
+fn leak<T: 'static>(x: Box<T>) -> &'static T {
+    let ptr: *const T = &*x;
+    unsafe {
+        mem::forget(x);
+        &*ptr
+    }
+}
+
+/// The definition of a custom card.
+pub fn get(id: uint) -> &'static CardDef {
+    registry().lock().cards[id]
+}
+
+/// The cards in a kingdom loaded from a file, found by name like cards
+/// are.
+pub fn kingdom(name: &str) -> Option<Vec<Card>> {
+    let name = normalize(name);
+    registry().lock().kingdoms.iter().find(|&&(ref x, _)| *x == name).map(|&(_, ref cards)| cards.clone())
+}
+
+/// Load custom cards and kingdoms from a string of JSON, returning the new
+/// cards.
+pub fn load_json(s: &str) -> Result<Vec<Card>, RegistryError> {
+    let file: CardFile = match json::decode(s) {
+        Ok(file) => file,
+        Err(e) => return Err(BadFile(e)),
+    };
+    let defs = file.cards.unwrap_or(Vec::new());
+    // Everything is checked and added under one lock, so two files loading
+    // the same name at once can't both get it.
+    let mut registry = registry().lock();
+    let mut names: Vec<String> = Vec::new();
+    for def in defs.iter() {
+        if def.types.is_empty() {
+            return Err(NoTypes(def.name.clone()));
+        }
+        let name = normalize(def.name.as_slice());
+        if names.contains(&name) || find_builtin(name.as_slice()).is_some() || registry.find(name.as_slice()).is_some() {
+            return Err(DuplicateName(def.name.clone()));
+        }
+        names.push(name);
+    }
+
+    let first = registry.cards.len();
+    let mut kingdoms: Vec<(String, Vec<Card>)> = Vec::new();
+    for kingdom in file.kingdoms.unwrap_or(Vec::new()).into_iter() {
+        let name = normalize(kingdom.name.as_slice());
+        if kingdoms.iter().chain(registry.kingdoms.iter()).any(|&(ref x, _)| *x == name) {
+            return Err(DuplicateName(kingdom.name));
+        }
+        let mut cards = Vec::new();
+        for card_name in kingdom.cards.iter() {
+            let wanted = normalize(card_name.as_slice());
+            let card = find_builtin(wanted.as_slice())
+                .or_else(|| names.iter().position(|x| *x == wanted).map(|i| Custom(first + i)))
+                .or_else(|| registry.find(wanted.as_slice()));
+            match card {
+                Some(card) => cards.push(card),
+                None => return Err(UnknownCard(card_name.clone())),
+            }
+        }
+        kingdoms.push((name, cards));
+    }
+
+    registry.kingdoms.extend(kingdoms.into_iter());
+    Ok(defs.into_iter().map(|def| {
+        registry.cards.push(leak(box def));
+        Custom(registry.cards.len() - 1)
+    }).collect())
+}
+
+/// Load custom cards from a JSON file.
+pub fn load_file(path: &Path) -> Result<Vec<Card>, RegistryError> {
+    match File::open(path).read_to_string() {
+        Ok(s) => load_json(s.as_slice()),
+        Err(e) => Err(IoFailed(e)),
+    }
+}
+
+/// Find a card by name, whether it's built in or custom. Case, spaces and
+/// apostrophes don't matter, so "Throne Room" finds ThroneRoom.
+pub fn lookup(name: &str) -> Option<Card> {
+    let name = normalize(name);
+    find_builtin(name.as_slice()).or_else(|| registry().lock().find(name.as_slice()))
+}
+
+/// Find a built-in card by its normalized name.
+fn find_builtin(name: &str) -> Option<Card> {
+    Card::builtin().into_iter().find(|x| normalize(x.name()) == name)
+}
+
+fn normalize(name: &str) -> String {
+    name.chars().filter(|x| *x != ' ' && *x != '\'').map(|x| x.to_lowercase()).collect()
+}
+
+impl FromStr for Card {
+    fn from_str(s: &str) -> Option<Card> {
+        lookup(s)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/registry.rs"]
+mod tests;
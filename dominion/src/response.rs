@@ -1,53 +1,59 @@
 use super::card::Card;
-use super::PendingPlay;
-
-/// Represents a closure that indicates whether the pending play
-/// is complete or not.
-type IsCompleteFn = |&PendingPlay|:Send -> bool;
-
-type Chans = (Sender<(Card, PendingPlay)>, Receiver<Response>);
+use super::cost::Cost;
+use super::decision::{Choice, Decision};
+use super::phase::Phase;
 
 /// Game response as an enum.
+#[deriving(Clone, Show, PartialEq)]
 pub enum Response {
     NoProblem, // rename to `Ok` after enum sub-namespacing occurs
     DontUnderstand,
+
+    /// The card can't be bought this turn, e.g. because it was named for
+    /// Contraband, or it's Grand Market and there's a Copper in play.
+    Forbidden(Card),
+
+    IllegalChoice(Choice),
+
+    /// You can't buy anything while you owe this much debt. Pay it off
+    /// first with `PayDebt`.
+    InDebt(uint),
+    NoBuys,
     NotEnoughActions,
+
+    /// The card costs more coins or potions than you have.
+    NotEnoughMoney { need: Cost, have: Cost },
+
+    /// You tried to spend more Coffers than you have.
+    NotEnoughCoffers { need: uint, have: uint },
+
+    /// The game isn't waiting on a choice, so the one you sent was ignored.
+    NotDeciding,
+
+    NotInHand(Card),
     NotInKingdom(Card),
     PileEmpty(Card),
+    WrongPhase(Phase),
 
-    Incomplete {
-        card: Card,
-        pending: PendingPlay,
-        chans: Chans,
-        is_complete: IsCompleteFn,
-    },
+    /// The game needs you to make a choice before it can carry on. Answer
+    /// it with `Connection::choose()`.
+    Decide(Decision),
 }
 
 impl Response {
     pub fn is_err(&self) -> bool {
         match *self {
-            DontUnderstand | NotEnoughActions | NotInKingdom(_) | PileEmpty(_) => true,
-            NoProblem | Incomplete{..} => false,
+            DontUnderstand | Forbidden(_) | IllegalChoice(_) | InDebt(_) | NoBuys | NotEnoughActions | NotEnoughMoney{..} | NotEnoughCoffers{..} | NotDeciding | NotInHand(_) | NotInKingdom(_) | PileEmpty(_) | WrongPhase(_) => true,
+            NoProblem | Decide(_) => false,
         }
     }
 
-    pub fn incomplete(card: Card, pending: PendingPlay, chans: Chans, is_complete: IsCompleteFn) -> Response {
-        Incomplete{card: card, pending: pending, chans: chans, is_complete: is_complete}
-    }
-
-    pub fn discarding(self, cards: Vec<Card>) -> Response {
-        match self {
-            Incomplete{card, mut pending, chans, is_complete} => {
-                let (play_send, resp_recv) = chans;
-                pending.discarding = cards;
-                if is_complete(&pending) {
-                    play_send.send((card, pending));
-                    resp_recv.recv()
-                } else {
-                    Incomplete{card: card, pending: pending, chans: (play_send, resp_recv), is_complete: is_complete}
-                }
-            },
-            _ => self,
+    /// Returns true if the game is waiting on a `Choice`. After an illegal
+    /// choice, the game asks again.
+    pub fn needs_choice(&self) -> bool {
+        match *self {
+            Decide(_) | IllegalChoice(_) => true,
+            _ => false,
         }
     }
 }
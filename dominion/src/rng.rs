@@ -0,0 +1,46 @@
+//! Random numbers for games.
+
+use std::cell::RefCell;
+use std::default::Default;
+use std::rand::{task_rng, Isaac64Rng, Rand, Rng, SeedableRng};
+use std::rc::Rc;
+
+/// A seedable random number generator. Every random choice made during a
+/// game comes from the same generator, so a game can be replayed exactly
+/// from its seed. Clones share the same underlying generator.
+#[deriving(Clone)]
+pub struct GameRng {
+    rng: Rc<RefCell<Isaac64Rng>>,
+    seed: u64,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> GameRng {
+        let rng: Isaac64Rng = SeedableRng::from_seed([seed].as_slice());
+        GameRng{rng: Rc::new(RefCell::new(rng)), seed: seed}
+    }
+
+    /// Create a generator with a random seed.
+    pub fn random() -> GameRng {
+        GameRng::new(task_rng().gen())
+    }
+
+    /// The seed this generator started from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn gen<T: Rand>(&self) -> T {
+        self.rng.borrow_mut().gen()
+    }
+
+    pub fn shuffle<T>(&self, values: &mut [T]) {
+        self.rng.borrow_mut().shuffle(values)
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> GameRng {
+        GameRng::random()
+    }
+}
@@ -0,0 +1,38 @@
+//! Extra rules for scoring a game, on top of what each player's cards and
+//! tokens are worth, e.g. Empires' Landmarks.
+//!
+//! A game's Landmarks add their rules when it's set up, and anything else
+//! can be plugged in with `Game::add_scoring_rule()`:
+//!
+//! ~~~ignore
+//! /// Curses cost everyone another point each.
+//! struct DoubleCurses;
+//!
+//! impl ScoringRule for DoubleCurses {
+//!     fn score(&self, sheet: &ScoreSheet) -> int {
+//!         -(sheet.cards.iter().filter(|x| x.is_curse()).count() as int)
+//!     }
+//! }
+//!
+//! game.add_scoring_rule(box DoubleCurses);
+//! ~~~
+
+use card::Card;
+
+/// Everything a rule can look at when it scores one player.
+pub struct ScoreSheet<'a> {
+    /// Every card the player owns.
+    pub cards: &'a [Card],
+
+    /// Every card each of the other players owns.
+    pub others: &'a [Vec<Card>],
+
+    /// The cards whose supply piles have run out.
+    pub empty_piles: &'a [Card],
+}
+
+/// A rule that adds to, or takes away from, each player's score.
+pub trait ScoringRule {
+    /// The points this rule is worth to the player on `sheet`.
+    fn score(&self, sheet: &ScoreSheet) -> int;
+}
@@ -0,0 +1,610 @@
+use std::collections::RingBuf;
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::attack;
+use super::super::response::*;
+use super::super::token;
+use super::super::token::Token;
+use super::dark_ages::costs_3_to_6;
+
+/// The kingdom cards in this set. The Duration cards (Amulet, Caravan
+/// Guard, Dungeon, Gear, Bridge Troll, Haunted Woods, Swamp Hag and
+/// Hireling), Messenger and Distant Lands aren't available yet.
+pub fn kingdom() -> Vec<Card> {
+    vec![CoinOfTheRealm, Page, Peasant, Ratcatcher, Raze, Guide, Duplicate, Magpie, Miser, Port, Ranger,
+         Transmogrify, Artificer, Giant, LostCity, Relic, RoyalCarriage, Storyteller, TreasureTrove, WineMerchant]
+}
+
+/// The Events in this set. Quest and Mission aren't available yet.
+pub fn events() -> Vec<Card> {
+    vec![Alms, Borrow, Save, ScoutingParty, TravellingFair, Bonfire, Expedition, Ferry, Plan, Pilgrimage, Ball,
+         Raid, Seaway, Trade, LostArts, Training, Inheritance, Pathfinding]
+}
+
+/// The Travellers that Page turns into, in order. Each has its own pile
+/// outside the supply.
+pub fn page_line() -> Vec<Card> {
+    vec![TreasureHunter, Warrior, Hero, Champion]
+}
+
+/// The Travellers that Peasant turns into, in order.
+pub fn peasant_line() -> Vec<Card> {
+    vec![Soldier, Fugitive, Disciple, Teacher]
+}
+
+/// What a Traveller can be exchanged for when it's discarded from play.
+fn next_traveller(card: Card) -> Option<Card> {
+    match card {
+        Page => Some(TreasureHunter),
+        TreasureHunter => Some(Warrior),
+        Warrior => Some(Hero),
+        Hero => Some(Champion),
+        Peasant => Some(Soldier),
+        Soldier => Some(Fugitive),
+        Fugitive => Some(Disciple),
+        Disciple => Some(Teacher),
+        _ => None,
+    }
+}
+
+/// Turn the player's Journey token over. Returns true if it's face up
+/// afterwards.
+fn turn_journey(player: &mut PlayerHandle) -> bool {
+    player.journey_face_up = !player.journey_face_up;
+    player.journey_face_up
+}
+
+/// Move a card the player just played onto their Tavern mat, to be called
+/// later.
+pub fn to_tavern(card: Card, player: &mut PlayerHandle) -> Response {
+    player.move_card(card, InPlay, TavernMat);
+    NoProblem
+}
+
+/// Call a card from the Tavern mat into play.
+fn from_tavern(card: Card, player: &mut PlayerHandle) {
+    player.move_card(card, TavernMat, InPlay);
+}
+
+/// Ask the player for a card from their hand to trash, if they have any.
+pub fn trash_from_hand(card: Card, player: &mut PlayerHandle, state: &mut GameState) -> Option<Card> {
+    let hand = player.get_hand();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    let trashed = player.decide(state, Decision::cards(card, Trashing, hand, min, 1)).cards().pop();
+    if let Some(trashed) = trashed {
+        player.trash(state, trashed);
+    }
+    trashed
+}
+
+/// Bonuses for playing a card: the player's tokens on its pile, and +1
+/// Action for each Champion they have in play.
+pub fn before_play(player: &mut PlayerHandle, state: &mut GameState, card: Card) {
+    for t in state.tokens.on(player.id, card).into_iter() {
+        match t {
+            token::PlusCard => { player.draw(); },
+            token::PlusAction => player.actions += 1,
+            token::PlusBuy => player.buys += 1,
+            token::PlusCoin => player.buying_power += 1,
+            _ => (),
+        }
+    }
+    if card.is_action() {
+        let mut champions = player.in_play.iter().filter(|x| **x == Champion).count();
+        // Champion doesn't count itself.
+        if card == Champion && champions > 0 {
+            champions -= 1;
+        }
+        player.actions += champions;
+    }
+}
+
+/// Buying a card from the pile with the player's Trashing token lets them
+/// trash a card from their hand.
+pub fn trashing_token(player: &mut PlayerHandle, state: &mut GameState) {
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Plan, Trashing, hand, 0, 1)).cards().pop() {
+        player.trash(state, card);
+    }
+}
+
+/// An Estate played with the Estate token on a pile does what that pile's
+/// card does.
+pub fn estate(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    match state.tokens.pile_of(player.id, token::EstateToken) {
+        Some(card) => card.resolve(player, state, opponents),
+        None => NoProblem,
+    }
+}
+
+/// A Traveller discarded from play can be exchanged for the next one in
+/// its line.
+pub fn traveller_discarded(card: Card, player: &mut PlayerHandle, state: &mut GameState) {
+    let next = match next_traveller(card) {
+        Some(next) if state.can_take(next) => next,
+        _ => return,
+    };
+    if player.decide(state, Decision::yes_no(card, Gaining, Some(next), true)).is_yes() {
+        player.exchange(state, card, next);
+    }
+}
+
+pub fn coin_of_the_realm_called(player: &mut PlayerHandle) {
+    from_tavern(CoinOfTheRealm, player);
+    player.actions += 2;
+}
+
+pub fn page(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    NoProblem
+}
+
+pub fn peasant(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+/// Ratcatcher and Guide both give +1 Card and +1 Action before they go on
+/// the Tavern mat.
+pub fn ratcatcher(card: Card, player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    to_tavern(card, player)
+}
+
+pub fn ratcatcher_called(player: &mut PlayerHandle, state: &mut GameState) {
+    from_tavern(Ratcatcher, player);
+    trash_from_hand(Ratcatcher, player, state);
+}
+
+pub fn guide_called(player: &mut PlayerHandle) {
+    from_tavern(Guide, player);
+    player.discard_hand();
+    player.draw_n(5);
+}
+
+pub fn raze(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    let hand = player.get_hand();
+    // Raze trashes itself unless a card from hand is chosen.
+    let trashed = match player.decide(state, Decision::cards(Raze, Trashing, hand, 0, 1)).cards().pop() {
+        Some(card) => {
+            player.trash(state, card);
+            card
+        },
+        None => {
+            player.trash_from_play(state, Raze);
+            Raze
+        },
+    };
+    let revealed: Vec<Card> = range(0, state.cost(trashed).coins).filter_map(|_| player.reveal()).collect();
+    if revealed.is_empty() {
+        return NoProblem;
+    }
+    let n = revealed.len() - 1;
+    for card in player.decide(state, Decision::cards(Raze, Discarding, revealed, n, n)).cards().into_iter() {
+        player.move_card(card, SetAside, DiscardPile);
+    }
+    for card in player.set_aside.clone().into_iter() {
+        player.move_card(card, SetAside, Hand);
+    }
+    NoProblem
+}
+
+pub fn duplicate_called(player: &mut PlayerHandle, state: &mut GameState, card: Card) {
+    from_tavern(Duplicate, player);
+    player.gain(state, card);
+}
+
+pub fn magpie(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    let card = match player.reveal() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.move_card(card, SetAside, if card.is_money() { Hand } else { Deck });
+    if card.is_action() || card.is_victory() {
+        player.gain(state, Magpie);
+    }
+    NoProblem
+}
+
+pub fn miser(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let options = vec!["Put a Copper onto your Tavern mat", "+$1 per Copper on your Tavern mat"];
+    match player.decide(state, Decision::option(Miser, options)).option() {
+        Some(1) => player.buying_power += player.tavern.iter().filter(|x| **x == Copper).count(),
+        _ => { player.move_card(Copper, Hand, TavernMat); },
+    }
+    NoProblem
+}
+
+pub fn port(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 2;
+    NoProblem
+}
+
+pub fn ranger(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    if turn_journey(player) {
+        player.draw_n(5);
+    }
+    NoProblem
+}
+
+pub fn transmogrify(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    to_tavern(Transmogrify, player)
+}
+
+pub fn transmogrify_called(player: &mut PlayerHandle, state: &mut GameState) {
+    from_tavern(Transmogrify, player);
+    let trashed = match trash_from_hand(Transmogrify, player, state) {
+        Some(card) => card,
+        None => return,
+    };
+    if let Some(card) = player.decide(state, Decision::pile(Transmogrify, Gaining, state.cost(trashed).plus(1))).pile() {
+        player.gain_to_hand(state, card);
+    }
+}
+
+pub fn artificer(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buying_power += 1;
+    let hand = player.get_hand();
+    let n = hand.len();
+    let discarded = player.decide(state, Decision::cards(Artificer, Discarding, hand, 0, n)).cards();
+    for card in discarded.iter() {
+        player.discard(*card);
+    }
+    let cost = Cost::coins(discarded.len());
+    match player.decide(state, Decision::pile_between(Artificer, Gaining, cost, cost)).pile() {
+        Some(card) => player.gain_to_deck(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn giant<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    if !turn_journey(player) {
+        player.buying_power += 1;
+        return NoProblem;
+    }
+    player.buying_power += 5;
+    attack(Giant, state, opponents, |opponent, state| {
+        match opponent.reveal() {
+            Some(card) if costs_3_to_6(state, card) => { opponent.trash_from(state, card, SetAside); },
+            Some(card) => {
+                opponent.move_card(card, SetAside, DiscardPile);
+                opponent.gain(state, Curse);
+            },
+            None => { opponent.gain(state, Curse); },
+        }
+    });
+    NoProblem
+}
+
+pub fn lost_city(player: &mut PlayerHandle) -> Response {
+    player.draw_n(2);
+    player.actions += 2;
+    NoProblem
+}
+
+/// Relic puts everyone else's −1 Card token on their deck.
+pub fn relic<'a, T: Iterator<&'a mut PlayerHandle>>(state: &mut GameState, opponents: T) -> Response {
+    attack(Relic, state, opponents, |opponent, _| opponent.minus_card = true);
+    NoProblem
+}
+
+pub fn royal_carriage(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    to_tavern(RoyalCarriage, player)
+}
+
+/// Royal Carriage is called once an Action card has resolved, to play it
+/// again if it's still in play. Playing it needs the opponents, so this
+/// happens here rather than through `Card::call()`. Another Royal Carriage
+/// can be called once the replay has resolved too.
+pub fn call_royal_carriages(card: Card, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) {
+    if !player.tavern.contains(&RoyalCarriage) || !player.in_play.contains(&card) {
+        return;
+    }
+    if !player.decide(state, Decision::yes_no(RoyalCarriage, Calling, Some(card), true)).is_yes() {
+        return;
+    }
+    from_tavern(RoyalCarriage, player);
+    let durations = player.durations.len();
+    card.play_in_place(player, state, opponents);
+    player.stay_with_durations(RoyalCarriage, durations);
+}
+
+pub fn storyteller(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.actions += 1;
+    player.buying_power += 1;
+    let money: Vec<Card> = player.hand.iter().filter(|x| x.is_money()).map(|x| *x).collect();
+    for card in player.decide(state, Decision::cards(Storyteller, Playing, money, 0, 3)).cards().into_iter() {
+        player.put_in_play(card);
+        let resp = card.play_effects(player, state, opponents);
+        if resp.is_err() {
+            return resp;
+        }
+    }
+    // Every coin the player has goes on drawing cards.
+    let paid = player.buying_power;
+    player.buying_power = 0;
+    player.draw_n(paid);
+    NoProblem
+}
+
+pub fn treasure_trove(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.gain(state, Gold);
+    player.gain(state, Copper)
+}
+
+pub fn wine_merchant(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    player.buying_power += 4;
+    to_tavern(WineMerchant, player)
+}
+
+/// Wine Merchant goes straight from the Tavern mat to the discard pile.
+pub fn wine_merchant_called(player: &mut PlayerHandle) {
+    player.move_card(WineMerchant, TavernMat, DiscardPile);
+}
+
+pub fn treasure_hunter(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.actions += 1;
+    player.buying_power += 1;
+    // The player to the right went last.
+    let silvers = opponents.back().map_or(0, |x| x.gained_last_turn);
+    for _ in range(0, silvers) {
+        player.gain(state, Silver);
+    }
+    NoProblem
+}
+
+pub fn warrior<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(2);
+    let travellers = player.in_play.iter().filter(|x| x.has_type(Traveller)).count();
+    attack(Warrior, state, opponents, |opponent, state| {
+        for _ in range(0, travellers) {
+            match opponent.reveal() {
+                Some(card) if state.cost(card) == Cost::coins(3) || state.cost(card) == Cost::coins(4) => {
+                    opponent.trash_from(state, card, SetAside);
+                },
+                Some(card) => { opponent.move_card(card, SetAside, DiscardPile); },
+                None => break,
+            }
+        }
+    });
+    NoProblem
+}
+
+pub fn hero(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buying_power += 2;
+    match player.decide(state, Decision::any_pile(Hero, Gaining).only(Money)).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+/// Champion stays in play for the rest of the game.
+pub fn champion(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    player.durations.push(Champion);
+    NoProblem
+}
+
+pub fn champion_next_turn(player: &mut PlayerHandle) -> Response {
+    player.durations.push(Champion);
+    NoProblem
+}
+
+pub fn soldier<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    let attacks = player.in_play.iter().filter(|x| x.has_type(Attack)).count();
+    // Soldier doesn't count itself.
+    player.buying_power += 2 + if attacks > 0 { attacks - 1 } else { 0 };
+    attack(Soldier, state, opponents, |opponent, state| {
+        if opponent.hand.len() < 4 {
+            return;
+        }
+        let hand = opponent.get_hand();
+        if let Some(card) = opponent.decide(state, Decision::cards(Soldier, Discarding, hand, 1, 1)).cards().pop() {
+            opponent.discard(card);
+        }
+    });
+    NoProblem
+}
+
+pub fn fugitive(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw_n(2);
+    player.actions += 1;
+    let hand = player.get_hand();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    if let Some(card) = player.decide(state, Decision::cards(Fugitive, Discarding, hand, min, 1)).cards().pop() {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+/// Disciple is like Throne Room, but it also gains a copy of the card.
+pub fn disciple(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    let actions: Vec<Card> = player.hand.iter().filter(|x| x.is_action()).map(|x| *x).collect();
+    let to_repeat = match player.decide(state, Decision::cards(Disciple, Playing, actions, 0, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.put_in_play(to_repeat);
+    let durations = player.durations.len();
+    let resp = to_repeat.play_in_place(player, state, opponents);
+    if resp.is_err() {
+        return resp;
+    }
+    let resp = to_repeat.play_in_place(player, state, opponents);
+    player.stay_with_durations(Disciple, durations);
+    if resp.is_err() {
+        return resp;
+    }
+    if state.can_take(to_repeat) {
+        player.gain(state, to_repeat);
+    }
+    NoProblem
+}
+
+/// Teacher moves one of the player's +1 Card, +1 Action, +1 Buy or +$1
+/// tokens to an Action supply pile that none of their tokens are on.
+pub fn teacher_called(player: &mut PlayerHandle, state: &mut GameState) {
+    from_tavern(Teacher, player);
+    let tokens = [token::PlusCard, token::PlusAction, token::PlusBuy, token::PlusCoin];
+    let options = vec!["+1 Card", "+1 Action", "+1 Buy", "+$1"];
+    let chosen = tokens[player.decide(state, Decision::option(Teacher, options)).option().unwrap_or(0)];
+    if let Some(card) = player.decide(state, Decision::any_pile(Teacher, PlacingToken).only(Action)).pile() {
+        if state.tokens.on(player.id, card).is_empty() {
+            state.tokens.place(player.id, chosen, card);
+        }
+    }
+}
+
+/// Move one of the player's tokens to an Action supply pile, for Events
+/// like Ferry and Lost Arts.
+pub fn place_token(event: Card, player: &mut PlayerHandle, state: &mut GameState, t: Token) {
+    if let Some(card) = player.decide(state, Decision::any_pile(event, PlacingToken).only(Action)).pile() {
+        state.tokens.place(player.id, t, card);
+    }
+}
+
+pub fn alms(player: &mut PlayerHandle, state: &mut GameState) {
+    if player.in_play.iter().any(|x| x.is_money()) {
+        return;
+    }
+    if let Some(card) = player.decide(state, Decision::pile(Alms, Gaining, Cost::coins(4))).pile() {
+        player.gain(state, card);
+    }
+}
+
+/// Borrow only gives +$1 when the player's −1 Card token isn't already on
+/// their deck.
+pub fn borrow(player: &mut PlayerHandle) {
+    player.buys += 1;
+    if !player.minus_card {
+        player.minus_card = true;
+        player.buying_power += 1;
+    }
+}
+
+/// Save sets a card from the player's hand aside. It comes back once
+/// they've drawn their next hand in Cleanup.
+pub fn save(player: &mut PlayerHandle, state: &mut GameState) {
+    player.buys += 1;
+    let hand = player.get_hand();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    if let Some(card) = player.decide(state, Decision::cards(Save, SettingAside, hand, min, 1)).cards().pop() {
+        player.move_card(card, Hand, EndOfTurn);
+    }
+}
+
+pub fn scouting_party(player: &mut PlayerHandle, state: &mut GameState) {
+    player.buys += 1;
+    let revealed: Vec<Card> = range(0u, 5).filter_map(|_| player.reveal()).collect();
+    let n = if revealed.len() < 3 { revealed.len() } else { 3 };
+    for card in player.decide(state, Decision::cards(ScoutingParty, Discarding, revealed, n, n)).cards().into_iter() {
+        player.move_card(card, SetAside, DiscardPile);
+    }
+    let rest = player.set_aside.clone();
+    let order = player.decide(state, Decision::order(ScoutingParty, TopDecking, rest)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+}
+
+pub fn travelling_fair(player: &mut PlayerHandle) {
+    player.buys += 2;
+    player.travelling_fair = true;
+}
+
+pub fn bonfire(player: &mut PlayerHandle, state: &mut GameState) {
+    let in_play = player.get_in_play();
+    for card in player.decide(state, Decision::cards(Bonfire, Trashing, in_play, 0, 2)).cards().into_iter() {
+        player.trash_from_play(state, card);
+    }
+}
+
+pub fn expedition(player: &mut PlayerHandle) {
+    player.extra_cards += 2;
+}
+
+pub fn pilgrimage(player: &mut PlayerHandle, state: &mut GameState) {
+    if !turn_journey(player) {
+        return;
+    }
+    let mut in_play = player.get_in_play();
+    in_play.sort_by(|a, b| a.name().cmp(b.name()));
+    in_play.dedup();
+    for card in player.decide(state, Decision::cards(Pilgrimage, Gaining, in_play, 0, 3)).cards().into_iter() {
+        player.gain(state, card);
+    }
+}
+
+pub fn ball(player: &mut PlayerHandle, state: &mut GameState) {
+    player.minus_coin = true;
+    for _ in range(0u, 2) {
+        if let Some(card) = player.decide(state, Decision::pile(Ball, Gaining, Cost::coins(4))).pile() {
+            player.gain(state, card);
+        }
+    }
+}
+
+/// Raid isn't an Attack, so nobody can react to it.
+pub fn raid<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, mut opponents: T) {
+    let silvers = player.in_play.iter().filter(|x| **x == Silver).count();
+    for _ in range(0, silvers) {
+        player.gain(state, Silver);
+    }
+    for opponent in opponents {
+        opponent.minus_card = true;
+    }
+}
+
+pub fn seaway(player: &mut PlayerHandle, state: &mut GameState) {
+    if let Some(card) = player.decide(state, Decision::pile(Seaway, Gaining, Cost::coins(4)).only(Action)).pile() {
+        player.gain(state, card);
+        state.tokens.place(player.id, token::PlusBuy, card);
+    }
+}
+
+pub fn trade(player: &mut PlayerHandle, state: &mut GameState) {
+    let hand = player.get_hand();
+    let trashed = player.decide(state, Decision::cards(Trade, Trashing, hand, 0, 2)).cards();
+    for card in trashed.iter() {
+        player.trash(state, *card);
+        player.gain(state, Silver);
+    }
+}
+
+/// Inheritance sets aside an Action card from the supply for the player's
+/// Estates to copy. It can only be bought once a game.
+pub fn inheritance(player: &mut PlayerHandle, state: &mut GameState) {
+    if state.tokens.pile_of(player.id, token::EstateToken).is_some() {
+        return;
+    }
+    let card = match player.decide(state, Decision::pile(Inheritance, SettingAside, Cost::coins(4)).only(Action)).pile() {
+        Some(card) if !card.is_victory() => card,
+        _ => return,
+    };
+    if state.take(card).is_err() {
+        return;
+    }
+    player.record(TookFromSupply{player: player.id, card: card});
+    state.tokens.place(player.id, token::EstateToken, card);
+}
+
+#[cfg(test)]
+#[path = "tests/adventures.rs"]
+mod tests;
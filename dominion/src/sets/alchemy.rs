@@ -0,0 +1,191 @@
+use std::collections::RingBuf;
+
+use super::super::{GameState, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::reaction::attack;
+use super::super::response::*;
+
+/// The kingdom cards in this set. Potion itself is added to the supply
+/// whenever the kingdom has a card that costs one.
+pub fn kingdom() -> Vec<Card> {
+    vec![Transmute, Vineyard, Herbalist, Apothecary, ScryingPool, University, Alchemist, Familiar,
+         PhilosophersStone, Golem, Apprentice, Possession]
+}
+
+pub fn transmute(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.hand.clone();
+    let card = match player.decide(state, Decision::cards(Transmute, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, card);
+    // A card with more than one type gains something for each of them.
+    if card.is_action() {
+        player.gain(state, Duchy);
+    }
+    if card.is_money() {
+        player.gain(state, Transmute);
+    }
+    if card.is_victory() {
+        player.gain(state, Gold);
+    }
+    NoProblem
+}
+
+pub fn herbalist(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+/// When Herbalist is discarded from play, one of the player's Treasures in
+/// play may go on top of their deck.
+pub fn herbalist_discarded(player: &mut PlayerHandle, state: &mut GameState) {
+    let money: Vec<Card> = player.in_play.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Herbalist, TopDecking, money, 0, 1)).cards().pop() {
+        player.move_card(card, InPlay, Deck);
+    }
+}
+
+pub fn apothecary(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    let revealed: Vec<Card> = range(0u, 4).filter_map(|_| player.reveal()).collect();
+    let (taken, rest) = revealed.partition(|x| *x == Copper || *x == Potion);
+    for card in taken.into_iter() {
+        player.move_card(card, SetAside, Hand);
+    }
+    let order = player.decide(state, Decision::order(Apothecary, TopDecking, rest)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+    NoProblem
+}
+
+pub fn scrying_pool<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.actions += 1;
+    // The player decides what happens to everyone's top card, their own
+    // included.
+    if let Some(card) = player.reveal() {
+        let discarding = player.decide(state, Decision::yes_no(ScryingPool, Discarding, Some(card), !card.is_action())).is_yes();
+        player.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+    }
+    attack(ScryingPool, state, opponents, |opponent, state| {
+        if let Some(card) = opponent.reveal() {
+            let is_worthless = card.is_victory() || card.is_curse();
+            let discarding = player.decide(state, Decision::yes_no(ScryingPool, Discarding, Some(card), !is_worthless)).is_yes();
+            opponent.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+        }
+    });
+    // Then every card revealed up to and including the first that isn't an
+    // Action goes into the player's hand.
+    loop {
+        match player.reveal() {
+            Some(card) => {
+                player.move_card(card, SetAside, Hand);
+                if !card.is_action() {
+                    break;
+                }
+            },
+            None => break,
+        }
+    }
+    NoProblem
+}
+
+pub fn university(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 2;
+    // Gaining is optional.
+    if player.decide(state, Decision::yes_no(University, Gaining, None, true)).is_yes() {
+        if let Some(card) = player.decide(state, Decision::pile(University, Gaining, Cost::coins(5)).only(Action)).pile() {
+            return player.gain(state, card);
+        }
+    }
+    NoProblem
+}
+
+pub fn alchemist(player: &mut PlayerHandle) -> Response {
+    player.draw_n(2);
+    player.actions += 1;
+    NoProblem
+}
+
+/// When Alchemist is discarded from play while there's a Potion in play, it
+/// may go on top of the player's deck instead.
+pub fn alchemist_discarded(player: &mut PlayerHandle, state: &mut GameState) {
+    if !player.in_play.contains(&Potion) || !player.in_play.contains(&Alchemist) {
+        return;
+    }
+    if player.decide(state, Decision::yes_no(Alchemist, TopDecking, Some(Alchemist), true)).is_yes() {
+        player.move_card(Alchemist, InPlay, Deck);
+    }
+}
+
+pub fn familiar<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw();
+    player.actions += 1;
+    attack(Familiar, state, opponents, |opponent, state| {
+        opponent.gain(state, Curse);
+    });
+    NoProblem
+}
+
+/// Philosopher's Stone is worth $1 for every 5 cards in the player's deck
+/// and discard pile, counted when it's played.
+pub fn philosophers_stone(player: &mut PlayerHandle) -> Response {
+    player.buying_power += (player.deck.len() + player.discard.len()) / 5;
+    NoProblem
+}
+
+pub fn golem(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    let mut found = Vec::new();
+    while found.len() < 2 {
+        match player.reveal() {
+            Some(card) if card.is_action() && card != Golem => found.push(card),
+            Some(_) => (),
+            None => break,
+        }
+    }
+    // The player picks the order to play them in, and everything else
+    // revealed is discarded.
+    let order = player.decide(state, Decision::order(Golem, Playing, found)).cards();
+    for card in order.iter() {
+        player.move_card(*card, SetAside, InPlay);
+    }
+    player.discard_all(SetAside);
+    for card in order.into_iter() {
+        let resp = card.play_effects(player, state, opponents);
+        if resp.is_err() {
+            return resp;
+        }
+    }
+    NoProblem
+}
+
+/// The next player takes a turn after this one, controlled by this player.
+/// See `Game::play_possessed_turn()`.
+/// Apprentice draws a card for each $1 the trashed card costs, and two
+/// more if it costs a Potion.
+pub fn apprentice(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    let hand = player.hand.clone();
+    let card = match player.decide(state, Decision::cards(Apprentice, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, card);
+    let cost = state.cost(card);
+    player.draw_n(cost.coins + if cost.potions > 0 { 2 } else { 0 });
+    NoProblem
+}
+
+pub fn possession(player: &mut PlayerHandle) -> Response {
+    player.possessions += 1;
+    NoProblem
+}
+
+#[cfg(test)]
+#[path = "tests/alchemy.rs"]
+mod tests;
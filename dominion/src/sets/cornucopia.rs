@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::{attack, NotBlocked, Outcome};
+use super::super::response::*;
+use super::dominion::discard_down_to;
+
+/// The kingdom cards in this set.
+pub fn kingdom() -> Vec<Card> {
+    vec![Hamlet, FortuneTeller, Menagerie, FarmingVillage, HorseTraders, Remake, Tournament,
+         YoungWitch, Harvest, HornOfPlenty, HuntingParty, Jester, Fairgrounds]
+}
+
+/// The Prizes, which Tournament gains. There's one of each, outside the
+/// supply.
+pub fn prizes() -> Vec<Card> {
+    vec![BagOfGold, Diadem, Followers, Princess, TrustySteed]
+}
+
+/// The number of different cards in `cards`, for Fairgrounds, Horn of
+/// Plenty, Menagerie and Harvest.
+pub fn differently_named(cards: &[Card]) -> uint {
+    cards.iter().collect::<HashSet<&Card>>().len()
+}
+
+pub fn hamlet(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    // Each discard is optional.
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Hamlet, Discarding, hand, 0, 1)).cards().pop() {
+        player.discard(card);
+        player.actions += 1;
+    }
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Hamlet, Discarding, hand, 0, 1)).cards().pop() {
+        player.discard(card);
+        player.buys += 1;
+    }
+    NoProblem
+}
+
+pub fn fortune_teller<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 2;
+    attack(FortuneTeller, state, opponents, |opponent, _| {
+        loop {
+            match opponent.reveal() {
+                Some(card) if card.is_victory() || card.is_curse() => {
+                    opponent.move_card(card, SetAside, Deck);
+                    break;
+                },
+                Some(_) => (),
+                None => break,
+            }
+        }
+        opponent.discard_all(SetAside);
+    });
+    NoProblem
+}
+
+pub fn menagerie(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    for card in player.hand.clone().into_iter() {
+        player.record(Revealed{player: player.id, card: card});
+    }
+    let no_duplicates = differently_named(player.hand.as_slice()) == player.hand.len();
+    player.draw_n(if no_duplicates { 3 } else { 1 });
+    NoProblem
+}
+
+pub fn farming_village(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    loop {
+        match player.reveal() {
+            Some(card) if card.is_action() || card.is_money() => {
+                player.move_card(card, SetAside, Hand);
+                break;
+            },
+            Some(_) => (),
+            None => break,
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn horse_traders(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buys += 1;
+    player.buying_power += 3;
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(HorseTraders, Discarding, hand, 2, 2)).cards().into_iter() {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+/// Horse Traders is set aside when it's revealed, and comes back at the
+/// start of the player's next turn.
+pub fn horse_traders_react(player: &mut PlayerHandle) -> Outcome {
+    player.move_card(HorseTraders, Hand, NextTurn);
+    player.durations.push(HorseTraders);
+    NotBlocked
+}
+
+pub fn horse_traders_next_turn(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.move_card(HorseTraders, NextTurn, Hand);
+    NoProblem
+}
+
+pub fn remake(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    for _ in range(0u, 2) {
+        let hand = player.get_hand();
+        let to_trash = match player.decide(state, Decision::cards(Remake, Trashing, hand, 1, 1)).cards().pop() {
+            Some(card) => card,
+            None => break,
+        };
+        player.trash(state, to_trash);
+        let cost = state.cost(to_trash).plus(1);
+        if let Some(card) = player.decide(state, Decision::pile_between(Remake, Gaining, cost, cost)).pile() {
+            player.gain(state, card);
+        }
+    }
+    NoProblem
+}
+
+pub fn tournament<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, mut opponents: T) -> Response {
+    player.actions += 1;
+    let revealed = player.has_in_hand(Province) && player.decide(state, Decision::yes_no(Tournament, Revealing, Some(Province), true)).is_yes();
+    // It isn't an attack, so everyone else reveals without reacting.
+    let mut matched = false;
+    for opponent in opponents {
+        if opponent.has_in_hand(Province) && opponent.decide(state, Decision::yes_no(Tournament, Revealing, Some(Province), true)).is_yes() {
+            opponent.record(Revealed{player: opponent.id, card: Province});
+            matched = true;
+        }
+    }
+    if revealed {
+        player.record(Revealed{player: player.id, card: Province});
+        player.discard(Province);
+        let mut options: Vec<Card> = prizes().into_iter().filter(|x| state.non_supply_count(*x) > 0).collect();
+        if state.count(Duchy).map_or(false, |x| x > 0) {
+            options.push(Duchy);
+        }
+        if let Some(card) = player.decide(state, Decision::cards(Tournament, Gaining, options, 1, 1)).cards().pop() {
+            player.gain_to_deck(state, card);
+        }
+    }
+    if !matched {
+        player.draw();
+        player.buying_power += 1;
+    }
+    NoProblem
+}
+
+pub fn young_witch<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(2);
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(YoungWitch, Discarding, hand, 2, 2)).cards().into_iter() {
+        player.discard(card);
+    }
+    attack(YoungWitch, state, opponents, |opponent, state| {
+        // Revealing the Bane card keeps the Curse away.
+        if let Some(bane) = state.bane {
+            if opponent.has_in_hand(bane) && opponent.decide(state, Decision::yes_no(YoungWitch, Revealing, Some(bane), true)).is_yes() {
+                opponent.record(Revealed{player: opponent.id, card: bane});
+                return;
+            }
+        }
+        opponent.gain(state, Curse);
+    });
+    NoProblem
+}
+
+pub fn harvest(player: &mut PlayerHandle) -> Response {
+    let revealed: Vec<Card> = range(0u, 4).filter_map(|_| player.reveal()).collect();
+    player.discard_all(SetAside);
+    player.buying_power += differently_named(revealed.as_slice());
+    NoProblem
+}
+
+/// Horn of Plenty gains a card costing up to $1 for each differently named
+/// card in play, itself included. If that's a Victory card, Horn of Plenty
+/// is trashed.
+pub fn horn_of_plenty(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let max_cost = Cost::coins(differently_named(player.in_play.as_slice()));
+    if let Some(card) = player.decide(state, Decision::pile(HornOfPlenty, Gaining, max_cost)).pile() {
+        player.gain(state, card);
+        if card.is_victory() {
+            player.trash_from_play(state, HornOfPlenty);
+        }
+    }
+    NoProblem
+}
+
+pub fn hunting_party(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    for card in player.hand.clone().into_iter() {
+        player.record(Revealed{player: player.id, card: card});
+    }
+    loop {
+        match player.reveal() {
+            Some(card) if !player.has_in_hand(card) => {
+                player.move_card(card, SetAside, Hand);
+                break;
+            },
+            Some(_) => (),
+            None => break,
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn jester<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 2;
+    attack(Jester, state, opponents, |opponent, state| {
+        let card = match opponent.reveal() {
+            Some(card) => card,
+            None => return,
+        };
+        opponent.move_card(card, SetAside, DiscardPile);
+        if card.is_victory() {
+            opponent.gain(state, Curse);
+        } else if player.decide(state, Decision::option(Jester, vec!["You gain a copy", "They gain a copy"])).option() == Some(1) {
+            opponent.gain(state, card);
+        } else {
+            player.gain(state, card);
+        }
+    });
+    NoProblem
+}
+
+pub fn bag_of_gold(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    player.gain_to_deck(state, Gold);
+    NoProblem
+}
+
+/// Diadem is worth an extra $1 for each action the player hasn't used.
+pub fn diadem(player: &mut PlayerHandle) -> Response {
+    player.buying_power += player.actions;
+    NoProblem
+}
+
+pub fn followers<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(2);
+    player.gain(state, Estate);
+    attack(Followers, state, opponents, |opponent, state| discard_down_to(Followers, opponent, state, 3));
+    NoProblem
+}
+
+/// While Princess is in play, cards cost $2 less. Playing it twice, e.g.
+/// with Throne Room, doesn't make them any cheaper.
+pub fn princess(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buys += 1;
+    state.princess = true;
+    NoProblem
+}
+
+pub fn trusty_steed(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    // Two different options, one after the other.
+    let mut options = vec!["+2 Cards", "+2 Actions", "+$2", "Gain 4 Silvers and put your deck into your discard pile"];
+    for _ in range(0u, 2) {
+        let chosen = match player.decide(state, Decision::option(TrustySteed, options.clone())).option() {
+            Some(i) => options.remove(i).unwrap(),
+            None => break,
+        };
+        match chosen {
+            "+2 Cards" => player.draw_n(2),
+            "+2 Actions" => player.actions += 2,
+            "+$2" => player.buying_power += 2,
+            _ => {
+                for _ in range(0u, 4) {
+                    player.gain(state, Silver);
+                }
+                player.discard_deck();
+            },
+        }
+    }
+    NoProblem
+}
+
+#[cfg(test)]
+#[path = "tests/cornucopia.rs"]
+mod tests;
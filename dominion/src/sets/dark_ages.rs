@@ -0,0 +1,633 @@
+use std::collections::RingBuf;
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::{attack, NotBlocked, Outcome};
+use super::super::response::*;
+use super::super::rng::GameRng;
+use super::dominion::discard_down_to;
+use super::guilds::name_card;
+
+/// The kingdom cards in this set. Knights stands in for the Knights pile.
+/// Beggar, Market Square, Band of Misfits, Fortress, Procession and
+/// Counterfeit aren't available yet.
+pub fn kingdom() -> Vec<Card> {
+    vec![PoorHouse, Squire, Vagrant, Forager, Hermit, Sage, Storeroom, Urchin, Armory, DeathCart,
+         Feodum, Ironmonger, Marauder, Rats, Scavenger, WanderingMinstrel, BanditCamp, Catacombs,
+         Count, Cultist, Graverobber, JunkDealer, Knights, Mystic, Pillage, Rebuild, Rogue, Altar,
+         HuntingGrounds]
+}
+
+/// The ten different Knights, one of each.
+pub fn knights() -> Vec<Card> {
+    vec![DameAnna, DameJosephine, DameMolly, DameNatalie, DameSylvia, SirBailey, SirDestry, SirMartin,
+         SirMichael, SirVander]
+}
+
+/// The Ruins pile for a game with `num_players` players: 10 of each kind
+/// shuffled together, with 10 kept for each player after the first.
+pub fn ruins_pile(num_players: uint, rng: &GameRng) -> Vec<Card> {
+    let mut pile: Vec<Card> = [AbandonedMine, RuinedLibrary, RuinedMarket, RuinedVillage, Survivors].iter()
+        .flat_map(|x| Some(*x).into_iter().cycle().take(10))
+        .collect();
+    rng.shuffle(pile.as_mut_slice());
+    pile.truncate(10 * (num_players - 1));
+    pile
+}
+
+/// Gain the top card of the Ruins pile, if there's one left.
+fn gain_ruins(player: &mut PlayerHandle, state: &mut GameState) {
+    if let Some(ruins) = state.top_of(Ruins) {
+        player.gain(state, ruins);
+    }
+}
+
+/// Whether a card costs from $3 to $6, for Knights, Graverobber and Rogue.
+pub fn costs_3_to_6(state: &GameState, card: Card) -> bool {
+    let cost = state.cost(card);
+    cost >= Cost::coins(3) && cost <= Cost::coins(6)
+}
+
+/// The player reveals the top 2 cards of their deck, trashes one of them
+/// costing from $3 to $6, and discards the rest. Returns the card trashed.
+fn reveal_and_trash(card: Card, player: &mut PlayerHandle, state: &mut GameState) -> Option<Card> {
+    let revealed: Vec<Card> = range(0u, 2).filter_map(|_| player.reveal()).collect();
+    let candidates: Vec<Card> = revealed.into_iter().filter(|x| costs_3_to_6(state, *x)).collect();
+    let trashed = player.decide(state, Decision::cards(card, Trashing, candidates, 1, 1)).cards().pop();
+    if let Some(trashed) = trashed {
+        player.trash_from(state, trashed, SetAside);
+    }
+    player.discard_all(SetAside);
+    trashed
+}
+
+/// Every Urchin already in play can be trashed for a Mercenary when
+/// another Attack card is played.
+pub fn attack_played(player: &mut PlayerHandle, state: &mut GameState, played: Card) {
+    let mut urchins = player.in_play.iter().filter(|x| **x == Urchin).count();
+    // The Urchin being played doesn't count itself.
+    if played == Urchin && urchins > 0 {
+        urchins -= 1;
+    }
+    for _ in range(0, urchins) {
+        if !state.can_take(Mercenary) || !player.decide(state, Decision::yes_no(Urchin, Trashing, Some(Urchin), true)).is_yes() {
+            break;
+        }
+        player.trash_from_play(state, Urchin);
+        player.gain(state, Mercenary);
+    }
+}
+
+pub fn poor_house(player: &mut PlayerHandle) -> Response {
+    for card in player.hand.clone().into_iter() {
+        player.record(Revealed{player: player.id, card: card});
+    }
+    // Each Treasure costs $1, but it never goes below $0.
+    let money = player.hand.iter().filter(|x| x.is_money()).count();
+    let total = player.buying_power + 4;
+    player.buying_power = if money > total { 0 } else { total - money };
+    NoProblem
+}
+
+pub fn squire(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    match player.decide(state, Decision::option(Squire, vec!["+2 Actions", "+2 Buys", "Gain a Silver"])).option() {
+        Some(2) => player.gain(state, Silver),
+        Some(1) => {
+            player.buys += 2;
+            NoProblem
+        },
+        _ => {
+            player.actions += 2;
+            NoProblem
+        },
+    }
+}
+
+/// Trashing Squire gains an Attack card.
+pub fn squire_trashed(player: &mut PlayerHandle, state: &mut GameState) {
+    if let Some(card) = player.decide(state, Decision::any_pile(Squire, Gaining).only(Attack)).pile() {
+        player.gain(state, card);
+    }
+}
+
+pub fn vagrant(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    if let Some(card) = player.reveal() {
+        let keep = card.is_curse() || card.is_victory() || card.has_type(Ruins) || card.has_type(Shelter);
+        player.move_card(card, SetAside, if keep { Hand } else { Deck });
+    }
+    NoProblem
+}
+
+pub fn forager(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    player.buys += 1;
+    let hand = player.get_hand();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    if let Some(card) = player.decide(state, Decision::cards(Forager, Trashing, hand, min, 1)).cards().pop() {
+        player.trash(state, card);
+    }
+    let mut money: Vec<Card> = state.trash.iter().filter(|x| x.is_money()).map(|x| *x).collect();
+    money.sort_by(|a, b| a.name().cmp(b.name()));
+    money.dedup();
+    player.buying_power += money.len();
+    NoProblem
+}
+
+pub fn hermit(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let candidates: Vec<Card> = player.hand.iter().chain(player.discard.iter()).filter(|x| !x.is_money()).map(|x| *x).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Hermit, Trashing, candidates, 0, 1)).cards().pop() {
+        if !player.trash_from(state, card, Hand) {
+            player.trash_from(state, card, DiscardPile);
+        }
+    }
+    match player.decide(state, Decision::pile(Hermit, Gaining, Cost::coins(3))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+/// A Hermit discarded from play on a turn with nothing bought is trashed
+/// for a Madman.
+pub fn hermit_discarded(player: &mut PlayerHandle, state: &mut GameState) {
+    if player.bought.is_empty() && player.trash_from(state, Hermit, InPlay) {
+        player.gain(state, Madman);
+    }
+}
+
+pub fn madman(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 2;
+    if player.return_to_pile(state, Madman, InPlay) {
+        let n = player.hand.len();
+        player.draw_n(n);
+    }
+    NoProblem
+}
+
+pub fn sage(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    loop {
+        match player.reveal() {
+            Some(card) if state.cost(card) >= Cost::coins(3) => {
+                player.move_card(card, SetAside, Hand);
+                break;
+            },
+            Some(_) => (),
+            None => break,
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn storeroom(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buys += 1;
+    let hand = player.get_hand();
+    let n = hand.len();
+    let discarded = player.decide(state, Decision::cards(Storeroom, Discarding, hand, 0, n)).cards();
+    for card in discarded.iter() {
+        player.discard(*card);
+    }
+    player.draw_n(discarded.len());
+    let hand = player.get_hand();
+    let n = hand.len();
+    let discarded = player.decide(state, Decision::cards(Storeroom, Discarding, hand, 0, n)).cards();
+    for card in discarded.iter() {
+        player.discard(*card);
+    }
+    player.buying_power += discarded.len();
+    NoProblem
+}
+
+pub fn urchin<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw();
+    player.actions += 1;
+    attack(Urchin, state, opponents, |opponent, state| discard_down_to(Urchin, opponent, state, 4));
+    NoProblem
+}
+
+pub fn mercenary<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    let hand = player.get_hand();
+    if hand.len() < 2 {
+        return NoProblem;
+    }
+    let trashed = player.decide(state, Decision::cards(Mercenary, Trashing, hand, 0, 2)).cards();
+    if trashed.len() < 2 {
+        return NoProblem;
+    }
+    for card in trashed.into_iter() {
+        player.trash(state, card);
+    }
+    player.draw_n(2);
+    player.buying_power += 2;
+    attack(Mercenary, state, opponents, |opponent, state| discard_down_to(Mercenary, opponent, state, 3));
+    NoProblem
+}
+
+pub fn armory(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    match player.decide(state, Decision::pile(Armory, Gaining, Cost::coins(4))).pile() {
+        Some(card) => player.gain_to_deck(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn death_cart(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buying_power += 5;
+    let actions: Vec<Card> = player.hand.iter().filter(|x| x.is_action()).map(|x| *x).collect();
+    match player.decide(state, Decision::cards(DeathCart, Trashing, actions, 0, 1)).cards().pop() {
+        Some(card) => player.trash(state, card),
+        None => player.trash_from_play(state, DeathCart),
+    }
+    NoProblem
+}
+
+/// Gaining a Death Cart also gains two Ruins.
+pub fn death_cart_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    for _ in range(0u, 2) {
+        gain_ruins(player, state);
+    }
+}
+
+/// Trashing a Feodum gains three Silvers.
+pub fn feodum_trashed(player: &mut PlayerHandle, state: &mut GameState) {
+    for _ in range(0u, 3) {
+        player.gain(state, Silver);
+    }
+}
+
+pub fn ironmonger(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    let card = match player.reveal() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    let junk = (card.is_victory() || card.is_curse()) && !card.is_action() && !card.is_money();
+    let discarding = player.decide(state, Decision::yes_no(Ironmonger, Discarding, Some(card), junk)).is_yes();
+    player.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+    if card.is_action() {
+        player.actions += 1;
+    }
+    if card.is_money() {
+        player.buying_power += 1;
+    }
+    if card.is_victory() {
+        player.draw();
+    }
+    NoProblem
+}
+
+pub fn marauder<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.gain(state, Spoils);
+    attack(Marauder, state, opponents, |opponent, state| gain_ruins(opponent, state));
+    NoProblem
+}
+
+/// Spoils goes back to its pile once it's played.
+pub fn spoils(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.return_to_pile(state, Spoils, InPlay);
+    NoProblem
+}
+
+pub fn rats(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.gain(state, Rats);
+    // A hand of nothing but Rats is revealed instead.
+    let others: Vec<Card> = player.hand.iter().filter(|x| **x != Rats).map(|x| *x).collect();
+    if others.is_empty() {
+        for card in player.hand.clone().into_iter() {
+            player.record(Revealed{player: player.id, card: card});
+        }
+        return NoProblem;
+    }
+    if let Some(card) = player.decide(state, Decision::cards(Rats, Trashing, others, 1, 1)).cards().pop() {
+        player.trash(state, card);
+    }
+    NoProblem
+}
+
+pub fn scavenger(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buying_power += 2;
+    if player.decide(state, Decision::yes_no(Scavenger, Discarding, None, false)).is_yes() {
+        player.discard_deck();
+    }
+    let discard = player.get_discard();
+    let min = if discard.is_empty() { 0 } else { 1 };
+    if let Some(card) = player.decide(state, Decision::cards(Scavenger, TopDecking, discard, min, 1)).cards().pop() {
+        player.move_card(card, DiscardPile, Deck);
+    }
+    NoProblem
+}
+
+pub fn wandering_minstrel(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 2;
+    let revealed: Vec<Card> = range(0u, 3).filter_map(|_| player.reveal()).collect();
+    let (actions, _) = revealed.partition(|x| x.is_action());
+    let order = player.decide(state, Decision::order(WanderingMinstrel, TopDecking, actions)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn bandit_camp(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 2;
+    player.gain(state, Spoils)
+}
+
+pub fn catacombs(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let revealed: Vec<Card> = range(0u, 3).filter_map(|_| player.reveal()).collect();
+    let options = vec!["Put them into your hand", "Discard them and draw 3"];
+    match player.decide(state, Decision::option(Catacombs, options)).option() {
+        Some(1) => {
+            player.discard_all(SetAside);
+            player.draw_n(3);
+        },
+        _ => {
+            for card in revealed.into_iter() {
+                player.move_card(card, SetAside, Hand);
+            }
+        },
+    }
+    NoProblem
+}
+
+/// Trashing Catacombs gains a cheaper card.
+pub fn catacombs_trashed(player: &mut PlayerHandle, state: &mut GameState) {
+    let cost = state.cost(Catacombs);
+    if cost.coins == 0 {
+        return;
+    }
+    if let Some(card) = player.decide(state, Decision::pile(Catacombs, Gaining, cost.minus(1))).pile() {
+        player.gain(state, card);
+    }
+}
+
+pub fn count(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let options = vec!["Discard 2 cards", "Put a card from your hand onto your deck", "Gain a Copper"];
+    match player.decide(state, Decision::option(Count, options)).option() {
+        Some(2) => { player.gain(state, Copper); },
+        Some(1) => {
+            let hand = player.get_hand();
+            let min = if hand.is_empty() { 0 } else { 1 };
+            if let Some(card) = player.decide(state, Decision::cards(Count, TopDecking, hand, min, 1)).cards().pop() {
+                player.move_card(card, Hand, Deck);
+            }
+        },
+        _ => {
+            let hand = player.get_hand();
+            let n = if hand.len() < 2 { hand.len() } else { 2 };
+            for card in player.decide(state, Decision::cards(Count, Discarding, hand, n, n)).cards().into_iter() {
+                player.discard(card);
+            }
+        },
+    }
+    match player.decide(state, Decision::option(Count, vec!["+$3", "Trash your hand", "Gain a Duchy"])).option() {
+        Some(2) => { player.gain(state, Duchy); },
+        Some(1) => {
+            for card in player.get_hand().into_iter() {
+                player.trash(state, card);
+            }
+        },
+        _ => player.buying_power += 3,
+    }
+    NoProblem
+}
+
+pub fn cultist(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.draw_n(2);
+    attack(Cultist, state, opponents.iter_mut(), |opponent, state| gain_ruins(opponent, state));
+    if !player.has_in_hand(Cultist) || !player.decide(state, Decision::yes_no(Cultist, Playing, Some(Cultist), true)).is_yes() {
+        return NoProblem;
+    }
+    player.put_in_play(Cultist);
+    Cultist.play_effects(player, state, opponents)
+}
+
+pub fn graverobber(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let options = vec!["Gain a card from the trash", "Trash an Action card from your hand"];
+    match player.decide(state, Decision::option(Graverobber, options)).option() {
+        Some(1) => {
+            let actions: Vec<Card> = player.hand.iter().filter(|x| x.is_action()).map(|x| *x).collect();
+            let min = if actions.is_empty() { 0 } else { 1 };
+            let to_trash = match player.decide(state, Decision::cards(Graverobber, Trashing, actions, min, 1)).cards().pop() {
+                Some(card) => card,
+                None => return NoProblem,
+            };
+            player.trash(state, to_trash);
+            match player.decide(state, Decision::pile(Graverobber, Gaining, state.cost(to_trash).plus(3))).pile() {
+                Some(card) => player.gain(state, card),
+                None => NoProblem,
+            }
+        },
+        _ => {
+            let candidates: Vec<Card> = state.trash.iter().filter(|x| costs_3_to_6(state, **x)).map(|x| *x).collect();
+            let min = if candidates.is_empty() { 0 } else { 1 };
+            if let Some(card) = player.decide(state, Decision::cards(Graverobber, Gaining, candidates, min, 1)).cards().pop() {
+                player.gain_from_trash(state, card, Deck);
+            }
+            NoProblem
+        },
+    }
+}
+
+pub fn junk_dealer(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buying_power += 1;
+    let hand = player.get_hand();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    if let Some(card) = player.decide(state, Decision::cards(JunkDealer, Trashing, hand, min, 1)).cards().pop() {
+        player.trash(state, card);
+    }
+    NoProblem
+}
+
+/// What every Knight does after its own bonus: each other player reveals
+/// the top 2 cards of their deck and trashes one costing from $3 to $6.
+/// If a Knight is trashed this way, so is the one that was played. Sir
+/// Michael also makes them discard down to 3 cards first.
+pub fn knight<'a, T: Iterator<&'a mut PlayerHandle>>(card: Card, player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    match card {
+        DameAnna => {
+            let hand = player.get_hand();
+            for trashed in player.decide(state, Decision::cards(DameAnna, Trashing, hand, 0, 2)).cards().into_iter() {
+                player.trash(state, trashed);
+            }
+        },
+        DameMolly => player.actions += 2,
+        DameNatalie => {
+            if let Some(gained) = player.decide(state, Decision::pile(DameNatalie, Gaining, Cost::coins(3))).pile() {
+                player.gain(state, gained);
+            }
+        },
+        DameSylvia => player.buying_power += 2,
+        SirBailey => {
+            player.draw();
+            player.actions += 1;
+        },
+        SirDestry => player.draw_n(2),
+        SirMartin => player.buys += 2,
+        _ => (),
+    }
+    let mut knight_trashed = false;
+    attack(card, state, opponents, |opponent, state| {
+        if card == SirMichael {
+            discard_down_to(SirMichael, opponent, state, 3);
+        }
+        if reveal_and_trash(card, opponent, state).map_or(false, |x| x.has_type(Knight)) {
+            knight_trashed = true;
+        }
+    });
+    if knight_trashed {
+        player.trash_from_play(state, card);
+    }
+    NoProblem
+}
+
+pub fn mystic(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    player.buying_power += 2;
+    let named = name_card(Mystic, player, state);
+    if let Some(card) = player.reveal() {
+        player.move_card(card, SetAside, if Some(card) == named { Hand } else { Deck });
+    }
+    NoProblem
+}
+
+pub fn pillage<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.trash_from_play(state, Pillage);
+    attack(Pillage, state, opponents, |opponent, state| {
+        if opponent.hand.len() < 5 {
+            return;
+        }
+        for card in opponent.hand.clone().into_iter() {
+            opponent.record(Revealed{player: opponent.id, card: card});
+        }
+        // The player who played Pillage picks the card.
+        let hand = opponent.get_hand();
+        if let Some(card) = player.decide(state, Decision::cards(Pillage, Discarding, hand, 1, 1)).cards().pop() {
+            opponent.discard(card);
+        }
+    });
+    for _ in range(0u, 2) {
+        player.gain(state, Spoils);
+    }
+    NoProblem
+}
+
+pub fn rebuild(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    let named = name_card(Rebuild, player, state);
+    let mut found = None;
+    loop {
+        match player.reveal() {
+            Some(card) if card.is_victory() && Some(card) != named => {
+                found = Some(card);
+                break;
+            },
+            Some(_) => (),
+            None => break,
+        }
+    }
+    let trashed = match found {
+        Some(card) => card,
+        None => {
+            player.discard_all(SetAside);
+            return NoProblem;
+        },
+    };
+    player.trash_from(state, trashed, SetAside);
+    player.discard_all(SetAside);
+    match player.decide(state, Decision::pile(Rebuild, Gaining, state.cost(trashed).plus(3)).only(Victory)).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn rogue<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 2;
+    let candidates: Vec<Card> = state.trash.iter().filter(|x| costs_3_to_6(state, **x)).map(|x| *x).collect();
+    if candidates.is_empty() {
+        attack(Rogue, state, opponents, |opponent, state| { reveal_and_trash(Rogue, opponent, state); });
+        return NoProblem;
+    }
+    if let Some(card) = player.decide(state, Decision::cards(Rogue, Gaining, candidates, 1, 1)).cards().pop() {
+        player.gain_from_trash(state, card, DiscardPile);
+    }
+    NoProblem
+}
+
+pub fn altar(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    if let Some(card) = player.decide(state, Decision::cards(Altar, Trashing, hand, min, 1)).cards().pop() {
+        player.trash(state, card);
+    }
+    match player.decide(state, Decision::pile(Altar, Gaining, Cost::coins(5))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn hunting_grounds(player: &mut PlayerHandle) -> Response {
+    player.draw_n(4);
+    NoProblem
+}
+
+/// Trashing Hunting Grounds gains a Duchy or three Estates.
+pub fn hunting_grounds_trashed(player: &mut PlayerHandle, state: &mut GameState) {
+    match player.decide(state, Decision::option(HuntingGrounds, vec!["Gain a Duchy", "Gain 3 Estates"])).option() {
+        Some(1) => {
+            for _ in range(0u, 3) {
+                player.gain(state, Estate);
+            }
+        },
+        _ => { player.gain(state, Duchy); },
+    }
+}
+
+pub fn ruins(card: Card, player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    match card {
+        AbandonedMine => player.buying_power += 1,
+        RuinedLibrary => { player.draw(); },
+        RuinedMarket => player.buys += 1,
+        RuinedVillage => player.actions += 1,
+        // Survivors: the top 2 cards are discarded, or put back in any order.
+        _ => {
+            let revealed: Vec<Card> = range(0u, 2).filter_map(|_| player.reveal()).collect();
+            if player.decide(state, Decision::yes_no(Survivors, Discarding, None, false)).is_yes() {
+                player.discard_all(SetAside);
+            } else {
+                let order = player.decide(state, Decision::order(Survivors, TopDecking, revealed)).cards();
+                for card in order.iter().rev() {
+                    player.move_card(*card, SetAside, Deck);
+                }
+            }
+        },
+    }
+    NoProblem
+}
+
+pub fn necropolis(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    NoProblem
+}
+
+/// Buying a Victory card lets the player trash Hovel from their hand.
+pub fn hovel_react(player: &mut PlayerHandle, state: &mut GameState) -> Outcome {
+    player.trash(state, Hovel);
+    NotBlocked
+}
+
+#[cfg(test)]
+#[path = "tests/dark_ages.rs"]
+mod tests;
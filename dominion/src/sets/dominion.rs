@@ -1,45 +1,285 @@
+use std::collections::RingBuf;
+
 use super::super::{GameState, Player, PlayerHandle};
 use super::super::card::*;
-use super::super::notify::*;
-use super::super::reaction::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::attack;
 use super::super::response::*;
 
-pub fn cellar(player: &mut PlayerHandle, to_discard: &[Card]) -> Response {
+/// The kingdom cards in this set.
+pub fn kingdom() -> Vec<Card> {
+    vec![Cellar, Chapel, Moat, Chancellor, Village, Woodcutter, Workshop, Bureaucrat, Feast, Gardens,
+         Militia, Moneylender, Remodel, Smithy, Spy, Thief, ThroneRoom, CouncilRoom, Festival,
+         Laboratory, Library, Market, Mine, Witch, Adventurer]
+}
+
+pub fn cellar(player: &mut PlayerHandle, state: &mut GameState) -> Response {
     player.actions += 1;
+    let hand = player.get_hand();
+    let n = hand.len();
+    let to_discard = player.decide(state, Decision::cards(Cellar, Discarding, hand, 0, n)).cards();
     for card in to_discard.iter() {
         player.discard(*card);
-        player.draw();
     }
+    player.draw_n(to_discard.len());
     NoProblem
 }
 
-pub fn chapel(player: &mut PlayerHandle, state: &mut GameState, to_trash: &[Card]) -> Response {
-    for card in to_trash.iter().take(4) {
-        player.trash(state, *card);
+pub fn chapel(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Chapel, Trashing, hand, 0, 4)).cards().into_iter() {
+        player.trash(state, card);
     }
     NoProblem
 }
 
-pub fn militia<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, mut opponents: T) -> Response {
+pub fn chancellor(player: &mut PlayerHandle, state: &mut GameState) -> Response {
     player.buying_power += 2;
-    for opponent in opponents {
-        for _ in range(3, opponent.get_hand_size()) {
-            opponent.notify_chan.send(Militia);
-            match opponent.react_port.recv() {
-                MilitiaDiscard(card) => opponent.discard(card),
-                RevealMoat => opponent.has_or_else(Moat, || panic!("player tried to block Militia with Moat, but he didn't have one!")),
-                NotImplemented => {
-                    let card = opponent.get_hand()[0];
-                    opponent.discard(card);
-                },
-                resp => panic!("player had to react to Militia, but responded with {}!", resp),
-            }
+    if player.decide(state, Decision::yes_no(Chancellor, Discarding, None, false)).is_yes() {
+        player.discard_deck();
+    }
+    NoProblem
+}
+
+pub fn village(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 2;
+    NoProblem
+}
+
+pub fn woodcutter(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    player.buying_power += 2;
+    NoProblem
+}
+
+pub fn workshop(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    match player.decide(state, Decision::pile(Workshop, Gaining, Cost::coins(4))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn bureaucrat<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    // Nothing happens to the attacker if the Silvers have run out.
+    player.gain_to_deck(state, Silver);
+    attack(Bureaucrat, state, opponents, |opponent, state| {
+        let victory: Vec<Card> = opponent.hand.iter().filter_map(|x| if x.is_victory() { Some(*x) } else { None }).collect();
+        if victory.is_empty() {
+            return;
         }
+        let card = opponent.decide(state, Decision::cards(Bureaucrat, TopDecking, victory, 1, 1)).cards()[0];
+        opponent.record(Revealed{player: opponent.id, card: card});
+        opponent.move_card(card, Hand, Deck);
+    });
+    NoProblem
+}
+
+pub fn feast(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.trash_from_play(state, Feast);
+    match player.decide(state, Decision::pile(Feast, Gaining, Cost::coins(5))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
     }
+}
+
+pub fn militia<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 2;
+    attack(Militia, state, opponents, |opponent, state| discard_down_to(Militia, opponent, state, 3));
     NoProblem
 }
 
+/// Make a player discard down to `n` cards in hand for an attack like
+/// Militia. Everything is discarded at once.
+pub fn discard_down_to(card: Card, player: &mut PlayerHandle, state: &mut GameState, n: uint) {
+    let hand = player.get_hand();
+    if hand.len() <= n {
+        return;
+    }
+    let extra = hand.len() - n;
+    for discarded in player.decide(state, Decision::cards(card, Discarding, hand, extra, extra)).cards().into_iter() {
+        player.discard(discarded);
+    }
+}
+
 pub fn moat(player: &mut PlayerHandle) -> Response {
     player.draw_n(2);
     NoProblem
 }
+
+pub fn moneylender(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    if player.has_in_hand(Copper) {
+        player.trash(state, Copper);
+        player.buying_power += 3;
+    }
+    NoProblem
+}
+
+pub fn remodel(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Remodel, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    match player.decide(state, Decision::pile(Remodel, Gaining, state.cost(to_trash).plus(2))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn smithy(player: &mut PlayerHandle) -> Response {
+    player.draw_n(3);
+    NoProblem
+}
+
+pub fn spy<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw();
+    player.actions += 1;
+    // The player decides whether each revealed card is discarded. By
+    // default, worthless cards are discarded from their own deck and left
+    // on top of everyone else's.
+    if let Some(card) = player.reveal() {
+        let discarding = player.decide(state, Decision::yes_no(Spy, Discarding, Some(card), is_worthless(card))).is_yes();
+        player.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+    }
+    attack(Spy, state, opponents, |opponent, state| {
+        if let Some(card) = opponent.reveal() {
+            let discarding = player.decide(state, Decision::yes_no(Spy, Discarding, Some(card), !is_worthless(card))).is_yes();
+            opponent.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+        }
+    });
+    NoProblem
+}
+
+fn is_worthless(card: Card) -> bool {
+    card.is_victory() || card.is_curse()
+}
+
+pub fn thief<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    attack(Thief, state, opponents, |opponent, state| {
+        let revealed: Vec<Card> = range(0u, 2).filter_map(|_| opponent.reveal()).collect();
+        // The player picks which Treasure is trashed, and whether to gain
+        // it. By default, they steal the most expensive one.
+        let mut treasures: Vec<Card> = revealed.into_iter().filter(|x| x.is_money()).collect();
+        treasures.sort_by(|a, b| b.cost().sort_key().cmp(&a.cost().sort_key()));
+        if let Some(card) = player.decide(state, Decision::cards(Thief, Trashing, treasures, 1, 1)).cards().pop() {
+            opponent.trash_from(state, card, SetAside);
+            if player.decide(state, Decision::yes_no(Thief, Gaining, Some(card), true)).is_yes() {
+                player.gain_from_trash(state, card, DiscardPile);
+            }
+        }
+        opponent.discard_all(SetAside);
+    });
+    NoProblem
+}
+
+pub fn throne_room(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    let actions: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_action() { Some(*x) } else { None }).collect();
+    // With no Action cards in hand, there's nothing to choose.
+    let to_repeat = match player.decide(state, Decision::cards(ThroneRoom, Playing, actions, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.put_in_play(to_repeat);
+    let durations = player.durations.len();
+    let resp = to_repeat.play_in_place(player, state, opponents);
+    if resp.is_err() {
+        return resp;
+    }
+    let resp = to_repeat.play_in_place(player, state, opponents);
+    player.stay_with_durations(ThroneRoom, durations);
+    resp
+}
+
+pub fn council_room<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, mut opponents: T) -> Response {
+    player.draw_n(4);
+    player.buys += 1;
+    for opponent in opponents {
+        opponent.draw();
+    }
+    NoProblem
+}
+
+pub fn festival(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    player.buys += 1;
+    player.buying_power += 2;
+    NoProblem
+}
+
+pub fn laboratory(player: &mut PlayerHandle) -> Response {
+    player.draw_n(2);
+    player.actions += 1;
+    NoProblem
+}
+
+pub fn library(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    while player.hand.len() < 7 && player.can_draw() {
+        match player.draw() {
+            // The player decides which Action cards to skip. By default,
+            // they're skipped if they can't be played.
+            Some(card) if card.is_action() => {
+                let can_play = player.actions > 0;
+                if player.decide(state, Decision::yes_no(Library, SettingAside, Some(card), !can_play)).is_yes() {
+                    player.move_card(card, Hand, SetAside);
+                }
+            },
+            _ => (),
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn market(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buys += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+pub fn mine(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let money: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    let to_trash = match player.decide(state, Decision::cards(Mine, Trashing, money, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    match player.decide(state, Decision::pile(Mine, Gaining, state.cost(to_trash).plus(3)).only(Money)).pile() {
+        Some(card) => player.gain_to_hand(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn witch<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(2);
+    attack(Witch, state, opponents, |opponent, state| {
+        // Once the Curses run out, there's nothing left to give.
+        opponent.gain(state, Curse);
+    });
+    NoProblem
+}
+
+pub fn adventurer(player: &mut PlayerHandle) -> Response {
+    let mut found = 0u;
+    while found < 2 {
+        match player.reveal() {
+            Some(card) if card.is_money() => {
+                player.move_card(card, SetAside, Hand);
+                found += 1;
+            },
+            Some(_) => (),
+            None => break,
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+#[cfg(test)]
+#[path = "tests/dominion.rs"]
+mod tests;
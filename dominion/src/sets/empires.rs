@@ -0,0 +1,541 @@
+use std::cmp;
+use std::collections::{HashMap, RingBuf};
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::phase;
+use super::super::reaction::attack;
+use super::super::response::*;
+use super::super::rng::GameRng;
+use super::super::scoring::{ScoreSheet, ScoringRule};
+use super::adventures::trash_from_hand;
+use super::cornucopia::differently_named;
+use super::dominion::discard_down_to;
+
+/// The kingdom cards in this set. The top card of each split pile stands in
+/// for the whole pile. Castles, Encampment, Enchantress, Overlord,
+/// Groundskeeper, Charm, Crown and Archive aren't available yet.
+pub fn kingdom() -> Vec<Card> {
+    vec![Engineer, CityQuarter, RoyalBlacksmith, Patrician, Settlers, Catapult, ChariotRace, FarmersMarket,
+         Gladiator, Sacrifice, Temple, Villa, Capital, Forum, Legionary, WildHunt]
+}
+
+/// The split piles, top card first. Each pile has five of each card.
+pub fn split_piles() -> Vec<(Card, Card)> {
+    vec![(Patrician, Emporium), (Settlers, BustlingVillage), (Catapult, Rocks), (Gladiator, Fortune)]
+}
+
+/// The Events in this set. Tax, Banquet, Conquest, Donate and Fundraiser
+/// aren't available yet.
+pub fn events() -> Vec<Card> {
+    vec![Advance, Delve, Ritual, SaltTheEarth, Wedding, Windfall, Dominate, Triumph, Annex]
+}
+
+/// The Landmarks in this set. Only the ones that change final scoring are
+/// available so far.
+pub fn landmarks() -> Vec<Card> {
+    vec![BanditFort, Fountain, Keep, Museum, Obelisk, Orchard, Palace, Tower, TriumphalArch, Wall, WolfDen]
+}
+
+/// The scoring rule for a Landmark. Obelisk picks one of `action_piles`,
+/// given as the different cards in each of the supply's Action piles.
+pub fn scoring_rule(landmark: Card, action_piles: &[Vec<Card>], rng: &GameRng) -> Box<ScoringRule + 'static> {
+    if landmark != Obelisk {
+        return box landmark as Box<ScoringRule + 'static>;
+    }
+    let mut piles = action_piles.to_vec();
+    rng.shuffle(piles.as_mut_slice());
+    box ObeliskPile(piles.into_iter().next().unwrap_or(Vec::new())) as Box<ScoringRule + 'static>
+}
+
+/// Obelisk's rule: 2 VP for each card from the pile it picked.
+pub struct ObeliskPile(pub Vec<Card>);
+
+impl ScoringRule for ObeliskPile {
+    fn score(&self, sheet: &ScoreSheet) -> int {
+        let ObeliskPile(ref pile) = *self;
+        2 * sheet.cards.iter().filter(|x| pile.contains(*x)).count() as int
+    }
+}
+
+/// How many copies of `card` there are in `cards`.
+fn copies(cards: &[Card], card: Card) -> uint {
+    cards.iter().filter(|x| **x == card).count()
+}
+
+/// How many copies of each differently named card there are in `cards`.
+fn tally(cards: &[Card]) -> HashMap<Card, uint> {
+    use std::collections::hash_map::{Vacant, Occupied};
+    let mut counts = HashMap::new();
+    for card in cards.iter() {
+        match counts.entry(*card) {
+            Vacant(entry) => { entry.set(1u); },
+            Occupied(entry) => { *entry.into_mut() += 1; },
+        }
+    }
+    counts
+}
+
+/// Reveal the player's hand, and return what's in it.
+fn reveal_hand(player: &mut PlayerHandle) -> Vec<Card> {
+    let hand = player.get_hand();
+    for card in hand.iter() {
+        player.record(Revealed{player: player.id, card: *card});
+    }
+    hand
+}
+
+/// Let the player take a copy of `wanted` from their discard pile into
+/// their hand, as for Settlers.
+fn take_from_discard(card: Card, wanted: Card, player: &mut PlayerHandle, state: &mut GameState) {
+    if !player.discard.contains(&wanted) {
+        return;
+    }
+    if player.decide(state, Decision::yes_no(card, Revealing, Some(wanted), true)).is_yes() {
+        player.record(Revealed{player: player.id, card: wanted});
+        player.move_card(wanted, DiscardPile, Hand);
+    }
+}
+
+/// Take every VP token off a Gathering pile.
+fn take_pile_vp(card: Card, player: &mut PlayerHandle, state: &mut GameState) {
+    let vp = state.take_pile_vp(card);
+    if vp > 0 {
+        player.take_vp_tokens(vp);
+    }
+}
+
+pub fn engineer(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    if let Some(card) = player.decide(state, Decision::pile(Engineer, Gaining, Cost::coins(4))).pile() {
+        player.gain(state, card);
+    }
+    // Engineer can be trashed for a second card.
+    if !player.in_play.contains(&Engineer) || !player.decide(state, Decision::yes_no(Engineer, Trashing, Some(Engineer), false)).is_yes() {
+        return NoProblem;
+    }
+    player.trash_from_play(state, Engineer);
+    if let Some(card) = player.decide(state, Decision::pile(Engineer, Gaining, Cost::coins(4))).pile() {
+        player.gain(state, card);
+    }
+    NoProblem
+}
+
+pub fn city_quarter(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    let actions = reveal_hand(player).iter().filter(|x| x.is_action()).count();
+    player.draw_n(actions);
+    NoProblem
+}
+
+pub fn royal_blacksmith(player: &mut PlayerHandle) -> Response {
+    player.draw_n(5);
+    for card in reveal_hand(player).into_iter().filter(|x| *x == Copper) {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+/// Patrician puts the top card of the deck into the player's hand if it
+/// costs $5 or more.
+pub fn patrician(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    if let Some(card) = player.reveal() {
+        player.move_card(card, SetAside, if state.cost(card).coins >= 5 { Hand } else { Deck });
+    }
+    NoProblem
+}
+
+pub fn settlers(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    take_from_discard(Settlers, Copper, player, state);
+    NoProblem
+}
+
+pub fn catapult<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 1;
+    let trashed = match trash_from_hand(Catapult, player, state) {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    let curse = state.cost(trashed).coins >= 3;
+    let treasure = trashed.is_money();
+    attack(Catapult, state, opponents, |opponent, state| {
+        if curse {
+            opponent.gain(state, Curse);
+        }
+        if treasure {
+            discard_down_to(Catapult, opponent, state, 3);
+        }
+    });
+    NoProblem
+}
+
+/// Rocks gives a Silver when it's gained or trashed. It goes on the deck
+/// during the player's Buy phase, and into their hand otherwise.
+pub fn rocks_silver(player: &mut PlayerHandle, state: &mut GameState) {
+    if player.phase == phase::BuyPhase {
+        player.gain_to_deck(state, Silver);
+    } else {
+        player.gain_to_hand(state, Silver);
+    }
+}
+
+/// Chariot Race races the top card of the player's deck against the top of
+/// the next player's. Having no card to reveal counts as $0.
+pub fn chariot_race(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.actions += 1;
+    let mine = match player.reveal() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.move_card(mine, SetAside, Hand);
+    let theirs = match opponents.front_mut() {
+        Some(next) => {
+            let revealed = next.reveal();
+            if let Some(card) = revealed {
+                next.move_card(card, SetAside, Deck);
+            }
+            revealed
+        },
+        None => None,
+    };
+    if state.cost(mine) > theirs.map_or(Cost::coins(0), |x| state.cost(x)) {
+        player.buying_power += 1;
+        player.take_vp_tokens(1);
+    }
+    NoProblem
+}
+
+/// Farmers' Market adds a VP to its pile and gives $1 for each one there,
+/// until there are 4 to take, when it's trashed.
+pub fn farmers_market(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buys += 1;
+    if state.pile_vp(FarmersMarket) >= 4 {
+        take_pile_vp(FarmersMarket, player, state);
+        player.trash_from_play(state, FarmersMarket);
+    } else {
+        state.add_pile_vp(FarmersMarket, 1);
+        player.buying_power += state.pile_vp(FarmersMarket);
+    }
+    NoProblem
+}
+
+/// The next player can match the card revealed for Gladiator. If they
+/// don't, the player gets +$1 and a Gladiator is trashed from the supply.
+pub fn gladiator(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.buying_power += 2;
+    let hand = player.get_hand();
+    let revealed = match player.decide(state, Decision::cards(Gladiator, Revealing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.record(Revealed{player: player.id, card: revealed});
+    let matched = opponents.front_mut().map_or(false, |next| matches(revealed, next, state));
+    if !matched {
+        player.buying_power += 1;
+        player.trash_from_supply(state, Gladiator);
+    }
+    NoProblem
+}
+
+/// Whether the next player matches the card revealed for Gladiator by
+/// revealing a copy of their own.
+fn matches(card: Card, next: &mut PlayerHandle, state: &mut GameState) -> bool {
+    if !next.has_in_hand(card) || !next.decide(state, Decision::yes_no(Gladiator, Revealing, Some(card), true)).is_yes() {
+        return false;
+    }
+    next.record(Revealed{player: next.id, card: card});
+    true
+}
+
+pub fn sacrifice(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let trashed = match trash_from_hand(Sacrifice, player, state) {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    if trashed.is_action() {
+        player.draw_n(2);
+        player.actions += 2;
+    }
+    if trashed.is_money() {
+        player.buying_power += 2;
+    }
+    if trashed.is_victory() {
+        player.take_vp_tokens(2);
+    }
+    NoProblem
+}
+
+/// Temple trashes up to 3 differently named cards, and adds a VP to its
+/// pile for whoever gains the next one.
+pub fn temple(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.take_vp_tokens(1);
+    let mut hand = player.get_hand();
+    hand.sort_by(|a, b| a.name().cmp(b.name()));
+    hand.dedup();
+    let min = if hand.is_empty() { 0 } else { 1 };
+    for card in player.decide(state, Decision::cards(Temple, Trashing, hand, min, 3)).cards().into_iter() {
+        player.trash(state, card);
+    }
+    state.add_pile_vp(Temple, 1);
+    NoProblem
+}
+
+pub fn temple_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    take_pile_vp(Temple, player, state);
+}
+
+pub fn villa(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    player.buys += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+/// A gained Villa goes into the player's hand with +1 Action. Gaining one
+/// in the Buy phase goes back to the Action phase, so it can be played.
+pub fn villa_gained(player: &mut PlayerHandle, state: &mut GameState, to: Option<Zone>) {
+    let in_hand = match to {
+        Some(Hand) => true,
+        Some(zone) => player.move_card(Villa, zone, Hand),
+        None => false,
+    };
+    if !in_hand {
+        return;
+    }
+    player.actions += 1;
+    if player.phase == phase::BuyPhase {
+        player.enter_phase(phase::ActionPhase, state);
+    }
+}
+
+pub fn bustling_village(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 3;
+    take_from_discard(BustlingVillage, Settlers, player, state);
+    NoProblem
+}
+
+pub fn capital(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    NoProblem
+}
+
+/// Capital leaves 6 debt behind when it's discarded from play, which the
+/// player pays off straight away with whatever they have left.
+pub fn capital_discarded(player: &mut PlayerHandle) {
+    player.debt += 6;
+    let paid = cmp::min(player.debt, player.buying_power);
+    player.debt -= paid;
+    player.buying_power -= paid;
+}
+
+pub fn emporium(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+pub fn emporium_gained(player: &mut PlayerHandle) {
+    if player.in_play.iter().filter(|x| x.is_action()).count() >= 5 {
+        player.take_vp_tokens(2);
+    }
+}
+
+pub fn forum(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw_n(3);
+    player.actions += 1;
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Forum, Discarding, hand, 2, 2)).cards().into_iter() {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+pub fn legionary<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 3;
+    if !player.has_in_hand(Gold) || !player.decide(state, Decision::yes_no(Legionary, Revealing, Some(Gold), true)).is_yes() {
+        return NoProblem;
+    }
+    player.record(Revealed{player: player.id, card: Gold});
+    attack(Legionary, state, opponents, |opponent, state| {
+        discard_down_to(Legionary, opponent, state, 2);
+        opponent.draw();
+    });
+    NoProblem
+}
+
+pub fn wild_hunt(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let options = vec!["+3 Cards and add 1 VP to the Wild Hunt pile", "Gain an Estate and take the VP from the Wild Hunt pile"];
+    match player.decide(state, Decision::option(WildHunt, options)).option() {
+        Some(1) => if !player.gain(state, Estate).is_err() {
+            take_pile_vp(WildHunt, player, state);
+        },
+        _ => {
+            player.draw_n(3);
+            state.add_pile_vp(WildHunt, 1);
+        },
+    }
+    NoProblem
+}
+
+/// The first Fortune played each turn doubles the player's $.
+pub fn fortune(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    if player.played.iter().filter(|x| **x == Fortune).count() == 1 {
+        player.buying_power *= 2;
+    }
+    NoProblem
+}
+
+pub fn fortune_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    let gladiators = player.in_play.iter().filter(|x| **x == Gladiator).count();
+    for _ in range(0, gladiators) {
+        player.gain(state, Gold);
+    }
+}
+
+pub fn advance(player: &mut PlayerHandle, state: &mut GameState) {
+    let actions: Vec<Card> = player.hand.iter().filter(|x| x.is_action()).map(|x| *x).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Advance, Trashing, actions, 0, 1)).cards().pop() {
+        player.trash(state, card);
+        if let Some(gained) = player.decide(state, Decision::pile(Advance, Gaining, Cost::coins(6)).only(Action)).pile() {
+            player.gain(state, gained);
+        }
+    }
+}
+
+pub fn delve(player: &mut PlayerHandle, state: &mut GameState) {
+    player.buys += 1;
+    player.gain(state, Silver);
+}
+
+/// Ritual gives a VP for each $1 the trashed card cost, but only if there
+/// was a Curse to gain.
+pub fn ritual(player: &mut PlayerHandle, state: &mut GameState) {
+    if player.gain(state, Curse).is_err() {
+        return;
+    }
+    if let Some(card) = trash_from_hand(Ritual, player, state) {
+        let vp = state.cost(card).coins;
+        player.take_vp_tokens(vp);
+    }
+}
+
+pub fn salt_the_earth(player: &mut PlayerHandle, state: &mut GameState) {
+    player.take_vp_tokens(1);
+    if let Some(card) = player.decide(state, Decision::any_pile(SaltTheEarth, Trashing).only(Victory)).pile() {
+        player.trash_from_supply(state, card);
+    }
+}
+
+pub fn wedding(player: &mut PlayerHandle, state: &mut GameState) {
+    player.take_vp_tokens(1);
+    player.gain(state, Gold);
+}
+
+pub fn windfall(player: &mut PlayerHandle, state: &mut GameState) {
+    if !player.deck.is_empty() || !player.discard.is_empty() {
+        return;
+    }
+    for _ in range(0u, 3) {
+        player.gain(state, Gold);
+    }
+}
+
+pub fn dominate(player: &mut PlayerHandle, state: &mut GameState) {
+    if !player.gain(state, Province).is_err() {
+        player.take_vp_tokens(9);
+    }
+}
+
+/// Triumph gives a VP for each card gained this turn, counting its Estate.
+pub fn triumph(player: &mut PlayerHandle, state: &mut GameState) {
+    if !player.gain(state, Estate).is_err() {
+        let gains = player.turn_gains;
+        player.take_vp_tokens(gains);
+    }
+}
+
+/// Annex shuffles all but up to 5 cards of the discard pile into the deck.
+pub fn annex(player: &mut PlayerHandle, state: &mut GameState) {
+    let mut rest = player.get_discard();
+    let kept = player.decide(state, Decision::cards(Annex, Discarding, rest.clone(), 0, 5)).cards();
+    for card in kept.iter() {
+        if let Some(i) = rest.iter().position(|x| x == card) {
+            rest.remove(i);
+        }
+    }
+    for card in rest.into_iter() {
+        player.move_card(card, DiscardPile, Deck);
+    }
+    player.shuffle_deck();
+    player.gain(state, Duchy);
+}
+
+pub fn bandit_fort(cards: &[Card]) -> int {
+    -2 * (copies(cards, Silver) + copies(cards, Gold)) as int
+}
+
+pub fn fountain(cards: &[Card]) -> int {
+    if copies(cards, Copper) >= 10 { 15 } else { 0 }
+}
+
+/// Keep is worth 5 VP for each differently named Treasure the player has
+/// at least as many of as every other player.
+pub fn keep(sheet: &ScoreSheet) -> int {
+    let treasures = tally(sheet.cards);
+    let kept = treasures.iter()
+        .filter(|&(card, n)| card.is_money() && sheet.others.iter().all(|x| copies(x.as_slice(), *card) <= *n))
+        .count();
+    5 * kept as int
+}
+
+pub fn museum(cards: &[Card]) -> int {
+    2 * differently_named(cards) as int
+}
+
+/// Orchard is worth 4 VP for each Action card the player has 3 or more
+/// copies of.
+pub fn orchard(cards: &[Card]) -> int {
+    4 * tally(cards).iter().filter(|&(card, n)| card.is_action() && *n >= 3).count() as int
+}
+
+/// Palace is worth 3 VP for each set of Copper, Silver and Gold.
+pub fn palace(cards: &[Card]) -> int {
+    let sets = cmp::min(copies(cards, Copper), cmp::min(copies(cards, Silver), copies(cards, Gold)));
+    3 * sets as int
+}
+
+/// Tower is worth 1 VP for each non-Victory card from an empty supply pile.
+pub fn tower(sheet: &ScoreSheet) -> int {
+    sheet.cards.iter().filter(|x| !x.is_victory() && sheet.empty_piles.contains(*x)).count() as int
+}
+
+/// Triumphal Arch is worth 3 VP for each copy of the player's second most
+/// common Action card.
+pub fn triumphal_arch(cards: &[Card]) -> int {
+    let mut counts: Vec<uint> = tally(cards).into_iter().filter(|&(card, _)| card.is_action()).map(|(_, n)| n).collect();
+    counts.sort_by(|a, b| b.cmp(a));
+    if counts.len() > 1 { 3 * counts[1] as int } else { 0 }
+}
+
+/// Wall takes a VP for each card the player has after the first 15.
+pub fn wall(cards: &[Card]) -> int {
+    if cards.len() > 15 { 15 - cards.len() as int } else { 0 }
+}
+
+/// Wolf Den takes 3 VP for each card the player has only one copy of.
+pub fn wolf_den(cards: &[Card]) -> int {
+    -3 * tally(cards).values().filter(|x| **x == 1).count() as int
+}
+
+#[cfg(test)]
+#[path = "tests/empires.rs"]
+mod tests;
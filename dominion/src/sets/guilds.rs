@@ -0,0 +1,243 @@
+use std::collections::RingBuf;
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::attack;
+use super::super::response::*;
+
+/// The kingdom cards in this set.
+pub fn kingdom() -> Vec<Card> {
+    vec![CandlestickMaker, Stonemason, Doctor, Masterpiece, Advisor, Plaza, Taxman, Herald, Baker,
+         Butcher, Journeyman, MerchantGuild, Soothsayer]
+}
+
+/// Ask the player to name a card, for Doctor and Journeyman. Any card in
+/// the supply can be named.
+pub fn name_card(card: Card, player: &mut PlayerHandle, state: &GameState) -> Option<Card> {
+    let mut names: Vec<Card> = state.kingdom.keys().map(|x| *x).collect();
+    names.sort_by(|a, b| a.name().cmp(b.name()));
+    player.decide(state, Decision::cards(card, Naming, names, 1, 1)).cards().pop()
+}
+
+/// Ask how many of their Coffers the player wants to spend, for Butcher.
+fn choose_coffers(card: Card, player: &mut PlayerHandle, state: &GameState) -> uint {
+    let options: Vec<String> = range(0, player.coffers + 1).map(|x| format!("Spend {} Coffers", x)).collect();
+    player.decide(state, Decision::option(card, options.iter().map(|x| x.as_slice()).collect())).option().unwrap_or(0)
+}
+
+pub fn candlestick_maker(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    player.buys += 1;
+    player.coffers += 1;
+    NoProblem
+}
+
+pub fn stonemason(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Stonemason, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    // Nothing costs less than a card that costs nothing.
+    let cost = state.cost(to_trash);
+    if cost.coins == 0 {
+        return NoProblem;
+    }
+    for _ in range(0u, 2) {
+        if let Some(card) = player.decide(state, Decision::pile(Stonemason, Gaining, cost.minus(1))).pile() {
+            player.gain(state, card);
+        }
+    }
+    NoProblem
+}
+
+/// Overpaying for Stonemason gains two Action cards that each cost exactly
+/// the amount overpaid.
+pub fn stonemason_overpaid(player: &mut PlayerHandle, state: &mut GameState, amount: uint) {
+    let cost = Cost::coins(amount);
+    for _ in range(0u, 2) {
+        if let Some(card) = player.decide(state, Decision::pile_between(Stonemason, Gaining, cost, cost).only(Action)).pile() {
+            player.gain(state, card);
+        }
+    }
+}
+
+pub fn doctor(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let named = name_card(Doctor, player, state);
+    let revealed: Vec<Card> = range(0u, 3).filter_map(|_| player.reveal()).collect();
+    let (matching, rest) = revealed.partition(|x| Some(*x) == named);
+    for card in matching.into_iter() {
+        player.trash_from(state, card, SetAside);
+    }
+    let order = player.decide(state, Decision::order(Doctor, TopDecking, rest)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+    NoProblem
+}
+
+/// For each $1 overpaid for Doctor, the player looks at the top card of
+/// their deck and trashes it, discards it or puts it back.
+pub fn doctor_overpaid(player: &mut PlayerHandle, state: &mut GameState, amount: uint) {
+    for _ in range(0, amount) {
+        let card = match player.reveal() {
+            Some(card) => card,
+            None => break,
+        };
+        match player.decide(state, Decision::option(Doctor, vec!["Put it back", "Discard it", "Trash it"])).option() {
+            Some(2) => { player.trash_from(state, card, SetAside); },
+            Some(1) => { player.move_card(card, SetAside, DiscardPile); },
+            _ => { player.move_card(card, SetAside, Deck); },
+        }
+    }
+}
+
+/// Overpaying for Masterpiece gains a Silver for each $1.
+pub fn masterpiece_overpaid(player: &mut PlayerHandle, state: &mut GameState, amount: uint) {
+    for _ in range(0, amount) {
+        player.gain(state, Silver);
+    }
+}
+
+pub fn advisor(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.actions += 1;
+    let revealed: Vec<Card> = range(0u, 3).filter_map(|_| player.reveal()).collect();
+    // The player to the left picks the card to discard.
+    let discarded = match opponents.front_mut() {
+        Some(left) => left.decide(state, Decision::cards(Advisor, Discarding, revealed, 1, 1)).cards().pop(),
+        None => None,
+    };
+    if let Some(card) = discarded {
+        player.move_card(card, SetAside, DiscardPile);
+    }
+    for card in player.set_aside.clone().into_iter() {
+        player.move_card(card, SetAside, Hand);
+    }
+    NoProblem
+}
+
+pub fn plaza(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 2;
+    let money: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Plaza, Discarding, money, 0, 1)).cards().pop() {
+        player.discard(card);
+        player.coffers += 1;
+    }
+    NoProblem
+}
+
+pub fn taxman<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    let money: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    let to_trash = match player.decide(state, Decision::cards(Taxman, Trashing, money, 0, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    attack(Taxman, state, opponents, |opponent, _| {
+        if opponent.hand.len() >= 5 && opponent.has_in_hand(to_trash) {
+            opponent.discard(to_trash);
+        }
+    });
+    match player.decide(state, Decision::pile(Taxman, Gaining, state.cost(to_trash).plus(3)).only(Money)).pile() {
+        Some(card) => player.gain_to_deck(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn herald(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.draw();
+    player.actions += 1;
+    match player.reveal() {
+        Some(card) if card.is_action() => {
+            player.move_card(card, SetAside, InPlay);
+            card.play_effects(player, state, opponents)
+        },
+        Some(card) => {
+            player.move_card(card, SetAside, Deck);
+            NoProblem
+        },
+        None => NoProblem,
+    }
+}
+
+/// For each $1 overpaid for Herald, a card from the player's discard pile
+/// goes on top of their deck.
+pub fn herald_overpaid(player: &mut PlayerHandle, state: &mut GameState, amount: uint) {
+    let discard = player.get_discard();
+    let chosen = player.decide(state, Decision::cards(Herald, TopDecking, discard, amount, amount)).cards();
+    for card in chosen.iter().rev() {
+        player.move_card(*card, DiscardPile, Deck);
+    }
+}
+
+pub fn baker(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.coffers += 1;
+    NoProblem
+}
+
+pub fn butcher(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.coffers += 2;
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Butcher, Trashing, hand, 0, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    // Coffers spent here make the card gained more expensive.
+    let spent = choose_coffers(Butcher, player, state);
+    player.coffers -= spent;
+    match player.decide(state, Decision::pile(Butcher, Gaining, state.cost(to_trash).plus(spent))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn journeyman(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let named = name_card(Journeyman, player, state);
+    let mut found = 0u;
+    while found < 3 {
+        match player.reveal() {
+            Some(card) if Some(card) != named => {
+                player.move_card(card, SetAside, Hand);
+                found += 1;
+            },
+            Some(_) => (),
+            None => break,
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn merchant_guild(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+/// Each Merchant Guild in play is worth a Coffers for every card bought.
+pub fn merchant_guild_buy(player: &mut PlayerHandle) {
+    player.coffers += 1;
+}
+
+pub fn soothsayer<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.gain(state, Gold);
+    attack(Soothsayer, state, opponents, |opponent, state| {
+        // Only those who actually gained a Curse draw a card.
+        if !opponent.gain(state, Curse).is_err() {
+            opponent.draw();
+        }
+    });
+    NoProblem
+}
+
+#[cfg(test)]
+#[path = "tests/guilds.rs"]
+mod tests;
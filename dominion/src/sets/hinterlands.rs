@@ -0,0 +1,407 @@
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::{attack, Blocked, NotBlocked, Outcome};
+use super::super::response::*;
+use super::dominion::discard_down_to;
+
+/// The kingdom cards in this set. Tunnel and Noble Brigand aren't
+/// available yet.
+pub fn kingdom() -> Vec<Card> {
+    vec![Crossroads, Duchess, FoolsGold, Develop, Oasis, Oracle, Scheme, JackOfAllTrades, NomadCamp,
+         SilkRoad, SpiceMerchant, Trader, Cache, Cartographer, Embassy, Haggler, Highway,
+         IllGottenGains, Inn, Mandarin, Margrave, Stables, BorderVillage, Farmland]
+}
+
+/// Returns true for cards that are only worth points, or worth less than
+/// nothing, which players would rather not draw.
+fn is_junk(card: Card) -> bool {
+    (card.is_victory() || card.is_curse()) && !card.is_action() && !card.is_money()
+}
+
+/// Whether this is the first time the player has played `card` this turn.
+fn first_played(player: &PlayerHandle, card: Card) -> bool {
+    player.played.iter().filter(|x| **x == card).count() == 1
+}
+
+/// Ask the player to gain a card costing exactly `cost`, e.g. for Develop.
+/// Returns the card gained.
+fn gain_costing(card: Card, player: &mut PlayerHandle, state: &mut GameState, cost: Cost, to: Zone) -> Option<Card> {
+    let gained = player.decide(state, Decision::pile_between(card, Gaining, cost, cost)).pile();
+    if let Some(gained) = gained {
+        match to {
+            Deck => player.gain_to_deck(state, gained),
+            _ => player.gain(state, gained),
+        };
+    }
+    gained
+}
+
+pub fn crossroads(player: &mut PlayerHandle) -> Response {
+    for card in player.hand.clone().into_iter() {
+        player.record(Revealed{player: player.id, card: card});
+    }
+    let victory = player.hand.iter().filter(|x| x.is_victory()).count();
+    player.draw_n(victory);
+    // Only the first Crossroads each turn gives actions, even when it's
+    // played again by Throne Room.
+    if first_played(player, Crossroads) {
+        player.actions += 3;
+    }
+    NoProblem
+}
+
+pub fn duchess<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, mut opponents: T) -> Response {
+    player.buying_power += 2;
+    // It isn't an attack, so everyone just looks at their own top card.
+    duchess_look(player, state);
+    for opponent in opponents {
+        duchess_look(opponent, state);
+    }
+    NoProblem
+}
+
+fn duchess_look(player: &mut PlayerHandle, state: &mut GameState) {
+    if let Some(card) = player.reveal() {
+        let discarding = player.decide(state, Decision::yes_no(Duchess, Discarding, Some(card), is_junk(card))).is_yes();
+        player.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+    }
+}
+
+/// Gaining a Duchy comes with a Duchess, if the player wants one.
+pub fn duchy_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    if !state.can_take(Duchess) {
+        return;
+    }
+    if player.decide(state, Decision::yes_no(Duchess, Gaining, Some(Duchess), true)).is_yes() {
+        player.gain(state, Duchess);
+    }
+}
+
+/// Fool's Gold is worth $1, or $4 if another Fool's Gold was already played
+/// this turn.
+pub fn fools_gold(player: &mut PlayerHandle) -> Response {
+    player.buying_power += if first_played(player, FoolsGold) { 1 } else { 4 };
+    NoProblem
+}
+
+/// When someone else gains a Province, Fool's Gold can be trashed from hand
+/// for a Gold on top of the player's deck.
+pub fn fools_gold_react(player: &mut PlayerHandle, state: &mut GameState) -> Outcome {
+    player.trash(state, FoolsGold);
+    player.gain_to_deck(state, Gold);
+    NotBlocked
+}
+
+pub fn develop(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Develop, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    let cost = state.cost(to_trash);
+    gain_costing(Develop, player, state, cost.plus(1), Deck);
+    if cost.coins > 0 {
+        gain_costing(Develop, player, state, cost.minus(1), Deck);
+    }
+    NoProblem
+}
+
+pub fn oasis(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buying_power += 1;
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Oasis, Discarding, hand, 1, 1)).cards().pop() {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+/// Put the cards a player revealed for Oracle back on their deck, in the
+/// order they choose.
+fn oracle_put_back(player: &mut PlayerHandle, state: &mut GameState) {
+    let revealed = player.set_aside.clone();
+    let order = player.decide(state, Decision::order(Oracle, TopDecking, revealed)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+}
+
+pub fn oracle<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    // The player discards their own junk, and everyone else's good cards.
+    let revealed: Vec<Card> = range(0u, 2).filter_map(|_| player.reveal()).collect();
+    if !revealed.is_empty() {
+        let all_junk = revealed.iter().all(|x| is_junk(*x));
+        if player.decide(state, Decision::yes_no(Oracle, Discarding, None, all_junk)).is_yes() {
+            player.discard_all(SetAside);
+        } else {
+            oracle_put_back(player, state);
+        }
+    }
+    attack(Oracle, state, opponents, |opponent, state| {
+        let revealed: Vec<Card> = range(0u, 2).filter_map(|_| opponent.reveal()).collect();
+        if revealed.is_empty() {
+            return;
+        }
+        let all_junk = revealed.iter().all(|x| is_junk(*x));
+        if player.decide(state, Decision::yes_no(Oracle, Discarding, None, !all_junk)).is_yes() {
+            opponent.discard_all(SetAside);
+        } else {
+            oracle_put_back(opponent, state);
+        }
+    });
+    player.draw_n(2);
+    NoProblem
+}
+
+pub fn scheme(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    NoProblem
+}
+
+/// When Scheme is discarded from play, one of the player's Action cards in
+/// play may go on top of their deck instead.
+pub fn scheme_discarded(player: &mut PlayerHandle, state: &mut GameState) {
+    let actions: Vec<Card> = player.in_play.iter().filter_map(|x| if x.is_action() { Some(*x) } else { None }).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Scheme, TopDecking, actions, 0, 1)).cards().pop() {
+        player.move_card(card, InPlay, Deck);
+    }
+}
+
+pub fn jack_of_all_trades(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.gain(state, Silver);
+    if let Some(card) = player.reveal() {
+        let discarding = player.decide(state, Decision::yes_no(JackOfAllTrades, Discarding, Some(card), is_junk(card))).is_yes();
+        player.move_card(card, SetAside, if discarding { DiscardPile } else { Deck });
+    }
+    while player.hand.len() < 5 && player.can_draw() {
+        player.draw();
+    }
+    let non_treasures: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { None } else { Some(*x) }).collect();
+    if let Some(card) = player.decide(state, Decision::cards(JackOfAllTrades, Trashing, non_treasures, 0, 1)).cards().pop() {
+        player.trash(state, card);
+    }
+    NoProblem
+}
+
+pub fn nomad_camp(player: &mut PlayerHandle) -> Response {
+    player.buys += 1;
+    player.buying_power += 2;
+    NoProblem
+}
+
+/// Nomad Camp goes on top of the player's deck when it's gained, unless a
+/// Reaction like Watchtower has moved it already.
+pub fn nomad_camp_gained(player: &mut PlayerHandle, to: Option<Zone>) {
+    match to {
+        Some(Deck) | None => (),
+        Some(zone) => { player.move_card(NomadCamp, zone, Deck); },
+    }
+}
+
+pub fn spice_merchant(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let money: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    let to_trash = match player.decide(state, Decision::cards(SpiceMerchant, Trashing, money, 0, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    match player.decide(state, Decision::option(SpiceMerchant, vec!["+2 Cards and +1 Action", "+$2 and +1 Buy"])).option() {
+        Some(1) => {
+            player.buying_power += 2;
+            player.buys += 1;
+        },
+        _ => {
+            player.draw_n(2);
+            player.actions += 1;
+        },
+    }
+    NoProblem
+}
+
+pub fn trader(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Trader, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    for _ in range(0, state.cost(to_trash).coins) {
+        player.gain(state, Silver);
+    }
+    NoProblem
+}
+
+/// Revealing Trader when the player would gain a card gains a Silver
+/// instead.
+pub fn trader_react(player: &mut PlayerHandle, state: &mut GameState) -> Outcome {
+    player.gain(state, Silver);
+    Blocked
+}
+
+/// Gaining a Cache comes with two Coppers.
+pub fn cache_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    player.gain(state, Copper);
+    player.gain(state, Copper);
+}
+
+pub fn cartographer(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    let revealed: Vec<Card> = range(0u, 4).filter_map(|_| player.reveal()).collect();
+    for card in player.decide(state, Decision::cards(Cartographer, Discarding, revealed, 0, 4)).cards().into_iter() {
+        player.move_card(card, SetAside, DiscardPile);
+    }
+    let rest = player.set_aside.clone();
+    let order = player.decide(state, Decision::order(Cartographer, TopDecking, rest)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+    NoProblem
+}
+
+pub fn embassy(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw_n(5);
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Embassy, Discarding, hand, 3, 3)).cards().into_iter() {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+pub fn haggler(player: &mut PlayerHandle) -> Response {
+    player.buying_power += 2;
+    NoProblem
+}
+
+/// Each Haggler in play gains a cheaper card that isn't a Victory card
+/// whenever something is bought.
+pub fn haggler_buy(player: &mut PlayerHandle, state: &mut GameState, bought: Card) {
+    let cost = state.cost(bought);
+    let mut options: Vec<Card> = state.kingdom.keys()
+        .filter(|x| !x.is_victory() && state.can_take(**x) && state.cost(**x) < cost)
+        .map(|x| *x)
+        .collect();
+    // The most expensive comes first, for the default.
+    options.sort_by(|a, b| (state.cost(*b).sort_key(), a.name()).cmp(&(state.cost(*a).sort_key(), b.name())));
+    if let Some(card) = player.decide(state, Decision::cards(Haggler, Gaining, options, 1, 1)).cards().pop() {
+        player.gain(state, card);
+    }
+}
+
+/// While Highway is in play, cards cost $1 less.
+pub fn highway(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    state.cost_reduction += 1;
+    NoProblem
+}
+
+pub fn ill_gotten_gains(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    if player.decide(state, Decision::yes_no(IllGottenGains, Gaining, Some(Copper), true)).is_yes() {
+        player.gain_to_hand(state, Copper);
+    }
+    NoProblem
+}
+
+pub fn inn(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw_n(2);
+    player.actions += 2;
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Inn, Discarding, hand, 2, 2)).cards().into_iter() {
+        player.discard(card);
+    }
+    NoProblem
+}
+
+/// Gaining an Inn shuffles any of the Actions in the player's discard pile
+/// back into their deck.
+pub fn inn_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    let actions: Vec<Card> = player.discard.iter().filter_map(|x| if x.is_action() { Some(*x) } else { None }).collect();
+    let n = actions.len();
+    let chosen = player.decide(state, Decision::cards(Inn, Revealing, actions, 0, n)).cards();
+    if chosen.is_empty() {
+        return;
+    }
+    for card in chosen.into_iter() {
+        player.record(Revealed{player: player.id, card: card});
+        player.move_card(card, DiscardPile, Deck);
+    }
+    player.shuffle_deck();
+}
+
+pub fn mandarin(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buying_power += 3;
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Mandarin, TopDecking, hand, 1, 1)).cards().pop() {
+        player.move_card(card, Hand, Deck);
+    }
+    NoProblem
+}
+
+/// Gaining a Mandarin puts every Treasure the player has in play back on
+/// top of their deck.
+pub fn mandarin_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    let money: Vec<Card> = player.in_play.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    let order = player.decide(state, Decision::order(Mandarin, TopDecking, money)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, InPlay, Deck);
+    }
+}
+
+pub fn margrave<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(3);
+    player.buys += 1;
+    attack(Margrave, state, opponents, |opponent, state| {
+        opponent.draw();
+        discard_down_to(Margrave, opponent, state, 3);
+    });
+    NoProblem
+}
+
+pub fn stables(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let money: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Stables, Discarding, money, 0, 1)).cards().pop() {
+        player.discard(card);
+        player.draw_n(3);
+        player.actions += 1;
+    }
+    NoProblem
+}
+
+pub fn border_village(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 2;
+    NoProblem
+}
+
+/// Gaining a Border Village comes with a card that costs less.
+pub fn border_village_gained(player: &mut PlayerHandle, state: &mut GameState) {
+    let cost = state.cost(BorderVillage);
+    if cost.coins == 0 {
+        return;
+    }
+    if let Some(card) = player.decide(state, Decision::pile(BorderVillage, Gaining, cost.minus(1))).pile() {
+        player.gain(state, card);
+    }
+}
+
+/// Buying a Farmland trashes a card from the player's hand, and gains one
+/// costing exactly $2 more.
+pub fn farmland_bought(player: &mut PlayerHandle, state: &mut GameState) {
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Farmland, Trashing, hand, 1, 1)).cards().pop() {
+        player.trash(state, card);
+        let cost = state.cost(card).plus(2);
+        gain_costing(Farmland, player, state, cost, DiscardPile);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/hinterlands.rs"]
+mod tests;
@@ -0,0 +1,378 @@
+use std::collections::RingBuf;
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::{attack, NotBlocked, Outcome};
+use super::super::response::*;
+
+/// The kingdom cards in this set.
+pub fn kingdom() -> Vec<Card> {
+    vec![Courtyard, Pawn, SecretChamber, GreatHall, Masquerade, ShantyTown, Steward, Swindler,
+         WishingWell, Baron, Bridge, Conspirator, Coppersmith, Ironworks, MiningVillage, Scout,
+         Duke, Minion, Saboteur, Torturer, TradingPost, Tribute, Upgrade, Harem, Nobles]
+}
+
+pub fn courtyard(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw_n(3);
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Courtyard, TopDecking, hand, 1, 1)).cards().into_iter() {
+        player.move_card(card, Hand, Deck);
+    }
+    NoProblem
+}
+
+static PAWN_OPTIONS: [&'static str, ..4] = ["+1 Card", "+1 Action", "+1 Buy", "+$1"];
+
+pub fn pawn(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    // Two different options, so whatever's picked first isn't offered again.
+    let mut left: Vec<uint> = range(0u, PAWN_OPTIONS.len()).collect();
+    for _ in range(0u, 2) {
+        let options = left.iter().map(|i| PAWN_OPTIONS[*i]).collect();
+        let picked = player.decide(state, Decision::option(Pawn, options)).option().unwrap_or(0);
+        match left.remove(picked).unwrap() {
+            0 => { player.draw(); },
+            1 => player.actions += 1,
+            2 => player.buys += 1,
+            _ => player.buying_power += 1,
+        }
+    }
+    NoProblem
+}
+
+pub fn secret_chamber(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let n = hand.len();
+    let to_discard = player.decide(state, Decision::cards(SecretChamber, Discarding, hand, 0, n)).cards();
+    for card in to_discard.iter() {
+        player.discard(*card);
+    }
+    player.buying_power += to_discard.len();
+    NoProblem
+}
+
+/// Secret Chamber's reaction: draw 2 cards, then put 2 cards from your hand
+/// back on your deck. It doesn't stop the attack.
+pub fn secret_chamber_react(player: &mut PlayerHandle, state: &mut GameState) -> Outcome {
+    player.draw_n(2);
+    let hand = player.get_hand();
+    let to_return = player.decide(state, Decision::cards(SecretChamber, TopDecking, hand, 2, 2)).cards();
+    let order = player.decide(state, Decision::order(SecretChamber, TopDecking, to_return)).cards();
+    // The first card chosen ends up on top, so it goes back last.
+    for card in order.iter().rev() {
+        player.move_card(*card, Hand, Deck);
+    }
+    NotBlocked
+}
+
+pub fn great_hall(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    NoProblem
+}
+
+pub fn masquerade(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.draw_n(2);
+    // Everyone chooses what to pass before anyone receives anything.
+    let mut passes = vec![choose_pass(player, state)];
+    for opponent in opponents.iter_mut() {
+        passes.push(choose_pass(opponent, state));
+    }
+    // Cards go to the next player in turn order, so the last opponent
+    // passes to the player who played Masquerade.
+    receive_pass(player, passes.pop().unwrap());
+    for (opponent, pass) in opponents.iter_mut().zip(passes.into_iter()) {
+        receive_pass(opponent, pass);
+    }
+
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Masquerade, Trashing, hand, 0, 1)).cards().into_iter() {
+        player.trash(state, card);
+    }
+    NoProblem
+}
+
+/// Take the card a player chooses to pass for Masquerade out of their hand,
+/// along with who's passing it.
+fn choose_pass(player: &mut PlayerHandle, state: &GameState) -> Option<(uint, Card)> {
+    let hand = player.get_hand();
+    let card = player.decide(state, Decision::cards(Masquerade, Passing, hand, 1, 1)).cards().pop();
+    card.map(|card| {
+        player.take(Hand, card);
+        (player.id, card)
+    })
+}
+
+fn receive_pass(player: &mut PlayerHandle, pass: Option<(uint, Card)>) {
+    if let Some((from, card)) = pass {
+        player.put(Hand, card);
+        player.record(Passed{player: from, card: card, to: player.id});
+    }
+}
+
+pub fn shanty_town(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    for card in player.get_hand().into_iter() {
+        player.record(Revealed{player: player.id, card: card});
+    }
+    if !player.hand.iter().any(|x| x.is_action()) {
+        player.draw_n(2);
+    }
+    NoProblem
+}
+
+pub fn steward(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    match player.decide(state, Decision::option(Steward, vec!["+2 Cards", "+$2", "Trash 2 cards"])).option() {
+        Some(1) => player.buying_power += 2,
+        Some(2) => {
+            let hand = player.get_hand();
+            for card in player.decide(state, Decision::cards(Steward, Trashing, hand, 2, 2)).cards().into_iter() {
+                player.trash(state, card);
+            }
+        },
+        _ => player.draw_n(2),
+    }
+    NoProblem
+}
+
+pub fn swindler<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 2;
+    attack(Swindler, state, opponents, |opponent, state| {
+        if let Some(card) = opponent.reveal() {
+            opponent.trash_from(state, card, SetAside);
+            // The attacker picks the replacement, which costs the same.
+            let cost = state.cost(card);
+            if let Some(replacement) = player.decide(state, Decision::pile_between(Swindler, Gaining, cost, cost)).pile() {
+                opponent.gain(state, replacement);
+            }
+        }
+    });
+    NoProblem
+}
+
+pub fn wishing_well(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    // Any card can be named, but only cards in the supply are offered.
+    let mut names: Vec<Card> = state.kingdom.keys().map(|x| *x).collect();
+    names.sort_by(|a, b| a.name().cmp(b.name()));
+    let named = player.decide(state, Decision::cards(WishingWell, Naming, names, 1, 1)).cards().pop();
+    if let Some(card) = player.reveal() {
+        let to = if Some(card) == named { Hand } else { Deck };
+        player.move_card(card, SetAside, to);
+    }
+    NoProblem
+}
+
+pub fn baron(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buys += 1;
+    if player.has_in_hand(Estate) && player.decide(state, Decision::yes_no(Baron, Discarding, Some(Estate), true)).is_yes() {
+        player.discard(Estate);
+        player.buying_power += 4;
+    } else {
+        // Nothing happens if the Estates have run out.
+        player.gain(state, Estate);
+    }
+    NoProblem
+}
+
+pub fn bridge(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buys += 1;
+    player.buying_power += 1;
+    state.cost_reduction += 1;
+    NoProblem
+}
+
+pub fn conspirator(player: &mut PlayerHandle) -> Response {
+    player.buying_power += 2;
+    // Conspirator itself is one of the three.
+    if player.actions_played >= 3 {
+        player.draw();
+        player.actions += 1;
+    }
+    NoProblem
+}
+
+pub fn coppersmith(player: &mut PlayerHandle) -> Response {
+    player.copper_bonus += 1;
+    NoProblem
+}
+
+pub fn ironworks(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let card = match player.decide(state, Decision::pile(Ironworks, Gaining, Cost::coins(4))).pile() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    let resp = player.gain(state, card);
+    if resp.is_err() {
+        return resp;
+    }
+    if card.is_action() {
+        player.actions += 1;
+    }
+    if card.is_money() {
+        player.buying_power += 1;
+    }
+    if card.is_victory() {
+        player.draw();
+    }
+    NoProblem
+}
+
+pub fn mining_village(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 2;
+    // When it's played twice, it can only be trashed the first time.
+    if player.decide(state, Decision::yes_no(MiningVillage, Trashing, Some(MiningVillage), false)).is_yes() {
+        if player.trash_from(state, MiningVillage, InPlay) {
+            player.buying_power += 2;
+        }
+    }
+    NoProblem
+}
+
+pub fn scout(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 1;
+    let revealed: Vec<Card> = range(0u, 4).filter_map(|_| player.reveal()).collect();
+    let (victory, rest) = revealed.partition(|x| x.is_victory());
+    for card in victory.into_iter() {
+        player.move_card(card, SetAside, Hand);
+    }
+    let order = player.decide(state, Decision::order(Scout, TopDecking, rest)).cards();
+    for card in order.iter().rev() {
+        player.move_card(*card, SetAside, Deck);
+    }
+    NoProblem
+}
+
+pub fn minion<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.actions += 1;
+    let discarding = player.decide(state, Decision::option(Minion, vec!["+$2", "Discard your hand, +4 Cards"])).option() == Some(1);
+    if discarding {
+        player.discard_hand();
+        player.draw_n(4);
+    } else {
+        player.buying_power += 2;
+    }
+    attack(Minion, state, opponents, |opponent, _| {
+        if discarding && opponent.hand.len() >= 5 {
+            opponent.discard_hand();
+            opponent.draw_n(4);
+        }
+    });
+    NoProblem
+}
+
+pub fn saboteur<'a, T: Iterator<&'a mut PlayerHandle>>(state: &mut GameState, opponents: T) -> Response {
+    attack(Saboteur, state, opponents, |opponent, state| {
+        loop {
+            match opponent.reveal() {
+                Some(card) if state.cost(card).coins >= 3 => {
+                    opponent.trash_from(state, card, SetAside);
+                    // The replacement is optional, and costs at least $2 less.
+                    if opponent.decide(state, Decision::yes_no(Saboteur, Gaining, None, true)).is_yes() {
+                        let max_cost = state.cost(card).minus(2);
+                        if let Some(replacement) = opponent.decide(state, Decision::pile(Saboteur, Gaining, max_cost)).pile() {
+                            opponent.gain(state, replacement);
+                        }
+                    }
+                    break;
+                },
+                Some(_) => (),
+                None => break,
+            }
+        }
+        opponent.discard_all(SetAside);
+    });
+    NoProblem
+}
+
+pub fn torturer<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(3);
+    attack(Torturer, state, opponents, |opponent, state| {
+        match opponent.decide(state, Decision::option(Torturer, vec!["Discard 2 cards", "Gain a Curse in hand"])).option() {
+            Some(1) => { opponent.gain_to_hand(state, Curse); },
+            _ => {
+                let hand = opponent.get_hand();
+                for card in opponent.decide(state, Decision::cards(Torturer, Discarding, hand, 2, 2)).cards().into_iter() {
+                    opponent.discard(card);
+                }
+            },
+        }
+    });
+    NoProblem
+}
+
+pub fn trading_post(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let to_trash = player.decide(state, Decision::cards(TradingPost, Trashing, hand, 2, 2)).cards();
+    for card in to_trash.iter() {
+        player.trash(state, *card);
+    }
+    // Trashing only one card gets nothing.
+    if to_trash.len() == 2 {
+        player.gain_to_hand(state, Silver);
+    }
+    NoProblem
+}
+
+pub fn tribute(player: &mut PlayerHandle, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    // The player to the left is the next one to take a turn.
+    let revealed: Vec<Card> = match opponents.front_mut() {
+        Some(left) => {
+            let revealed = range(0u, 2).filter_map(|_| left.reveal()).collect();
+            left.discard_all(SetAside);
+            revealed
+        },
+        None => return NoProblem,
+    };
+    // Two copies of the same card only count once, but a card with two
+    // types gives both bonuses.
+    let mut seen = Vec::new();
+    for card in revealed.into_iter() {
+        if seen.contains(&card) {
+            continue;
+        }
+        seen.push(card);
+        if card.is_action() {
+            player.actions += 2;
+        }
+        if card.is_money() {
+            player.buying_power += 2;
+        }
+        if card.is_victory() {
+            player.draw_n(2);
+        }
+    }
+    NoProblem
+}
+
+pub fn upgrade(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 1;
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Upgrade, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    let cost = state.cost(to_trash).plus(1);
+    match player.decide(state, Decision::pile_between(Upgrade, Gaining, cost, cost)).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+pub fn nobles(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    match player.decide(state, Decision::option(Nobles, vec!["+3 Cards", "+2 Actions"])).option() {
+        Some(1) => player.actions += 2,
+        _ => player.draw_n(3),
+    }
+    NoProblem
+}
+
+#[cfg(test)]
+#[path = "tests/intrigue.rs"]
+mod tests;
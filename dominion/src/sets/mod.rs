@@ -1 +1,141 @@
+use card::Card;
+
+pub mod adventures;
+pub mod alchemy;
+pub mod cornucopia;
+pub mod dark_ages;
 pub mod dominion;
+pub mod empires;
+pub mod guilds;
+pub mod hinterlands;
+pub mod intrigue;
+pub mod prosperity;
+pub mod seaside;
+
+/// The card sets that kingdom cards can be chosen from.
+#[deriving(Clone, Show, PartialEq)]
+pub enum Set {
+    Dominion,
+    Intrigue,
+    Seaside,
+    Prosperity,
+    Alchemy,
+    Cornucopia,
+    Guilds,
+    Hinterlands,
+    DarkAges,
+    Adventures,
+    Empires,
+}
+
+impl Set {
+    /// All of the kingdom cards in this set.
+    pub fn kingdom(&self) -> Vec<Card> {
+        match *self {
+            Dominion => dominion::kingdom(),
+            Intrigue => intrigue::kingdom(),
+            Seaside => seaside::kingdom(),
+            Prosperity => prosperity::kingdom(),
+            Alchemy => alchemy::kingdom(),
+            Cornucopia => cornucopia::kingdom(),
+            Guilds => guilds::kingdom(),
+            Hinterlands => hinterlands::kingdom(),
+            DarkAges => dark_ages::kingdom(),
+            Adventures => adventures::kingdom(),
+            Empires => empires::kingdom(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::cell::RefCell;
+    use std::collections::RingBuf;
+    use std::default::Default;
+    use std::rc::Rc;
+
+    use super::super::{GameState, PlayerHandle};
+    use super::super::card::*;
+    use super::super::decision;
+    use super::super::decision::{Choice, Decision};
+    use super::super::notify;
+    use super::super::response::Response;
+    use super::super::strategy::{Strategy, Turn};
+
+    /// Set up a player whose turn it is, holding `hand` with `deck` left to
+    /// draw from.
+    pub fn player(hand: Vec<Card>, deck: Vec<Card>) -> PlayerHandle {
+        let (mut player, _) = PlayerHandle::new();
+        player.hand = hand;
+        player.deck = deck;
+        player.actions = 1;
+        player.buys = 1;
+        player
+    }
+
+    /// Set up an opponent. Its connection is handed to a bot that leaves
+    /// every decision up to the game, so attacks fall back on their
+    /// defaults.
+    pub fn opponent(hand: Vec<Card>, deck: Vec<Card>) -> PlayerHandle {
+        let (mut opponent, conn) = PlayerHandle::new();
+        opponent.hand = hand;
+        opponent.deck = deck;
+        spawn(proc() {
+            loop {
+                match conn.recv_notification() {
+                    notify::GameOver => break,
+                    _ => conn.not_implemented(),
+                }
+            }
+        });
+        opponent
+    }
+
+    /// A game with 10 of every card in the supply.
+    pub fn state() -> GameState {
+        let mut state: GameState = Default::default();
+        for card in vec![Copper, Silver, Gold, Estate, Duchy, Province, Curse, Gardens, Village, Smithy, Feast, Workshop].into_iter() {
+            state.kingdom.insert(card, 10);
+        }
+        state
+    }
+
+    /// Makes the choices it's given, in order, and then leaves the rest up
+    /// to the game.
+    pub struct Script {
+        choices: RefCell<Vec<Choice>>,
+    }
+
+    impl Strategy for Script {
+        fn choose_buy(&self, _: &Turn) -> Option<Card> {
+            None
+        }
+
+        fn decide(&self, _: &Decision) -> Choice {
+            self.choices.borrow_mut().remove(0).unwrap_or(decision::Undecided)
+        }
+    }
+
+    /// Play a card that doesn't need any more input.
+    pub fn play(card: Card, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+        card.play(player, state, opponents)
+    }
+
+    /// Answer whatever the player is asked with `choices`, e.g. to script
+    /// how an opponent responds to an attack.
+    pub fn script(player: &mut PlayerHandle, choices: Vec<Choice>) {
+        player.strategy = Some(Rc::new(box Script{choices: RefCell::new(choices)} as Box<Strategy + 'static>));
+    }
+
+    /// Play a card, answering whatever it asks with `choices`.
+    pub fn play_with(card: Card, player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>, choices: Vec<Choice>) -> Response {
+        script(player, choices);
+        card.play(player, state, opponents)
+    }
+
+    pub fn assert_ok(resp: Response) {
+        if resp.is_err() {
+            panic!("card play failed!");
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::RingBuf;
+
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::reaction::{attack, NotBlocked, Outcome};
+use super::super::response::*;
+use super::dominion::discard_down_to;
+
+/// The kingdom cards in this set. Trade Route, with its mat and tokens,
+/// isn't available yet.
+pub fn kingdom() -> Vec<Card> {
+    vec![Loan, Watchtower, Talisman, Quarry, WorkersVillage, Monument, Bishop, City, Contraband, CountingHouse,
+         Venture, Mint, Mountebank, Rabble, RoyalSeal, Vault, Bank, Hoard, Goons, GrandMarket, Expand, Forge,
+         KingsCourt, Peddler]
+}
+
+pub fn loan(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    if let Some(card) = reveal_treasure(player) {
+        // By default, only Coppers are trashed.
+        if player.decide(state, Decision::yes_no(Loan, Trashing, Some(card), card == Copper)).is_yes() {
+            player.trash_from(state, card, SetAside);
+        }
+    }
+    player.discard_all(SetAside);
+    NoProblem
+}
+
+pub fn watchtower(player: &mut PlayerHandle) -> Response {
+    while player.hand.len() < 6 && player.can_draw() {
+        player.draw();
+    }
+    NoProblem
+}
+
+/// Revealing Watchtower when a card is gained either trashes the card or
+/// puts it on top of the player's deck. By default, only Curses are
+/// trashed.
+pub fn watchtower_react(player: &mut PlayerHandle, state: &mut GameState, card: Card, zone: Zone) -> Outcome {
+    if player.decide(state, Decision::yes_no(Watchtower, Trashing, Some(card), card.is_curse())).is_yes() {
+        player.trash_from(state, card, zone);
+    } else if zone != Deck {
+        player.move_card(card, zone, Deck);
+    }
+    NotBlocked
+}
+
+pub fn quarry(state: &mut GameState) -> Response {
+    state.action_cost_reduction += 2;
+    NoProblem
+}
+
+pub fn monument(player: &mut PlayerHandle) -> Response {
+    player.buying_power += 2;
+    player.take_vp_tokens(1);
+    NoProblem
+}
+
+pub fn bishop<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, mut opponents: T) -> Response {
+    player.buying_power += 1;
+    player.take_vp_tokens(1);
+    let hand = player.get_hand();
+    if let Some(card) = player.decide(state, Decision::cards(Bishop, Trashing, hand, 1, 1)).cards().pop() {
+        player.trash(state, card);
+        player.take_vp_tokens(state.cost(card).coins / 2);
+    }
+    // Everyone else may trash a card too. It isn't an attack, so there's
+    // nothing to react to.
+    for opponent in opponents {
+        let hand = opponent.get_hand();
+        for card in opponent.decide(state, Decision::cards(Bishop, Trashing, hand, 0, 1)).cards().into_iter() {
+            opponent.trash(state, card);
+        }
+    }
+    NoProblem
+}
+
+pub fn contraband(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    player.buys += 1;
+    // The player to the left names the card.
+    let mut names: Vec<Card> = state.kingdom.keys().map(|x| *x).collect();
+    names.sort_by(|a, b| a.name().cmp(b.name()));
+    let named = match opponents.front_mut() {
+        Some(left) => left.decide(state, Decision::cards(Contraband, Naming, names, 1, 1)).cards().pop(),
+        None => None,
+    };
+    if let Some(card) = named {
+        player.contraband.push(card);
+    }
+    NoProblem
+}
+
+pub fn venture(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    let treasure = reveal_treasure(player);
+    if let Some(card) = treasure {
+        player.move_card(card, SetAside, InPlay);
+    }
+    player.discard_all(SetAside);
+    match treasure {
+        Some(card) => card.play_effects(player, state, opponents),
+        None => NoProblem,
+    }
+}
+
+/// Reveal cards from the top of the player's deck until a Treasure turns
+/// up, for Loan and Venture. Everything revealed is left set aside.
+fn reveal_treasure(player: &mut PlayerHandle) -> Option<Card> {
+    loop {
+        match player.reveal() {
+            Some(card) if card.is_money() => return Some(card),
+            Some(_) => (),
+            None => return None,
+        }
+    }
+}
+
+pub fn mint(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let money: Vec<Card> = player.hand.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    if let Some(card) = player.decide(state, Decision::cards(Mint, Revealing, money, 0, 1)).cards().pop() {
+        player.record(Revealed{player: player.id, card: card});
+        player.gain(state, card);
+    }
+    NoProblem
+}
+
+/// When Mint is bought, every Treasure the player has in play is trashed.
+pub fn mint_bought(player: &mut PlayerHandle, state: &mut GameState) {
+    let money: Vec<Card> = player.in_play.iter().filter_map(|x| if x.is_money() { Some(*x) } else { None }).collect();
+    for card in money.into_iter() {
+        player.trash_from(state, card, InPlay);
+    }
+}
+
+pub fn mountebank<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buying_power += 2;
+    attack(Mountebank, state, opponents, |opponent, state| {
+        if opponent.has_in_hand(Curse) && opponent.decide(state, Decision::yes_no(Mountebank, Discarding, Some(Curse), true)).is_yes() {
+            opponent.discard(Curse);
+        } else {
+            opponent.gain(state, Curse);
+            opponent.gain(state, Copper);
+        }
+    });
+    NoProblem
+}
+
+pub fn bank(player: &mut PlayerHandle) -> Response {
+    // Bank counts itself.
+    player.buying_power += player.in_play.iter().filter(|x| x.is_money()).count();
+    NoProblem
+}
+
+pub fn goons<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.buys += 1;
+    player.buying_power += 2;
+    attack(Goons, state, opponents, |opponent, state| discard_down_to(Goons, opponent, state, 3));
+    NoProblem
+}
+
+/// Each Goons in play is worth a VP token for every card bought.
+pub fn goons_buy(player: &mut PlayerHandle) {
+    player.take_vp_tokens(1);
+}
+
+pub fn peddler(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+pub fn workers_village(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 2;
+    player.buys += 1;
+    NoProblem
+}
+
+/// City gets better with one empty supply pile, and better again with
+/// two.
+pub fn city(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.draw();
+    player.actions += 2;
+    let empty = state.kingdom.values().filter(|x| **x == 0).count();
+    if empty >= 1 {
+        player.draw();
+    }
+    if empty >= 2 {
+        player.buys += 1;
+        player.buying_power += 1;
+    }
+    NoProblem
+}
+
+/// Counting House takes any number of Coppers from the discard pile into
+/// the player's hand. By default, it takes all of them.
+pub fn counting_house(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let coppers = player.discard.iter().filter(|x| **x == Copper).count();
+    let options: Vec<String> = range(0, coppers + 1).rev().map(|x| format!("Take {} Copper", x)).collect();
+    let taking = match player.decide(state, Decision::option(CountingHouse, options.iter().map(|x| x.as_slice()).collect())).option() {
+        Some(i) if i <= coppers => coppers - i,
+        _ => 0,
+    };
+    for _ in range(0, taking) {
+        player.record(Revealed{player: player.id, card: Copper});
+        player.move_card(Copper, DiscardPile, Hand);
+    }
+    NoProblem
+}
+
+/// Rabble makes everyone else discard the Actions and Treasures from the
+/// top three cards of their deck, and put the rest back in any order.
+pub fn rabble<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(3);
+    attack(Rabble, state, opponents, |opponent, state| {
+        let revealed: Vec<Card> = range(0u, 3).filter_map(|_| opponent.reveal()).collect();
+        let (discarded, rest) = revealed.partition(|x| x.is_action() || x.is_money());
+        for card in discarded.into_iter() {
+            opponent.move_card(card, SetAside, DiscardPile);
+        }
+        let order = opponent.decide(state, Decision::order(Rabble, TopDecking, rest)).cards();
+        for card in order.iter().rev() {
+            opponent.move_card(*card, SetAside, Deck);
+        }
+    });
+    NoProblem
+}
+
+/// Vault gives $1 for each card discarded. Everyone else may discard two
+/// cards to draw one; it isn't an attack.
+pub fn vault<'a, T: Iterator<&'a mut PlayerHandle>>(player: &mut PlayerHandle, state: &mut GameState, opponents: T) -> Response {
+    player.draw_n(2);
+    let hand = player.get_hand();
+    let n = hand.len();
+    for card in player.decide(state, Decision::cards(Vault, Discarding, hand, 0, n)).cards().into_iter() {
+        player.discard(card);
+        player.buying_power += 1;
+    }
+    for opponent in opponents {
+        let hand = opponent.get_hand();
+        let discarded = opponent.decide(state, Decision::cards(Vault, Discarding, hand, 0, 2)).cards();
+        for card in discarded.iter() {
+            opponent.discard(*card);
+        }
+        if discarded.len() == 2 {
+            opponent.draw();
+        }
+    }
+    NoProblem
+}
+
+/// Grand Market can't be bought with a Copper in play, which is checked
+/// when buying.
+pub fn grand_market(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.buys += 1;
+    player.buying_power += 2;
+    NoProblem
+}
+
+pub fn expand(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let to_trash = match player.decide(state, Decision::cards(Expand, Trashing, hand, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.trash(state, to_trash);
+    match player.decide(state, Decision::pile(Expand, Gaining, state.cost(to_trash).plus(3))).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+/// Forge gains a card costing exactly as many coins as the trashed cards
+/// cost between them.
+pub fn forge(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    let hand = player.get_hand();
+    let n = hand.len();
+    let mut total = 0;
+    for card in player.decide(state, Decision::cards(Forge, Trashing, hand, 0, n)).cards().into_iter() {
+        total += state.cost(card).coins;
+        player.trash(state, card);
+    }
+    let cost = Cost::coins(total);
+    match player.decide(state, Decision::pile_between(Forge, Gaining, cost, cost)).pile() {
+        Some(card) => player.gain(state, card),
+        None => NoProblem,
+    }
+}
+
+/// King's Court is like Throne Room, but it plays the card three times.
+pub fn kings_court(player: &mut PlayerHandle, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) -> Response {
+    let actions: Vec<Card> = player.hand.iter().filter(|x| x.is_action()).map(|x| *x).collect();
+    let to_repeat = match player.decide(state, Decision::cards(KingsCourt, Playing, actions, 1, 1)).cards().pop() {
+        Some(card) => card,
+        None => return NoProblem,
+    };
+    player.put_in_play(to_repeat);
+    let durations = player.durations.len();
+    let mut resp = NoProblem;
+    for _ in range(0u, 3) {
+        resp = to_repeat.play_in_place(player, state, opponents);
+        if resp.is_err() {
+            break;
+        }
+    }
+    player.stay_with_durations(KingsCourt, durations);
+    resp
+}
+
+/// Each Talisman in play gains a copy of anything bought that costs $4 or
+/// less, apart from Victory cards.
+pub fn talisman_buy(player: &mut PlayerHandle, state: &mut GameState, bought: Card) {
+    if !bought.is_victory() && state.cost(bought) <= Cost::coins(4) {
+        player.gain(state, bought);
+    }
+}
+
+/// Each Hoard in play gains a Gold whenever a Victory card is bought.
+pub fn hoard_buy(player: &mut PlayerHandle, state: &mut GameState, bought: Card) {
+    if bought.is_victory() {
+        player.gain(state, Gold);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/prosperity.rs"]
+mod tests;
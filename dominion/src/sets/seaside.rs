@@ -0,0 +1,142 @@
+use super::super::{GameState, Player, PlayerHandle};
+use super::super::card::*;
+use super::super::decision::*;
+use super::super::event::*;
+use super::super::response::*;
+
+/// The kingdom cards in this set. Haven, Pearl Diver, Ambassador, Lookout,
+/// Smugglers, Warehouse, Cutpurse, Navigator, Pirate Ship, Salvager, Sea Hag,
+/// Treasure Map, Bazaar, Explorer, Ghost Ship and Treasury aren't available
+/// yet.
+pub fn kingdom() -> Vec<Card> {
+    vec![Embargo, Lighthouse, NativeVillage, FishingVillage, Caravan, Island, MerchantShip, Outpost,
+         Tactician, Wharf]
+}
+
+pub fn embargo(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.buying_power += 2;
+    player.trash_from_play(state, Embargo);
+    if let Some(card) = player.decide(state, Decision::any_pile(Embargo, Embargoing)).pile() {
+        state.add_embargo_token(card);
+    }
+    NoProblem
+}
+
+pub fn lighthouse(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    player.buying_power += 1;
+    player.durations.push(Lighthouse);
+    NoProblem
+}
+
+pub fn lighthouse_next_turn(player: &mut PlayerHandle) -> Response {
+    player.buying_power += 1;
+    NoProblem
+}
+
+pub fn native_village(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    player.actions += 2;
+    let options = vec!["Set aside the top card of your deck", "Put the cards from your mat into your hand"];
+    match player.decide(state, Decision::option(NativeVillage, options)).option() {
+        Some(1) => {
+            for card in player.native_village.clone().into_iter() {
+                player.move_card(card, NativeVillageMat, Hand);
+            }
+        },
+        _ => {
+            // The card is set aside face down, so it isn't revealed or
+            // named in the log.
+            if let Some(card) = player.next_card() {
+                player.put(NativeVillageMat, card);
+                player.record(SetAsideFaceDown{player: player.id, to: NativeVillageMat});
+            }
+        },
+    }
+    NoProblem
+}
+
+pub fn fishing_village(player: &mut PlayerHandle) -> Response {
+    player.actions += 2;
+    player.buying_power += 1;
+    player.durations.push(FishingVillage);
+    NoProblem
+}
+
+pub fn fishing_village_next_turn(player: &mut PlayerHandle) -> Response {
+    player.actions += 1;
+    player.buying_power += 1;
+    NoProblem
+}
+
+pub fn caravan(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    player.actions += 1;
+    player.durations.push(Caravan);
+    NoProblem
+}
+
+pub fn caravan_next_turn(player: &mut PlayerHandle) -> Response {
+    player.draw();
+    NoProblem
+}
+
+pub fn island(player: &mut PlayerHandle, state: &mut GameState) -> Response {
+    // When Island is played twice, it's only on the mat after the first time.
+    player.move_card(Island, InPlay, IslandMat);
+    let hand = player.get_hand();
+    for card in player.decide(state, Decision::cards(Island, SettingAside, hand, 1, 1)).cards().into_iter() {
+        player.move_card(card, Hand, IslandMat);
+    }
+    NoProblem
+}
+
+pub fn merchant_ship(player: &mut PlayerHandle) -> Response {
+    player.buying_power += 2;
+    player.durations.push(MerchantShip);
+    NoProblem
+}
+
+pub fn merchant_ship_next_turn(player: &mut PlayerHandle) -> Response {
+    player.buying_power += 2;
+    NoProblem
+}
+
+pub fn outpost(player: &mut PlayerHandle) -> Response {
+    player.outpost = true;
+    player.durations.push(Outpost);
+    NoProblem
+}
+
+pub fn tactician(player: &mut PlayerHandle) -> Response {
+    // With nothing to discard, Tactician does nothing and is discarded as
+    // usual.
+    if !player.hand.is_empty() {
+        player.discard_hand();
+        player.durations.push(Tactician);
+    }
+    NoProblem
+}
+
+pub fn tactician_next_turn(player: &mut PlayerHandle) -> Response {
+    player.draw_n(5);
+    player.actions += 1;
+    player.buys += 1;
+    NoProblem
+}
+
+pub fn wharf(player: &mut PlayerHandle) -> Response {
+    player.draw_n(2);
+    player.buys += 1;
+    player.durations.push(Wharf);
+    NoProblem
+}
+
+pub fn wharf_next_turn(player: &mut PlayerHandle) -> Response {
+    player.draw_n(2);
+    player.buys += 1;
+    NoProblem
+}
+
+#[cfg(test)]
+#[path = "tests/seaside.rs"]
+mod tests;
@@ -0,0 +1,211 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, play, player, state};
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::reaction;
+use super::super::super::response::*;
+use super::super::super::token;
+
+#[test]
+fn test_pile_tokens() {
+    let mut p = player(vec![Village], vec![Copper, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.tokens.place(0, token::PlusCard, Village);
+    state.tokens.place(0, token::PlusBuy, Village);
+    assert_ok(play(Village, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Silver]);
+    assert_eq!(p.buys, 2);
+}
+
+#[test]
+fn test_ferry_token() {
+    let mut state = state();
+    state.tokens.place(0, token::MinusCost, Smithy);
+    assert_eq!(state.cost(Smithy).coins, 2);
+    // It only counts on its owner's turns.
+    state.active_player = 1;
+    assert_eq!(state.cost(Smithy).coins, 4);
+}
+
+#[test]
+fn test_buy_event() {
+    let mut p = player(vec![Gold, Gold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.events = vec![Ball, Borrow];
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.handle_cmd(command::BuyEvent(Trade), &mut state, &mut opponents), NotInKingdom(Trade));
+    assert_ok(p.handle_cmd(command::BuyEvent(Ball), &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(p.buys, 0);
+    assert!(p.minus_coin);
+    assert_eq!(p.discard, vec![Feast, Feast]);
+}
+
+#[test]
+fn test_once_per_turn() {
+    let mut p = player(vec![], vec![Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.events = vec![Borrow];
+    assert_ok(p.handle_cmd(command::BuyEvent(Borrow), &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(p.buys, 1);
+    assert_eq!(p.handle_cmd(command::BuyEvent(Borrow), &mut state, &mut opponents), Forbidden(Borrow));
+
+    // Pilgrimage can't be bought twice either, even with Buys to spare.
+    state.events = vec![Pilgrimage];
+    p.buys = 2;
+    p.buying_power = 8;
+    assert_ok(p.handle_cmd(command::BuyEvent(Pilgrimage), &mut state, &mut opponents));
+    assert_eq!(p.handle_cmd(command::BuyEvent(Pilgrimage), &mut state, &mut opponents), Forbidden(Pilgrimage));
+
+    // The −1 Card token makes the next draw one card short.
+    p.draw_n(2);
+    assert_eq!(p.hand, vec![Copper]);
+    assert!(!p.minus_card);
+}
+
+#[test]
+fn test_save_and_expedition() {
+    let mut p = player(vec![Gold, Estate], Vec::from_elem(8, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.events = vec![Save, Expedition];
+    p.buying_power = 4;
+    assert_ok(p.handle_cmd(command::BuyEvent(Save), &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Estate]);
+    assert_ok(p.handle_cmd(command::BuyEvent(Expedition), &mut state, &mut opponents));
+    // Seven cards are drawn, and then the saved Gold comes back.
+    p.cleanup();
+    assert_eq!(p.hand.len(), 8);
+    assert_eq!(p.hand[7], Gold);
+    assert!(p.end_of_turn.is_empty());
+}
+
+#[test]
+fn test_travelling_fair() {
+    let mut p = player(vec![], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.events = vec![TravellingFair];
+    p.buying_power = 5;
+    assert_ok(p.handle_cmd(command::BuyEvent(TravellingFair), &mut state, &mut opponents));
+    assert_eq!(p.buys, 2);
+    assert_ok(p.handle_cmd(command::Buy(Silver), &mut state, &mut opponents));
+    assert_eq!(p.deck, vec![Silver, Copper]);
+    // Victory cards are left in the discard pile.
+    p.gain(&mut state, Estate);
+    assert_eq!(p.discard, vec![Estate]);
+}
+
+#[test]
+fn test_minus_card_library() {
+    // The token uses up one draw, not the rest of the deck.
+    let mut p = player(vec![Library, Copper, Copper, Copper, Copper], vec![Silver, Silver, Gold, Estate]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    p.minus_card = true;
+    assert_ok(play(Library, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Copper, Copper, Copper, Silver, Silver, Gold]);
+    assert_eq!(p.deck, vec![Estate]);
+    assert!(!p.minus_card);
+}
+
+#[test]
+fn test_ratcatcher() {
+    let mut p = player(vec![Ratcatcher], vec![Estate]);
+    let mut state = state();
+    assert_ok(play(Ratcatcher, &mut p, &mut state, &mut RingBuf::new()));
+    assert_eq!(p.tavern, vec![Ratcatcher]);
+    assert!(p.in_play.is_empty());
+    p.call_reserves(&mut state, reaction::TurnStarted);
+    assert_eq!(state.trash, vec![Estate]);
+    assert_eq!(p.in_play, vec![Ratcatcher]);
+}
+
+#[test]
+fn test_coin_of_the_realm() {
+    let mut p = player(vec![Village], vec![]);
+    p.tavern = vec![CoinOfTheRealm];
+    assert_ok(play(Village, &mut p, &mut state(), &mut RingBuf::new()));
+    assert_eq!(p.actions, 4);
+    assert!(p.tavern.is_empty());
+}
+
+#[test]
+fn test_royal_carriage() {
+    let mut p = player(vec![Village], vec![Copper, Silver]);
+    p.tavern = vec![RoyalCarriage];
+    assert_ok(play(Village, &mut p, &mut state(), &mut RingBuf::new()));
+    assert_eq!(p.hand, vec![Copper, Silver]);
+    assert_eq!(p.actions, 4);
+    assert_eq!(p.in_play, vec![Village, RoyalCarriage]);
+}
+
+#[test]
+fn test_duplicate() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    p.tavern = vec![Duplicate, Duplicate];
+    p.gain(&mut state, Curse);
+    assert_eq!(p.discard, vec![Curse]);
+    // Calling one Duplicate for the copy gives the other a chance too.
+    p.gain(&mut state, Silver);
+    assert_eq!(p.discard, vec![Curse, Silver, Silver, Silver]);
+    assert_eq!(p.in_play, vec![Duplicate, Duplicate]);
+}
+
+#[test]
+fn test_traveller_exchange() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    state.kingdom.insert(Page, 9);
+    state.non_supply.insert(TreasureHunter, 5);
+    p.in_play = vec![Page];
+    p.discard_from_play(&mut state);
+    assert_eq!(p.discard, vec![TreasureHunter]);
+    assert!(p.in_play.is_empty());
+    assert_eq!(state.kingdom.get(&Page), Some(&10));
+    assert_eq!(state.non_supply_count(TreasureHunter), 4);
+}
+
+#[test]
+fn test_ranger() {
+    let deck = vec![Copper, Copper, Copper, Copper, Copper];
+    let mut p = player(vec![Ranger, Ranger], deck);
+    p.actions = 2;
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // The Journey token starts face up, so the first Ranger turns it down.
+    assert_ok(play(Ranger, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Ranger]);
+    assert_ok(play(Ranger, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 5);
+    assert_eq!(p.buys, 3);
+}
+
+#[test]
+fn test_champion() {
+    let mut p = player(vec![Champion, Village], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Champion, &mut p, &mut state, &mut opponents));
+    assert_ok(play(Village, &mut p, &mut state, &mut opponents));
+    // +1 Action from Champion, and +1 for each Action played after it.
+    assert_eq!(p.actions, 3);
+    p.cleanup();
+    assert_eq!(p.in_play, vec![Champion]);
+}
+
+#[test]
+fn test_inheritance() {
+    let mut p = player(vec![Gold, Gold, Silver], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.events = vec![Inheritance];
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::BuyEvent(Inheritance), &mut state, &mut opponents));
+    assert_eq!(state.tokens.pile_of(0, token::EstateToken), Some(Feast));
+    assert_eq!(state.count(Feast), Some(9));
+
+    // Estates are played like Smithy with the token on Smithy's pile.
+    let mut p = player(vec![Estate], vec![Copper, Copper, Copper]);
+    state.tokens.place(0, token::EstateToken, Smithy);
+    assert_ok(play(Estate, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Copper, Copper]);
+    assert_eq!(p.in_play, vec![Estate]);
+}
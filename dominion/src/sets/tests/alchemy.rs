@@ -0,0 +1,205 @@
+use std::collections::RingBuf;
+use std::rc::Rc;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, state};
+use super::super::super::Game;
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::decision::*;
+use super::super::super::response::*;
+use super::super::super::strategy::{Strategy, Turn};
+
+#[test]
+fn test_buy_with_potion() {
+    let mut p = player(vec![Copper, Copper, Copper, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Golem, 10);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    match p.handle_cmd(command::Buy(Golem), &mut state, &mut opponents) {
+        NotEnoughMoney{..} => (),
+        _ => panic!("bought a Golem without a potion!"),
+    }
+
+    let mut p = player(vec![Potion, Copper, Copper, Copper, Copper], vec![]);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!((p.buying_power, p.potions), (4, 1));
+    assert_ok(p.handle_cmd(command::Buy(Golem), &mut state, &mut opponents));
+    assert_eq!((p.buying_power, p.potions), (0, 0));
+    assert_eq!(p.discard, vec![Golem]);
+}
+
+#[test]
+fn test_transmute() {
+    let mut p = player(vec![Transmute, GreatHall], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Transmute, 10);
+    // Great Hall is an Action and a Victory card.
+    assert_ok(play_with(Transmute, &mut p, &mut state, &mut opponents, vec![Cards(vec![GreatHall])]));
+    assert_eq!(state.trash, vec![GreatHall]);
+    assert_eq!(p.discard, vec![Duchy, Gold]);
+}
+
+#[test]
+fn test_apothecary() {
+    let mut p = player(vec![Apothecary], vec![Silver, Copper, Potion, Estate, Gold]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Apothecary, &mut p, &mut state, &mut opponents, vec![Order(vec![Gold, Estate])]));
+    assert_eq!(p.hand, vec![Silver, Copper, Potion]);
+    assert_eq!(p.deck, vec![Gold, Estate]);
+    assert_eq!(p.actions, 1);
+}
+
+#[test]
+fn test_scrying_pool() {
+    let mut p = player(vec![ScryingPool], vec![Estate, Village, Smithy, Copper, Gold]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Province, Gold]));
+    // Discard the Estate, and leave the opponent's Province on their deck.
+    assert_ok(play_with(ScryingPool, &mut p, &mut state, &mut opponents, vec![Yes, No]));
+    assert_eq!(p.discard, vec![Estate]);
+    assert_eq!(p.hand, vec![Village, Smithy, Copper]);
+    assert_eq!(p.deck, vec![Gold]);
+    assert_eq!(opponents[0].deck, vec![Province, Gold]);
+}
+
+#[test]
+fn test_university() {
+    let mut p = player(vec![University], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // Gold isn't an Action, so the most expensive Action is gained.
+    assert_ok(play(University, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.actions, 2);
+    assert_eq!(p.discard, vec![Feast]);
+}
+
+#[test]
+fn test_familiar() {
+    let mut p = player(vec![Familiar], vec![Copper]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![]));
+    assert_ok(play(Familiar, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(opponents[0].discard, vec![Curse]);
+}
+
+#[test]
+fn test_philosophers_stone() {
+    let mut p = player(vec![PhilosophersStone], Vec::from_elem(8, Copper));
+    p.discard = vec![Estate, Estate, Estate];
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+}
+
+#[test]
+fn test_golem() {
+    let deck = vec![Golem, Copper, Village, Smithy, Estate, Estate, Estate, Estate, Estate, Estate];
+    let mut p = player(vec![Golem], deck);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Golem, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.in_play, vec![Golem, Village, Smithy]);
+    assert_eq!(p.discard, vec![Golem, Copper]);
+    assert_eq!(p.hand.len(), 4);
+    assert_eq!(p.actions, 2);
+}
+
+#[test]
+fn test_apprentice() {
+    let mut p = player(vec![Apprentice, Apprentice, Golem, Estate], Vec::from_elem(10, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // Golem costs $4 and a Potion.
+    assert_ok(play_with(Apprentice, &mut p, &mut state, &mut opponents, vec![Cards(vec![Golem])]));
+    assert_eq!(p.hand.len(), 8);
+    assert_eq!(p.actions, 1);
+    assert_ok(play_with(Apprentice, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate])]));
+    assert_eq!(p.hand, Vec::from_elem(8, Copper));
+    assert_eq!(state.trash, vec![Golem, Estate]);
+}
+
+#[test]
+fn test_herbalist_and_alchemist() {
+    let mut p = player(vec![Herbalist, Alchemist], vec![Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    p.actions = 2;
+    assert_ok(play_with(Herbalist, &mut p, &mut state, &mut opponents, vec![Cards(vec![Potion])]));
+    assert_ok(play(Alchemist, &mut p, &mut state, &mut opponents));
+    assert_eq!((p.buys, p.buying_power), (2, 1));
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    p.hand.push(Potion);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+
+    // Herbalist puts the Potion back, and then Alchemist has no Potion in
+    // play to go back with.
+    p.discard_from_play(&mut state);
+    assert_eq!(p.deck, vec![Potion]);
+    assert_eq!(p.in_play, vec![Herbalist, Alchemist, Copper, Copper]);
+
+    p.in_play.push(Potion);
+    p.discard_from_play(&mut state);
+    assert_eq!(p.deck, vec![Alchemist, Potion]);
+}
+
+/// Plays Chapel to trash an Estate, and then buys Silver.
+struct Possessor;
+
+impl Strategy for Possessor {
+    fn take_action_phase(&self, turn: &mut Turn) {
+        turn.play(Chapel);
+    }
+
+    fn choose_buy(&self, _: &Turn) -> Option<Card> {
+        Some(Silver)
+    }
+
+    fn decide(&self, decision: &Decision) -> Choice {
+        match *decision {
+            ChooseCards{purpose: Trashing, ..} => Cards(vec![Estate]),
+            _ => Undecided,
+        }
+    }
+}
+
+#[test]
+fn test_possession() {
+    let mut p = player(vec![Possession], vec![]);
+    let (mut state, mut handles) = (state(), RingBuf::new());
+    assert_ok(play(Possession, &mut p, &mut state, &mut handles));
+    assert_eq!(p.possessions, 1);
+
+    let mut possessed = player(vec![Chapel, Estate, Copper, Copper, Copper], Vec::from_elem(5, Copper));
+    possessed.id = 1;
+    p.strategy = Some(Rc::new(box Possessor as Box<Strategy + 'static>));
+    handles.push(possessed);
+    handles.push(p);
+    Game::play_possessed_turn(&mut state, &mut handles, 1);
+
+    // The Silver goes to whoever played Possession, and the trashed Estate
+    // comes back.
+    let (possessed, p) = (&handles[0], &handles[1]);
+    assert_eq!(p.discard, vec![Silver]);
+    assert!(state.trash.is_empty());
+    assert!(possessed.discard.contains(&Estate));
+    assert!(!possessed.discard.contains(&Silver));
+    assert!(possessed.strategy.is_none() && p.strategy.is_some());
+}
+
+#[test]
+fn test_possessor_reacts_to_gains() {
+    let mut p = player(vec![Watchtower], vec![]);
+    let (mut state, mut handles) = (state(), RingBuf::new());
+    let mut possessed = player(vec![Chapel, Estate, Copper, Copper, Copper], Vec::from_elem(5, Copper));
+    possessed.id = 1;
+    p.strategy = Some(Rc::new(box Possessor as Box<Strategy + 'static>));
+    handles.push(possessed);
+    handles.push(p);
+    Game::play_possessed_turn(&mut state, &mut handles, 1);
+
+    // The Silver is the possessor's gain, so their Watchtower can put it on
+    // their deck.
+    let p = &handles[1];
+    assert_eq!(p.deck, vec![Silver]);
+    assert!(p.discard.is_empty());
+    assert_eq!(p.turn_gains, 1);
+}
@@ -0,0 +1,101 @@
+use std::collections::RingBuf;
+
+use super::differently_named;
+use super::super::test::{assert_ok, opponent, play, play_with, player, state};
+use super::super::super::card::*;
+use super::super::super::decision::*;
+
+#[test]
+fn test_differently_named() {
+    assert_eq!(differently_named(&[]), 0);
+    assert_eq!(differently_named(&[Copper, Estate, Copper, Gold, Estate]), 3);
+    assert_eq!(Fairgrounds.victory_points(&[Copper, Silver, Gold, Estate, Duchy, Copper]), 2);
+    assert_eq!(Fairgrounds.victory_points(&[Copper, Silver, Gold, Estate, Estate]), 0);
+}
+
+#[test]
+fn test_hamlet() {
+    let mut p = player(vec![Hamlet, Estate, Copper], vec![Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Hamlet, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate]), Cards(vec![Copper])]));
+    assert_eq!((p.actions, p.buys), (2, 2));
+    assert_eq!(p.hand, vec![Silver]);
+    assert_eq!(p.discard, vec![Estate, Copper]);
+}
+
+#[test]
+fn test_menagerie() {
+    let mut p = player(vec![Menagerie, Copper, Silver], vec![Estate, Estate, Estate, Estate]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Menagerie, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 5);
+
+    // With a duplicate in hand, it's only one card.
+    let mut p = player(vec![Menagerie, Copper, Copper], vec![Estate, Estate, Estate]);
+    assert_ok(play(Menagerie, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 3);
+}
+
+#[test]
+fn test_tournament() {
+    let mut p = player(vec![Tournament, Province], vec![Copper]);
+    let mut state = state();
+    state.non_supply.insert(Princess, 1);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper], vec![]));
+    // The Prize goes on the deck, so it's drawn straight away.
+    assert_ok(play_with(Tournament, &mut p, &mut state, &mut opponents, vec![Yes, Cards(vec![Princess])]));
+    assert_eq!(p.hand, vec![Princess]);
+    assert_eq!(p.discard, vec![Province]);
+    assert_eq!((p.actions, p.buying_power), (1, 1));
+    assert_eq!(state.non_supply_count(Princess), 0);
+}
+
+#[test]
+fn test_young_witch() {
+    let mut p = player(vec![YoungWitch, Estate, Copper], vec![Silver, Gold]);
+    let mut state = state();
+    state.bane = Some(Chapel);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Chapel], vec![]));
+    opponents.push(opponent(vec![Copper], vec![]));
+    assert_ok(play_with(YoungWitch, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate, Copper])]));
+    assert_eq!(p.hand, vec![Silver, Gold]);
+    assert!(opponents[0].discard.is_empty());
+    assert_eq!(opponents[1].discard, vec![Curse]);
+}
+
+#[test]
+fn test_jester() {
+    let mut p = player(vec![Jester], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Estate]));
+    opponents.push(opponent(vec![], vec![Silver]));
+    assert_ok(play_with(Jester, &mut p, &mut state, &mut opponents, vec![Pick(0)]));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(opponents[0].discard, vec![Estate, Curse]);
+    assert_eq!(opponents[1].discard, vec![Silver]);
+    assert_eq!(p.discard, vec![Silver]);
+}
+
+#[test]
+fn test_trusty_steed() {
+    let mut p = player(vec![TrustySteed], vec![Copper, Copper, Estate]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // Each option can only be picked once, so the second Pick(0) is +2
+    // Actions.
+    assert_ok(play_with(TrustySteed, &mut p, &mut state, &mut opponents, vec![Pick(0), Pick(0)]));
+    assert_eq!(p.hand, vec![Copper, Copper]);
+    assert_eq!(p.actions, 2);
+}
+
+#[test]
+fn test_princess() {
+    let mut p = player(vec![Princess], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Princess, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buys, 2);
+    assert_eq!(state.cost(Gold).coins, 4);
+    assert_eq!(state.cost(Copper).coins, 0);
+}
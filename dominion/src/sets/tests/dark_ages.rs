@@ -0,0 +1,167 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, state};
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::decision::*;
+use super::super::super::rng::GameRng;
+use super::ruins_pile;
+
+#[test]
+fn test_ruins_pile() {
+    assert_eq!(ruins_pile(3, &GameRng::new(1)).len(), 20);
+    assert!(ruins_pile(1, &GameRng::new(1)).is_empty());
+}
+
+#[test]
+fn test_marauder() {
+    let mut p = player(vec![Marauder], vec![]);
+    let mut state = state();
+    state.non_supply.insert(Spoils, 15);
+    state.mixed_piles.push(vec![RuinedVillage, Survivors]);
+    state.kingdom.insert(RuinedVillage, 2);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![]));
+    assert_ok(play(Marauder, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Spoils]);
+    assert_eq!(opponents[0].discard, vec![RuinedVillage]);
+    // Survivors is on top of the Ruins pile now.
+    assert_eq!(state.kingdom.get(&RuinedVillage), None);
+    assert_eq!(state.kingdom.get(&Survivors), Some(&1));
+}
+
+#[test]
+fn test_spoils() {
+    let mut p = player(vec![Spoils], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.non_supply.insert(Spoils, 14);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 3);
+    assert!(p.in_play.is_empty());
+    assert_eq!(state.non_supply_count(Spoils), 15);
+}
+
+#[test]
+fn test_death_cart() {
+    let mut p = player(vec![DeathCart, Village], vec![]);
+    let mut state = state();
+    state.kingdom.insert(DeathCart, 10);
+    state.mixed_piles.push(vec![RuinedLibrary, Survivors, AbandonedMine]);
+    state.kingdom.insert(RuinedLibrary, 3);
+    assert_ok(play_with(DeathCart, &mut p, &mut state, &mut RingBuf::new(), vec![Cards(vec![])]));
+    assert_eq!(p.buying_power, 5);
+    assert_eq!(state.trash, vec![DeathCart]);
+
+    // Gaining one comes with two Ruins.
+    p.gain(&mut state, DeathCart);
+    assert_eq!(p.discard, vec![DeathCart, RuinedLibrary, Survivors]);
+    assert_eq!(state.kingdom.get(&AbandonedMine), Some(&1));
+}
+
+#[test]
+fn test_trash_triggers() {
+    let mut p = player(vec![Feodum, Cultist], vec![Copper, Silver, Gold]);
+    let mut state = state();
+    p.trash(&mut state, Feodum);
+    assert_eq!(p.discard, vec![Silver, Silver, Silver]);
+    p.trash(&mut state, Cultist);
+    assert_eq!(p.hand, vec![Copper, Silver, Gold]);
+    assert_eq!(state.trash, vec![Feodum, Cultist]);
+}
+
+#[test]
+fn test_feodum() {
+    let deck = vec![Feodum, Silver, Silver, Silver, Silver, Silver, Silver, Silver];
+    assert_eq!(Feodum.victory_points(deck.as_slice()), 2);
+}
+
+#[test]
+fn test_rats() {
+    let mut p = player(vec![Rats, Copper], vec![Rats]);
+    let mut state = state();
+    state.kingdom.insert(Rats, 10);
+    assert_ok(play(Rats, &mut p, &mut state, &mut RingBuf::new()));
+    assert_eq!(p.hand, vec![Rats]);
+    assert_eq!(p.discard, vec![Rats]);
+    assert_eq!(state.trash, vec![Copper]);
+}
+
+#[test]
+fn test_knight() {
+    let mut p = player(vec![DameSylvia], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![SirBailey, Estate]));
+    assert_ok(play(DameSylvia, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+    // Trashing another Knight trashes this one too.
+    assert_eq!(state.trash, vec![SirBailey, DameSylvia]);
+    assert_eq!(opponents[0].discard, vec![Estate]);
+}
+
+#[test]
+fn test_rogue() {
+    let mut p = player(vec![Rogue], vec![]);
+    let mut state = state();
+    state.trash = vec![Copper, Duchy];
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Silver, Estate]));
+    assert_ok(play(Rogue, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Duchy]);
+    assert_eq!(state.trash, vec![Copper]);
+    // Nothing happens to the opponent while there's something to gain.
+    assert_eq!(opponents[0].deck, vec![Silver, Estate]);
+}
+
+#[test]
+fn test_graverobber() {
+    let mut p = player(vec![Graverobber], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.trash = vec![Gold];
+    assert_ok(play_with(Graverobber, &mut p, &mut state, &mut opponents, vec![Pick(0)]));
+    assert_eq!(p.deck, vec![Gold, Copper]);
+    assert!(state.trash.is_empty());
+}
+
+#[test]
+fn test_hermit() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    state.non_supply.insert(Madman, 10);
+    p.in_play = vec![Hermit];
+    p.discard_from_play(&mut state);
+    assert_eq!(state.trash, vec![Hermit]);
+    assert_eq!(p.discard, vec![Madman]);
+}
+
+#[test]
+fn test_madman() {
+    let mut p = player(vec![Madman, Copper, Copper], vec![Silver, Gold, Estate]);
+    let mut state = state();
+    state.non_supply.insert(Madman, 9);
+    assert_ok(play(Madman, &mut p, &mut state, &mut RingBuf::new()));
+    assert_eq!(p.hand, vec![Copper, Copper, Silver, Gold]);
+    assert_eq!(p.actions, 2);
+    assert_eq!(state.non_supply_count(Madman), 10);
+}
+
+#[test]
+fn test_urchin() {
+    let mut p = player(vec![Urchin, Militia], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.non_supply.insert(Mercenary, 10);
+    assert_ok(play(Urchin, &mut p, &mut state, &mut opponents));
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(state.trash, vec![Urchin]);
+    assert_eq!(p.discard, vec![Mercenary]);
+}
+
+#[test]
+fn test_hovel() {
+    let mut p = player(vec![Hovel, Gold, Gold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Duchy), &mut state, &mut opponents));
+    assert_eq!(state.trash, vec![Hovel]);
+    assert_eq!(p.discard, vec![Duchy]);
+}
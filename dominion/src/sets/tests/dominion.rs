@@ -0,0 +1,381 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, state};
+use super::super::super::{Player, PlayerHandle};
+use super::super::super::card::*;
+use super::super::super::cost::Cost;
+use super::super::super::decision::*;
+use super::super::super::notify;
+use super::super::super::token;
+
+#[test]
+fn test_cellar() {
+    let mut p = player(vec![Cellar, Estate, Estate, Copper], vec![Silver, Gold]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Cellar, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate, Estate])]));
+    assert_eq!(p.hand, vec![Copper, Silver, Gold]);
+    assert_eq!(p.discard, vec![Estate, Estate]);
+    assert_eq!(p.actions, 1);
+}
+
+#[test]
+fn test_chapel() {
+    let mut p = player(vec![Chapel, Estate, Estate, Copper, Estate, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Chapel, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate, Estate, Estate, Copper])]));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(state.trash.len(), 4);
+}
+
+#[test]
+fn test_moat() {
+    let mut p = player(vec![Moat], vec![Copper, Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Moat, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Copper]);
+}
+
+#[test]
+fn test_moat_blocks_attacks() {
+    let mut p = player(vec![Militia, Witch], vec![Copper, Copper]);
+    p.actions = 2;
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Moat, Copper, Copper, Copper, Copper], vec![]));
+    opponents.push(opponent(vec![Copper, Copper, Copper, Copper, Copper], vec![]));
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].get_hand_size(), 5);
+    assert_eq!(opponents[1].get_hand_size(), 3);
+    assert_ok(play(Witch, &mut p, &mut state, &mut opponents));
+    assert!(opponents[0].discard.is_empty());
+    assert_eq!(opponents[1].discard, vec![Copper, Copper, Curse]);
+}
+
+#[test]
+fn test_chancellor() {
+    // Don't discard the deck.
+    let mut p = player(vec![Chancellor], vec![Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Chancellor, &mut p, &mut state, &mut opponents, vec![No]));
+    assert_eq!(p.buying_power, 2);
+    assert!(p.discard.is_empty());
+
+    // Discard the deck.
+    let mut p = player(vec![Chancellor], vec![Copper, Copper]);
+    assert_ok(play_with(Chancellor, &mut p, &mut state, &mut opponents, vec![Yes]));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(p.discard.len(), 2);
+    assert!(p.deck.is_empty());
+}
+
+#[test]
+fn test_village() {
+    let mut p = player(vec![Village], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Village, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.actions, 2);
+}
+
+#[test]
+fn test_woodcutter() {
+    let mut p = player(vec![Woodcutter], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Woodcutter, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buys, 2);
+    assert_eq!(p.buying_power, 2);
+}
+
+#[test]
+fn test_workshop() {
+    let mut p = player(vec![Workshop], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Workshop, &mut p, &mut state, &mut opponents, vec![Pile(Smithy)]));
+    assert_eq!(p.discard, vec![Smithy]);
+    assert_eq!(state.kingdom.get(&Smithy), Some(&9));
+
+    // Gold costs too much, so something else is gained instead.
+    let mut p = player(vec![Workshop], vec![]);
+    assert_ok(play_with(Workshop, &mut p, &mut state, &mut opponents, vec![Pile(Gold)]));
+    assert_eq!(p.discard.len(), 1);
+    assert!(p.discard[0].cost() <= Cost::coins(4));
+    assert_eq!(state.kingdom.get(&Gold), Some(&10));
+}
+
+#[test]
+fn test_bureaucrat() {
+    let mut p = player(vec![Bureaucrat], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper, Estate, Copper, Duchy, Copper], vec![]));
+    opponents.push(opponent(vec![Copper, Copper, Copper, Copper, Copper], vec![]));
+    assert_ok(play(Bureaucrat, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.deck, vec![Silver]);
+    assert_eq!(opponents[0].hand, vec![Copper, Copper, Duchy, Copper]);
+    assert_eq!(opponents[0].deck, vec![Estate]);
+    assert_eq!(opponents[1].hand.len(), 5);
+    assert!(opponents[1].deck.is_empty());
+}
+
+#[test]
+fn test_feast() {
+    let mut p = player(vec![Feast], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Feast, &mut p, &mut state, &mut opponents, vec![Pile(Duchy)]));
+    assert_eq!(p.discard, vec![Duchy]);
+    assert!(p.in_play.is_empty());
+    assert_eq!(state.trash, vec![Feast]);
+}
+
+#[test]
+fn test_gardens() {
+    let mut p = player(vec![Gardens], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert!(play(Gardens, &mut p, &mut state, &mut opponents).is_err());
+    assert_eq!(p.hand, vec![Gardens]);
+}
+
+#[test]
+fn test_militia() {
+    let mut p = player(vec![Militia], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper, Copper, Copper, Copper, Copper], vec![]));
+    opponents.push(opponent(vec![Gold, Estate, Silver, Curse, Copper, Copper], vec![]));
+    opponents.push(opponent(vec![Gold, Gold], vec![]));
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(opponents[0].get_hand_size(), 3);
+    // Bots that don't choose lose their least useful cards.
+    assert_eq!(opponents[1].hand, vec![Gold, Silver, Copper]);
+    assert_eq!(opponents[1].discard, vec![Curse, Estate, Copper]);
+    assert_eq!(opponents[2].hand, vec![Gold, Gold]);
+}
+
+#[test]
+fn test_militia_illegal_discard() {
+    let mut p = player(vec![Militia], vec![]);
+    let mut state = state();
+    let (mut o, conn) = PlayerHandle::new();
+    o.hand = vec![Copper, Estate, Estate, Silver, Gold];
+    spawn(proc() {
+        match conn.recv_notification() {
+            notify::Decide(_) => (),
+            _ => panic!("expected to be asked what to discard"),
+        }
+        // One card too few, and then a card that isn't in hand.
+        assert!(conn.choose(Cards(vec![Estate])).is_err());
+        assert!(conn.choose(Cards(vec![Estate, Province])).is_err());
+        assert!(!conn.choose(Cards(vec![Estate, Estate])).is_err());
+    });
+    let mut opponents = RingBuf::new();
+    opponents.push(o);
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].hand, vec![Copper, Silver, Gold]);
+}
+
+#[test]
+fn test_moneylender() {
+    let mut p = player(vec![Moneylender, Copper, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Moneylender, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.buying_power, 3);
+    assert_eq!(state.trash, vec![Copper]);
+}
+
+#[test]
+fn test_remodel() {
+    let mut p = player(vec![Remodel, Estate], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Remodel, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate]), Pile(Smithy)]));
+    assert!(p.hand.is_empty());
+    assert_eq!(p.discard, vec![Smithy]);
+    assert_eq!(state.trash, vec![Estate]);
+
+    // Duchy costs more than $2 above an Estate.
+    let mut p = player(vec![Remodel, Estate], vec![]);
+    assert_ok(play_with(Remodel, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate]), Pile(Duchy)]));
+    assert_eq!(p.discard.len(), 1);
+    assert!(p.discard[0].cost() <= Cost::coins(4));
+    assert_eq!(state.kingdom.get(&Duchy), Some(&10));
+}
+
+#[test]
+fn test_smithy() {
+    let mut p = player(vec![Smithy], vec![Copper, Silver, Gold, Estate]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Smithy, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Silver, Gold]);
+    assert_eq!(p.deck, vec![Estate]);
+}
+
+#[test]
+fn test_spy() {
+    let mut p = player(vec![Spy], vec![Copper, Estate]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Gold]));
+    opponents.push(opponent(vec![], vec![Province]));
+    assert_ok(play(Spy, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.discard, vec![Estate]);
+    assert_eq!(opponents[0].discard, vec![Gold]);
+    assert_eq!(opponents[1].deck, vec![Province]);
+
+    // The player can choose otherwise.
+    let mut p = player(vec![Spy], vec![Copper, Estate]);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Gold]));
+    assert_ok(play_with(Spy, &mut p, &mut state, &mut opponents, vec![No, No]));
+    assert_eq!(p.deck, vec![Estate]);
+    assert_eq!(opponents[0].deck, vec![Gold]);
+}
+
+#[test]
+fn test_thief() {
+    let mut p = player(vec![Thief], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Copper, Gold, Estate]));
+    opponents.push(opponent(vec![], vec![Estate, Estate]));
+    assert_ok(play(Thief, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Gold]);
+    assert_eq!(opponents[0].discard, vec![Copper]);
+    assert_eq!(opponents[0].deck, vec![Estate]);
+    assert_eq!(opponents[1].discard, vec![Estate, Estate]);
+
+    // The player can pick the cheaper Treasure, and leave it in the trash.
+    let mut p = player(vec![Thief], vec![]);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Silver, Gold]));
+    assert_ok(play_with(Thief, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver]), No]));
+    assert!(p.discard.is_empty());
+    assert_eq!(state.trash, vec![Silver]);
+    assert_eq!(opponents[0].discard, vec![Gold]);
+}
+
+#[test]
+fn test_throne_room() {
+    let mut p = player(vec![ThroneRoom, Smithy], vec![Copper, Copper, Copper, Silver, Silver, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(ThroneRoom, &mut p, &mut state, &mut opponents, vec![Cards(vec![Smithy])]));
+    assert_eq!(p.hand.len(), 6);
+    assert_eq!(p.in_play, vec![ThroneRoom, Smithy]);
+    assert_eq!(p.actions, 0);
+
+    // Only Action cards can be played twice.
+    let mut p = player(vec![ThroneRoom, Smithy, Copper], vec![]);
+    assert_ok(play_with(ThroneRoom, &mut p, &mut state, &mut opponents, vec![Cards(vec![Copper])]));
+    assert_eq!(p.in_play, vec![ThroneRoom, Smithy]);
+
+    // With nothing to play twice, nothing happens.
+    let mut p = player(vec![ThroneRoom, Copper], vec![]);
+    assert_ok(play(ThroneRoom, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+}
+
+#[test]
+fn test_throne_room_with_token() {
+    let mut p = player(vec![ThroneRoom, Smithy], Vec::from_elem(10, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.tokens.place(0, token::PlusCard, Smithy);
+    p.tavern = vec![CoinOfTheRealm];
+    assert_ok(play(ThroneRoom, &mut p, &mut state, &mut opponents));
+    // Both plays of Smithy get the +1 Card token.
+    assert_eq!(p.hand.len(), 8);
+    // Coin of the Realm can be called once the Smithy has resolved.
+    assert_eq!(p.actions, 2);
+    assert!(p.tavern.is_empty());
+}
+
+#[test]
+fn test_council_room() {
+    let mut p = player(vec![CouncilRoom], vec![Copper, Copper, Copper, Copper]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Estate]));
+    assert_ok(play(CouncilRoom, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 4);
+    assert_eq!(p.buys, 2);
+    assert_eq!(opponents[0].hand, vec![Estate]);
+}
+
+#[test]
+fn test_festival() {
+    let mut p = player(vec![Festival], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Festival, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.actions, 2);
+    assert_eq!(p.buys, 2);
+    assert_eq!(p.buying_power, 2);
+}
+
+#[test]
+fn test_laboratory() {
+    let mut p = player(vec![Laboratory], vec![Copper, Silver, Gold]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Laboratory, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Silver]);
+    assert_eq!(p.actions, 1);
+}
+
+#[test]
+fn test_library() {
+    // With no actions left, drawn Action cards are set aside.
+    let mut p = player(vec![Library, Copper, Copper, Copper, Copper], vec![Smithy, Silver, Gold, Estate]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Library, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Copper, Copper, Copper, Silver, Gold, Estate]);
+    assert_eq!(p.discard, vec![Smithy]);
+}
+
+#[test]
+fn test_market() {
+    let mut p = player(vec![Market], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Market, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.buys, 2);
+    assert_eq!(p.buying_power, 1);
+}
+
+#[test]
+fn test_mine() {
+    let mut p = player(vec![Mine, Silver], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Mine, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver]), Pile(Gold)]));
+    assert_eq!(p.hand, vec![Gold]);
+    assert_eq!(state.trash, vec![Silver]);
+
+    // Only Treasures can be gained.
+    let mut p = player(vec![Mine, Silver], vec![]);
+    assert_ok(play_with(Mine, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver]), Pile(Smithy)]));
+    assert_eq!(p.hand, vec![Gold]);
+}
+
+#[test]
+fn test_witch() {
+    let mut p = player(vec![Witch], vec![Copper, Copper]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![]));
+    opponents.push(opponent(vec![], vec![]));
+    assert_ok(play(Witch, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Copper]);
+    assert_eq!(opponents[0].discard, vec![Curse]);
+    assert_eq!(opponents[1].discard, vec![Curse]);
+    assert_eq!(state.kingdom.get(&Curse), Some(&8));
+}
+
+#[test]
+fn test_adventurer() {
+    let mut p = player(vec![Adventurer], vec![Estate, Copper, Smithy, Gold, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Adventurer, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Gold]);
+    assert_eq!(p.discard, vec![Estate, Smithy]);
+    assert_eq!(p.deck, vec![Silver]);
+}
@@ -0,0 +1,187 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, player, state};
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::phase;
+use super::super::super::response::*;
+use super::super::super::scoring::{ScoreSheet, ScoringRule};
+use super::{keep, orchard, palace, tower, wall, ObeliskPile};
+
+#[test]
+fn test_debt() {
+    let mut p = player(vec![Gold, Gold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Engineer, 10);
+    p.buys = 2;
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Engineer), &mut state, &mut opponents));
+    assert_eq!(p.debt, 4);
+    assert_eq!(p.buying_power, 6);
+    assert_eq!(p.handle_cmd(command::Buy(Copper), &mut state, &mut opponents), InDebt(4));
+    assert_ok(p.handle_cmd(command::PayDebt(4), &mut state, &mut opponents));
+    assert_eq!(p.debt, 0);
+    assert_eq!(p.buying_power, 2);
+    assert_ok(p.handle_cmd(command::Buy(Copper), &mut state, &mut opponents));
+}
+
+#[test]
+fn test_capital() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    p.in_play = vec![Capital];
+    p.buying_power = 2;
+    p.discard_from_play(&mut state);
+    assert_eq!(p.debt, 4);
+    assert_eq!(p.buying_power, 0);
+}
+
+#[test]
+fn test_split_pile() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    state.mixed_piles.push(vec![Patrician, Patrician, Emporium]);
+    state.kingdom.insert(Patrician, 3);
+    assert_eq!(p.gain(&mut state, Emporium), NotInKingdom(Emporium));
+    p.gain(&mut state, Patrician);
+    p.gain(&mut state, Patrician);
+    assert_eq!(state.kingdom.get(&Emporium), Some(&1));
+    assert_eq!(p.discard, vec![Patrician, Patrician]);
+}
+
+#[test]
+fn test_patrician() {
+    let mut p = player(vec![Patrician, Patrician], vec![Copper, Gold, Silver, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Patrician, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Patrician, Copper, Gold]);
+    // A cheaper card stays on the deck.
+    assert_ok(play(Patrician, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Gold, Silver]);
+    assert_eq!(p.deck, vec![Silver]);
+}
+
+#[test]
+fn test_farmers_market() {
+    let mut p = player(vec![FarmersMarket, FarmersMarket], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.add_pile_vp(FarmersMarket, 3);
+    p.actions = 2;
+    assert_ok(play(FarmersMarket, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 4);
+    assert_eq!(state.pile_vp(FarmersMarket), 4);
+    assert_ok(play(FarmersMarket, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.vp_tokens, 4);
+    assert_eq!(state.pile_vp(FarmersMarket), 0);
+    assert_eq!(state.trash, vec![FarmersMarket]);
+}
+
+#[test]
+fn test_temple() {
+    let mut p = player(vec![Temple, Estate, Copper, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Temple, 10);
+    assert_ok(play(Temple, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.vp_tokens, 1);
+    assert_eq!(state.trash, vec![Copper]);
+    assert_eq!(state.pile_vp(Temple), 1);
+
+    // Whoever gains the next Temple takes the VP from its pile.
+    p.gain(&mut state, Temple);
+    assert_eq!(p.vp_tokens, 2);
+    assert_eq!(state.pile_vp(Temple), 0);
+}
+
+#[test]
+fn test_villa() {
+    let mut p = player(vec![Gold, Gold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Villa, 10);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Villa), &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Villa]);
+    assert_eq!(p.actions, 2);
+    assert_eq!(p.phase, phase::ActionPhase);
+}
+
+#[test]
+fn test_gladiator() {
+    let mut p = player(vec![Gladiator, Copper], vec![]);
+    let mut state = state();
+    state.mixed_piles.push(vec![Gladiator, Fortune]);
+    state.kingdom.insert(Gladiator, 2);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Silver], vec![]));
+    assert_ok(play(Gladiator, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 3);
+    assert_eq!(state.trash, vec![Gladiator]);
+    assert_eq!(state.kingdom.get(&Fortune), Some(&1));
+}
+
+#[test]
+fn test_chariot_race() {
+    let mut p = player(vec![ChariotRace], vec![Gold]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Copper]));
+    assert_ok(play(ChariotRace, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Gold]);
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(p.vp_tokens, 1);
+    assert_eq!(opponents[0].deck, vec![Copper]);
+}
+
+#[test]
+fn test_event_with_debt() {
+    let mut p = player(vec![], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.events = vec![Triumph];
+    p.buys = 2;
+    assert_ok(p.handle_cmd(command::BuyEvent(Triumph), &mut state, &mut opponents));
+    assert_eq!(p.debt, 5);
+    assert_eq!(p.vp_tokens, 1);
+    assert_eq!(p.discard, vec![Estate]);
+    assert_eq!(p.handle_cmd(command::BuyEvent(Triumph), &mut state, &mut opponents), InDebt(5));
+}
+
+#[test]
+fn test_landmarks() {
+    let mut cards = Vec::from_elem(17, Copper);
+    assert_eq!(wall(cards.as_slice()), -2);
+    cards.push_all(&[Silver, Silver, Gold]);
+    assert_eq!(palace(cards.as_slice()), 3);
+    assert_eq!(orchard(&[Village, Village, Village, Smithy, Smithy]), 4);
+
+    let others = [vec![Copper, Silver, Silver]];
+    let sheet = ScoreSheet{cards: &[Copper, Copper, Silver, Village, Duchy], others: &others, empty_piles: &[Village, Duchy]};
+    assert_eq!(keep(&sheet), 5);
+    assert_eq!(tower(&sheet), 1);
+    assert_eq!(ObeliskPile(vec![Patrician, Emporium]).score(&ScoreSheet{cards: &[Patrician, Emporium, Copper], others: &[], empty_piles: &[]}), 4);
+}
+
+#[test]
+fn test_final_score() {
+    let mut p = player(Vec::from_elem(16, Copper), vec![Estate]);
+    let mut state = state();
+    let opponents: RingBuf<_> = RingBuf::new();
+    state.scoring.push(box Wall as Box<ScoringRule + 'static>);
+    state.scoring.push(box Palace as Box<ScoringRule + 'static>);
+    p.vp_tokens = 3;
+    // The Estate and VP tokens, less 2 for Wall and nothing for Palace.
+    assert_eq!(p.final_score(&state, opponents.iter()), 2);
+}
+
+#[test]
+fn test_tower_counts_whole_split_pile() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    let opponents: RingBuf<_> = RingBuf::new();
+    state.mixed_piles.push(vec![Patrician, Emporium]);
+    state.mixed_pile_contents.push(vec![Patrician, Emporium]);
+    state.kingdom.insert(Patrician, 2);
+    p.gain(&mut state, Patrician);
+    p.gain(&mut state, Emporium);
+    state.scoring.push(box Tower as Box<ScoringRule + 'static>);
+    // The pile is known by Emporium now, but the Patrician came from it too.
+    assert_eq!(p.final_score(&state, opponents.iter()), 2);
+}
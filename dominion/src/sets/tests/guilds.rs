@@ -0,0 +1,114 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, script, state};
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::decision::*;
+use super::super::super::response::*;
+
+#[test]
+fn test_coffers() {
+    let mut p = player(vec![Baker, CandlestickMaker], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Baker, &mut p, &mut state, &mut opponents));
+    assert_ok(play(CandlestickMaker, &mut p, &mut state, &mut opponents));
+    assert_eq!((p.coffers, p.buys), (2, 2));
+
+    match p.handle_cmd(command::SpendCoffers(3), &mut state, &mut opponents) {
+        NotEnoughCoffers{need: 3, have: 2} => (),
+        _ => panic!("spent Coffers the player didn't have!"),
+    }
+    assert_ok(p.handle_cmd(command::SpendCoffers(2), &mut state, &mut opponents));
+    assert_eq!((p.coffers, p.buying_power), (0, 2));
+}
+
+#[test]
+fn test_stonemason() {
+    let mut p = player(vec![Stonemason, Silver], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // Both cards have to cost less than the Silver.
+    assert_ok(play_with(Stonemason, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver]), Pile(Estate), Pile(Village)]));
+    assert_eq!(state.trash, vec![Silver]);
+    assert_eq!(p.discard, vec![Estate, Estate]);
+}
+
+#[test]
+fn test_overpay() {
+    let mut p = player(vec![Copper, Copper, Copper, Copper, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Masterpiece, 10);
+    script(&mut p, vec![Pick(2)]);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Masterpiece), &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 0);
+    assert_eq!(p.discard, vec![Masterpiece, Silver, Silver]);
+}
+
+#[test]
+fn test_doctor() {
+    let mut p = player(vec![Doctor], vec![Estate, Copper, Estate, Gold]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Doctor, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate])]));
+    assert_eq!(state.trash, vec![Estate, Estate]);
+    assert_eq!(p.deck, vec![Copper, Gold]);
+}
+
+#[test]
+fn test_advisor() {
+    let mut p = player(vec![Advisor], vec![Copper, Gold, Estate]);
+    let mut state = state();
+    let mut left = player(vec![], vec![]);
+    left.id = 1;
+    script(&mut left, vec![Cards(vec![Gold])]);
+    let mut opponents = RingBuf::new();
+    opponents.push(left);
+    assert_ok(play(Advisor, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Gold]);
+    assert_eq!(p.hand, vec![Copper, Estate]);
+    assert_eq!(p.actions, 1);
+}
+
+#[test]
+fn test_taxman() {
+    let mut p = player(vec![Taxman, Silver], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Silver, Copper, Copper, Estate, Estate], vec![]));
+    opponents.push(opponent(vec![Silver, Copper, Copper, Estate], vec![]));
+    assert_ok(play_with(Taxman, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver])]));
+    assert_eq!(p.deck, vec![Gold]);
+    assert_eq!(opponents[0].discard, vec![Silver]);
+    assert!(opponents[1].discard.is_empty());
+}
+
+#[test]
+fn test_journeyman() {
+    let mut p = player(vec![Journeyman], vec![Estate, Copper, Estate, Silver, Gold, Duchy]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Journeyman, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate])]));
+    assert_eq!(p.hand, vec![Copper, Silver, Gold]);
+    assert_eq!(p.discard, vec![Estate, Estate]);
+    assert_eq!(p.deck, vec![Duchy]);
+}
+
+#[test]
+fn test_merchant_guild() {
+    let mut p = player(vec![MerchantGuild, Copper, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(MerchantGuild, &mut p, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Silver), &mut state, &mut opponents));
+    assert_eq!(p.coffers, 1);
+}
+
+#[test]
+fn test_soothsayer() {
+    let mut p = player(vec![Soothsayer], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Copper]));
+    assert_ok(play(Soothsayer, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Gold]);
+    assert_eq!(opponents[0].discard, vec![Curse]);
+    assert_eq!(opponents[0].hand, vec![Copper]);
+}
@@ -0,0 +1,181 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, script, state};
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::decision::*;
+
+#[test]
+fn test_crossroads() {
+    let mut p = player(vec![ThroneRoom, Crossroads, Estate], vec![Copper, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // Played twice, it draws twice but only gives actions the first time.
+    assert_ok(play_with(ThroneRoom, &mut p, &mut state, &mut opponents, vec![Cards(vec![Crossroads])]));
+    assert_eq!(p.hand, vec![Estate, Copper, Silver]);
+    assert_eq!(p.actions, 3);
+}
+
+#[test]
+fn test_fools_gold() {
+    let mut p = player(vec![FoolsGold, FoolsGold, FoolsGold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 9);
+}
+
+#[test]
+fn test_fools_gold_reaction() {
+    let mut p = player(vec![Gold, Gold, Gold], vec![]);
+    let mut state = state();
+    let mut other = opponent(vec![FoolsGold, Copper], vec![Estate]);
+    other.id = 1;
+    let mut opponents = RingBuf::new();
+    opponents.push(other);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Province), &mut state, &mut opponents));
+    assert_eq!(state.trash, vec![FoolsGold]);
+    assert_eq!(opponents[0].deck, vec![Gold, Estate]);
+    assert_eq!(opponents[0].hand, vec![Copper]);
+}
+
+#[test]
+fn test_develop() {
+    let mut p = player(vec![Develop, Silver], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Develop, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver]), Pile(Smithy), Pile(Estate)]));
+    assert_eq!(state.trash, vec![Silver]);
+    assert_eq!(p.deck, vec![Estate, Smithy]);
+}
+
+#[test]
+fn test_trader() {
+    let mut p = player(vec![Trader, Estate], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Trader, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate])]));
+    assert_eq!(p.discard, vec![Silver, Silver]);
+
+    // Revealing Trader swaps a Curse for a Silver, and the Curse stays in
+    // the supply.
+    let mut p = player(vec![Trader], vec![]);
+    p.gain(&mut state, Curse);
+    assert_eq!(p.discard, vec![Silver]);
+    assert_eq!(state.count(Curse), Some(10));
+}
+
+#[test]
+fn test_gain_triggers() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    state.kingdom.insert(NomadCamp, 10);
+    state.kingdom.insert(Cache, 10);
+    p.gain(&mut state, NomadCamp);
+    assert_eq!(p.deck, vec![NomadCamp]);
+    p.gain(&mut state, Cache);
+    assert_eq!(p.discard, vec![Cache, Copper, Copper]);
+
+    // Once Watchtower has trashed it, there's nothing to put on the deck,
+    // even with another Nomad Camp in the discard pile.
+    let mut p = player(vec![Watchtower], vec![]);
+    p.discard = vec![NomadCamp];
+    script(&mut p, vec![Yes, Yes]);
+    p.gain(&mut state, NomadCamp);
+    assert_eq!(state.trash, vec![NomadCamp]);
+    assert_eq!(p.discard, vec![NomadCamp]);
+    assert!(p.deck.is_empty());
+}
+
+#[test]
+fn test_border_village() {
+    // It comes with the most expensive card that costs less.
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    state.kingdom.insert(BorderVillage, 10);
+    p.gain(&mut state, BorderVillage);
+    assert_eq!(p.discard, vec![BorderVillage, Duchy]);
+}
+
+#[test]
+fn test_inn_and_mandarin() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    state.kingdom.insert(Inn, 10);
+    state.kingdom.insert(Mandarin, 10);
+    p.discard = vec![Village, Copper];
+    script(&mut p, vec![Cards(vec![Village, Inn])]);
+    p.gain(&mut state, Inn);
+    assert_eq!(p.discard, vec![Copper]);
+    assert_eq!(p.deck.len(), 2);
+    assert!(p.deck.contains(&Village) && p.deck.contains(&Inn));
+
+    let mut p = player(vec![], vec![]);
+    p.in_play = vec![Copper, Silver];
+    script(&mut p, vec![Order(vec![Silver, Copper])]);
+    p.gain(&mut state, Mandarin);
+    assert!(p.in_play.is_empty());
+    assert_eq!(p.deck, vec![Silver, Copper]);
+}
+
+#[test]
+fn test_ill_gotten_gains() {
+    let mut p = player(vec![Gold, Gold], vec![]);
+    let mut state = state();
+    state.kingdom.insert(IllGottenGains, 10);
+    let mut opponents = RingBuf::new();
+    for id in range(1u, 3) {
+        let mut other = opponent(vec![], vec![]);
+        other.id = id;
+        opponents.push(other);
+    }
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(IllGottenGains), &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![IllGottenGains]);
+    assert_eq!(opponents[0].discard, vec![Curse]);
+    assert_eq!(opponents[1].discard, vec![Curse]);
+}
+
+#[test]
+fn test_haggler() {
+    let mut p = player(vec![Haggler, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Haggler, &mut p, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    // Copper is the only card cheaper than Silver that isn't a Victory
+    // card, apart from Curse.
+    assert_ok(p.handle_cmd(command::Buy(Silver), &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Silver, Copper]);
+}
+
+#[test]
+fn test_farmland() {
+    let mut p = player(vec![Gold, Gold, Estate], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Farmland, 8);
+    script(&mut p, vec![Cards(vec![Estate]), Pile(Smithy)]);
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Farmland), &mut state, &mut opponents));
+    assert_eq!(state.trash, vec![Estate]);
+    assert_eq!(p.discard, vec![Farmland, Smithy]);
+}
+
+#[test]
+fn test_highway() {
+    let mut p = player(vec![Highway, Highway], vec![Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    p.actions = 2;
+    assert_ok(play(Highway, &mut p, &mut state, &mut opponents));
+    assert_ok(play(Highway, &mut p, &mut state, &mut opponents));
+    assert_eq!(state.cost(Gold).coins, 4);
+    assert_eq!(p.actions, 2);
+}
+
+#[test]
+fn test_margrave() {
+    let mut p = player(vec![Margrave], vec![Copper, Copper, Copper]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper, Copper, Copper, Estate, Estate], vec![Curse]));
+    assert_ok(play(Margrave, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 3);
+    assert_eq!(p.buys, 2);
+    assert_eq!(opponents[0].hand, vec![Copper, Copper, Copper]);
+}
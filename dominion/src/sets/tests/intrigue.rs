@@ -0,0 +1,319 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, script, state};
+use super::super::super::Player;
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::cost::Cost;
+use super::super::super::decision::*;
+
+#[test]
+fn test_courtyard() {
+    let mut p = player(vec![Courtyard], vec![Copper, Silver, Gold]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Courtyard, &mut p, &mut state, &mut opponents, vec![Cards(vec![Gold])]));
+    assert_eq!(p.hand, vec![Copper, Silver]);
+    assert_eq!(p.deck, vec![Gold]);
+}
+
+#[test]
+fn test_pawn() {
+    // The second pick is out of the three options that are left.
+    let mut p = player(vec![Pawn], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Pawn, &mut p, &mut state, &mut opponents, vec![Pick(0), Pick(2)]));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(p.actions, 0);
+    assert_eq!(p.buys, 1);
+}
+
+#[test]
+fn test_secret_chamber() {
+    let mut p = player(vec![SecretChamber, Estate, Estate, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(SecretChamber, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate, Estate])]));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.buying_power, 2);
+}
+
+#[test]
+fn test_secret_chamber_reaction() {
+    let mut p = player(vec![Militia], vec![]);
+    let mut state = state();
+    let mut o = opponent(vec![SecretChamber, Copper, Copper, Estate, Estate], vec![Gold, Silver]);
+    script(&mut o, vec![Yes, Cards(vec![Estate, Estate]), Order(vec![Estate, Estate])]);
+    let mut opponents = RingBuf::new();
+    opponents.push(o);
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    // The Estates go back on the deck, and Militia still hits.
+    assert_eq!(opponents[0].deck, vec![Estate, Estate]);
+    assert_eq!(opponents[0].hand, vec![SecretChamber, Gold, Silver]);
+    assert_eq!(opponents[0].discard, vec![Copper, Copper]);
+}
+
+#[test]
+fn test_great_hall() {
+    let mut p = player(vec![GreatHall], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(GreatHall, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.actions, 1);
+    assert_eq!(GreatHall.victory_points(&[]), 1);
+}
+
+#[test]
+fn test_masquerade() {
+    let mut p = player(vec![Masquerade, Estate], vec![Copper, Copper]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Gold, Curse], vec![]));
+    opponents.push(opponent(vec![Silver], vec![]));
+    assert_ok(play_with(Masquerade, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate]), Cards(vec![])]));
+    // Each card goes to the next player, and bots pass their worst card.
+    assert_eq!(p.hand, vec![Copper, Copper, Silver]);
+    assert_eq!(opponents[0].hand, vec![Gold, Estate]);
+    assert_eq!(opponents[1].hand, vec![Curse]);
+    assert!(state.trash.is_empty());
+}
+
+#[test]
+fn test_shanty_town() {
+    let mut p = player(vec![ShantyTown, Copper], vec![Silver, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(ShantyTown, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Silver, Silver]);
+    assert_eq!(p.actions, 2);
+
+    // Nothing is drawn with another Action in hand.
+    let mut p = player(vec![ShantyTown, Village], vec![Silver, Silver]);
+    assert_ok(play(ShantyTown, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Village]);
+}
+
+#[test]
+fn test_steward() {
+    let mut p = player(vec![Steward, Estate, Estate, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Steward, &mut p, &mut state, &mut opponents, vec![Pick(2), Cards(vec![Estate, Estate])]));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(state.trash, vec![Estate, Estate]);
+}
+
+#[test]
+fn test_swindler() {
+    let mut p = player(vec![Swindler], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Silver]));
+    assert_ok(play_with(Swindler, &mut p, &mut state, &mut opponents, vec![Pile(Village)]));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(state.trash, vec![Silver]);
+    assert_eq!(opponents[0].discard, vec![Village]);
+}
+
+#[test]
+fn test_wishing_well() {
+    let mut p = player(vec![WishingWell], vec![Copper, Gold, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(WishingWell, &mut p, &mut state, &mut opponents, vec![Cards(vec![Gold])]));
+    assert_eq!(p.hand, vec![Copper, Gold]);
+
+    // A wrong guess stays on the deck.
+    let mut p = player(vec![WishingWell], vec![Copper, Gold, Silver]);
+    assert_ok(play_with(WishingWell, &mut p, &mut state, &mut opponents, vec![Cards(vec![Silver])]));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.deck, vec![Gold, Silver]);
+}
+
+#[test]
+fn test_baron() {
+    let mut p = player(vec![Baron, Estate], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Baron, &mut p, &mut state, &mut opponents, vec![Yes]));
+    assert_eq!(p.buying_power, 4);
+    assert_eq!(p.buys, 2);
+    assert_eq!(p.discard, vec![Estate]);
+    assert_eq!(state.kingdom.get(&Estate), Some(&10));
+
+    // Without an Estate to discard, one is gained.
+    let mut p = player(vec![Baron], vec![]);
+    assert_ok(play(Baron, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 0);
+    assert_eq!(p.discard, vec![Estate]);
+    assert_eq!(state.kingdom.get(&Estate), Some(&9));
+}
+
+#[test]
+fn test_bridge() {
+    let mut p = player(vec![Bridge, Copper, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Bridge, &mut p, &mut state, &mut opponents));
+    assert_eq!(state.cost(Smithy), Cost::coins(3));
+    assert_eq!(state.cost(Copper), Cost::coins(0));
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Smithy), &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 0);
+    assert_eq!(p.buys, 1);
+}
+
+#[test]
+fn test_conspirator() {
+    let mut p = player(vec![Village, Conspirator, Conspirator], vec![Copper, Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Village, &mut p, &mut state, &mut opponents));
+    assert_ok(play(Conspirator, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.actions, 1);
+    // The third Action played this turn.
+    assert_ok(play(Conspirator, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.buying_power, 4);
+    assert_eq!(p.hand, vec![Copper, Copper]);
+}
+
+#[test]
+fn test_coppersmith() {
+    let mut p = player(vec![Coppersmith, Copper, Copper, Silver], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Coppersmith, &mut p, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 6);
+}
+
+#[test]
+fn test_ironworks() {
+    let mut p = player(vec![Ironworks], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Ironworks, &mut p, &mut state, &mut opponents, vec![Pile(Gardens)]));
+    assert_eq!(p.discard, vec![Gardens]);
+    assert_eq!(p.hand, vec![Copper]);
+
+    let mut p = player(vec![Ironworks], vec![]);
+    assert_ok(play_with(Ironworks, &mut p, &mut state, &mut opponents, vec![Pile(Village)]));
+    assert_eq!(p.actions, 1);
+}
+
+#[test]
+fn test_mining_village() {
+    let mut p = player(vec![MiningVillage], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(MiningVillage, &mut p, &mut state, &mut opponents, vec![Yes]));
+    assert_eq!(p.actions, 2);
+    assert_eq!(p.buying_power, 2);
+    assert!(p.in_play.is_empty());
+    assert_eq!(state.trash, vec![MiningVillage]);
+}
+
+#[test]
+fn test_scout() {
+    let mut p = player(vec![Scout], vec![Estate, Copper, Duchy, Silver, Gold]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Scout, &mut p, &mut state, &mut opponents, vec![Order(vec![Silver, Copper])]));
+    assert_eq!(p.hand, vec![Estate, Duchy]);
+    assert_eq!(p.deck, vec![Silver, Copper, Gold]);
+}
+
+#[test]
+fn test_duke() {
+    assert_eq!(Duke.victory_points([Duke, Duchy, Duchy, Estate].as_slice()), 2);
+    assert_eq!(Duke.victory_points([Duke].as_slice()), 0);
+}
+
+#[test]
+fn test_minion() {
+    let mut p = player(vec![Minion, Copper], vec![Silver, Silver, Silver, Silver]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper, Copper, Copper, Copper, Copper], vec![Gold, Gold, Gold, Gold]));
+    opponents.push(opponent(vec![Copper, Copper, Copper, Copper], vec![Gold]));
+    assert_ok(play_with(Minion, &mut p, &mut state, &mut opponents, vec![Pick(1)]));
+    assert_eq!(p.hand, vec![Silver, Silver, Silver, Silver]);
+    assert_eq!(p.discard, vec![Copper]);
+    assert_eq!(opponents[0].hand, vec![Gold, Gold, Gold, Gold]);
+    assert_eq!(opponents[1].get_hand_size(), 4);
+    assert!(opponents[1].discard.is_empty());
+}
+
+#[test]
+fn test_saboteur() {
+    let mut p = player(vec![Saboteur], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Copper, Gold, Silver]));
+    assert_ok(play(Saboteur, &mut p, &mut state, &mut opponents));
+    // Bots take the most expensive replacement they can.
+    assert_eq!(state.trash, vec![Gold]);
+    assert_eq!(opponents[0].discard, vec![Feast, Copper]);
+    assert_eq!(opponents[0].deck, vec![Silver]);
+}
+
+#[test]
+fn test_torturer() {
+    let mut p = player(vec![Torturer], vec![Copper, Copper, Copper]);
+    let mut state = state();
+    let mut o = opponent(vec![Copper, Gold], vec![]);
+    script(&mut o, vec![Pick(1)]);
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper, Estate, Gold], vec![]));
+    opponents.push(o);
+    assert_ok(play(Torturer, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.get_hand_size(), 3);
+    assert_eq!(opponents[0].hand, vec![Gold]);
+    assert_eq!(opponents[1].hand, vec![Copper, Gold, Curse]);
+}
+
+#[test]
+fn test_trading_post() {
+    let mut p = player(vec![TradingPost, Estate, Curse, Gold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(TradingPost, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate, Curse])]));
+    assert_eq!(p.hand, vec![Gold, Silver]);
+    assert_eq!(state.trash, vec![Estate, Curse]);
+}
+
+#[test]
+fn test_tribute() {
+    let mut p = player(vec![Tribute], vec![Silver, Silver]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Copper, GreatHall]));
+    assert_ok(play(Tribute, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(p.actions, 2);
+    assert_eq!(p.hand, vec![Silver, Silver]);
+    assert_eq!(opponents[0].discard, vec![Copper, GreatHall]);
+
+    // The same card twice only counts once.
+    let mut p = player(vec![Tribute], vec![]);
+    opponents.front_mut().unwrap().deck = vec![Gold, Gold];
+    assert_ok(play(Tribute, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+}
+
+#[test]
+fn test_upgrade() {
+    let mut p = player(vec![Upgrade, Estate], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Upgrade, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate]), Pile(Silver)]));
+    assert_eq!(p.hand, vec![Copper]);
+    assert_eq!(p.discard, vec![Silver]);
+
+    // The new card has to cost exactly $1 more.
+    let mut p = player(vec![Upgrade, Estate], vec![]);
+    assert_ok(play_with(Upgrade, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate]), Pile(Smithy)]));
+    assert_eq!(p.discard.len(), 1);
+    assert_eq!(p.discard[0].cost(), Cost::coins(3));
+}
+
+#[test]
+fn test_harem_and_nobles() {
+    let mut p = player(vec![Harem], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Harem, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(Harem.victory_points(&[]), 2);
+
+    let mut p = player(vec![Nobles], vec![]);
+    assert_ok(play_with(Nobles, &mut p, &mut state, &mut opponents, vec![Pick(1)]));
+    assert_eq!(p.actions, 2);
+}
@@ -0,0 +1,282 @@
+use std::collections::RingBuf;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, script, state};
+use super::super::super::Player;
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::cost::Cost;
+use super::super::super::decision::*;
+use super::super::super::response::*;
+
+#[test]
+fn test_platinum_and_bank() {
+    // Bank is played after the other treasures, so it counts them all.
+    let mut p = player(vec![Copper, Bank, Platinum, Silver], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 12);
+    assert_eq!(p.in_play, vec![Copper, Platinum, Silver, Bank]);
+}
+
+#[test]
+fn test_venture() {
+    let mut p = player(vec![Venture], vec![Estate, Smithy, Gold, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Venture, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 4);
+    assert_eq!(p.in_play, vec![Venture, Gold]);
+    assert_eq!(p.discard, vec![Estate, Smithy]);
+    assert_eq!(p.deck, vec![Copper]);
+}
+
+#[test]
+fn test_loan() {
+    let mut p = player(vec![Loan], vec![Estate, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Loan, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(state.trash, vec![Copper]);
+    assert_eq!(p.discard, vec![Estate]);
+}
+
+#[test]
+fn test_quarry() {
+    let mut p = player(vec![Quarry], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Quarry, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(state.cost(Smithy), Cost::coins(2));
+    assert_eq!(state.cost(Silver), Cost::coins(3));
+}
+
+#[test]
+fn test_peddler_cost() {
+    let mut p = player(vec![], vec![]);
+    p.in_play = vec![Village, Smithy, Village];
+    p.buying_power = 2;
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Peddler, 10);
+    assert_ok(p.handle_cmd(command::Buy(Peddler), &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 0);
+}
+
+#[test]
+fn test_peddler_cost_before_buying() {
+    let mut p = player(vec![Copper], vec![]);
+    p.in_play = vec![Village, Smithy];
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_eq!(state.cost(Peddler).coins, 8);
+    // Playing a Treasure starts the Buy phase, before anything is bought.
+    assert_ok(p.handle_cmd(command::Play(Copper), &mut state, &mut opponents));
+    assert_eq!(state.cost(Peddler).coins, 4);
+    assert_ok(p.handle_cmd(command::EndPhase, &mut state, &mut opponents));
+    assert_eq!(state.cost(Peddler).coins, 8);
+}
+
+#[test]
+fn test_monument() {
+    let mut p = player(vec![Monument], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Monument, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(p.vp_tokens, 1);
+    assert_eq!(p.get_total_points(), 1);
+}
+
+#[test]
+fn test_bishop() {
+    let mut p = player(vec![Bishop, Gold], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Estate], vec![]));
+    assert_ok(play_with(Bishop, &mut p, &mut state, &mut opponents, vec![Cards(vec![Gold])]));
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(p.vp_tokens, 4);
+    assert_eq!(state.trash, vec![Gold]);
+    // Bots keep their cards.
+    assert_eq!(opponents[0].hand, vec![Estate]);
+}
+
+#[test]
+fn test_contraband() {
+    let mut p = player(vec![Contraband], vec![]);
+    let mut state = state();
+    let mut o = opponent(vec![], vec![]);
+    script(&mut o, vec![Cards(vec![Province])]);
+    let mut opponents = RingBuf::new();
+    opponents.push(o);
+    assert_ok(play(Contraband, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 3);
+    assert_eq!(p.buys, 2);
+    p.buying_power = 8;
+    assert_eq!(p.handle_cmd(command::Buy(Province), &mut state, &mut opponents), Forbidden(Province));
+    assert_ok(p.handle_cmd(command::Buy(Gold), &mut state, &mut opponents));
+}
+
+#[test]
+fn test_mint() {
+    let mut p = player(vec![Mint, Gold, Gold], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Mint, 10);
+    assert_ok(play_with(Mint, &mut p, &mut state, &mut opponents, vec![Cards(vec![Gold])]));
+    assert_eq!(p.discard, vec![Gold]);
+
+    // Buying Mint trashes the treasures in play.
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_ok(p.handle_cmd(command::Buy(Mint), &mut state, &mut opponents));
+    assert_eq!(state.trash, vec![Gold, Gold]);
+    assert_eq!(p.in_play, vec![Mint]);
+    assert_eq!(p.discard, vec![Gold, Mint]);
+}
+
+#[test]
+fn test_mountebank() {
+    let mut p = player(vec![Mountebank], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Curse, Copper], vec![]));
+    opponents.push(opponent(vec![Copper], vec![]));
+    assert_ok(play(Mountebank, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(opponents[0].discard, vec![Curse]);
+    assert_eq!(opponents[1].discard, vec![Curse, Copper]);
+}
+
+#[test]
+fn test_goons() {
+    let mut p = player(vec![Goons], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![Copper, Copper, Copper, Copper, Copper], vec![]));
+    assert_ok(play(Goons, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].get_hand_size(), 3);
+
+    // Every card bought is worth a VP token.
+    p.buying_power += 1;
+    assert_ok(p.handle_cmd(command::Buy(Village), &mut state, &mut opponents));
+    assert_eq!(p.vp_tokens, 1);
+    assert_eq!(p.buys, 1);
+}
+
+#[test]
+fn test_talisman_and_hoard() {
+    let mut p = player(vec![Talisman, Hoard, Gold], vec![]);
+    p.buys = 2;
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(p.handle_cmd(command::PlayAllMoney, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 6);
+    assert_ok(p.handle_cmd(command::Buy(Smithy), &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Smithy, Smithy]);
+    assert_ok(p.handle_cmd(command::Buy(Estate), &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Smithy, Smithy, Estate, Gold]);
+}
+
+#[test]
+fn test_watchtower() {
+    let mut p = player(vec![Watchtower, Copper], vec![Copper, Copper, Copper, Copper, Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Watchtower, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 6);
+
+    // Curses are trashed, and anything else goes on top of the deck.
+    let mut p = player(vec![Watchtower], vec![]);
+    p.gain(&mut state, Curse);
+    p.gain(&mut state, Gold);
+    assert_eq!(state.trash, vec![Curse]);
+    assert_eq!(p.deck[0], Gold);
+    assert!(p.discard.is_empty());
+}
+
+#[test]
+fn test_city() {
+    let mut p = player(vec![City, City], Vec::from_elem(6, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(Village, 0);
+    assert_ok(play(City, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 3);
+    assert_eq!(p.actions, 2);
+    // With two empty piles, it's +$1 and +1 Buy too.
+    state.kingdom.insert(Smithy, 0);
+    assert_ok(play(City, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, Vec::from_elem(4, Copper));
+    assert_eq!(p.buys, 2);
+    assert_eq!(p.buying_power, 1);
+}
+
+#[test]
+fn test_counting_house() {
+    let mut p = player(vec![CountingHouse], vec![]);
+    p.discard = vec![Copper, Estate, Copper];
+    assert_ok(play(CountingHouse, &mut p, &mut state(), &mut RingBuf::new()));
+    assert_eq!(p.hand, vec![Copper, Copper]);
+    assert_eq!(p.discard, vec![Estate]);
+}
+
+#[test]
+fn test_rabble() {
+    let mut p = player(vec![Rabble], vec![]);
+    let mut state = state();
+    let mut opponents = RingBuf::new();
+    opponents.push(opponent(vec![], vec![Copper, Estate, Village, Gold]));
+    assert_ok(play(Rabble, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].discard, vec![Copper, Village]);
+    assert_eq!(opponents[0].deck, vec![Estate, Gold]);
+}
+
+#[test]
+fn test_royal_seal() {
+    let mut p = player(vec![], vec![]);
+    let mut state = state();
+    p.in_play = vec![RoyalSeal];
+    p.gain(&mut state, Silver);
+    assert_eq!(p.deck, vec![Silver]);
+    // Victory cards are left in the discard pile.
+    p.gain(&mut state, Estate);
+    assert_eq!(p.discard, vec![Estate]);
+}
+
+#[test]
+fn test_vault() {
+    let mut p = player(vec![Vault, Estate], vec![Copper, Copper]);
+    let mut state = state();
+    let mut o = opponent(vec![Estate, Estate, Copper], vec![Gold]);
+    script(&mut o, vec![Cards(vec![Estate, Estate])]);
+    let mut opponents = RingBuf::new();
+    opponents.push(o);
+    assert_ok(play_with(Vault, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate])]));
+    assert_eq!(p.hand, vec![Copper, Copper]);
+    assert_eq!(p.buying_power, 1);
+    assert_eq!(opponents[0].hand, vec![Copper, Gold]);
+}
+
+#[test]
+fn test_grand_market() {
+    let mut p = player(vec![], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    state.kingdom.insert(GrandMarket, 10);
+    p.in_play = vec![Copper];
+    p.buying_power = 6;
+    assert_eq!(p.handle_cmd(command::Buy(GrandMarket), &mut state, &mut opponents), Forbidden(GrandMarket));
+    p.in_play = vec![Silver];
+    assert_ok(p.handle_cmd(command::Buy(GrandMarket), &mut state, &mut opponents));
+}
+
+#[test]
+fn test_forge() {
+    let mut p = player(vec![Forge, Estate, Estate, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Forge, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate, Estate]), Pile(Smithy)]));
+    assert_eq!(state.trash, vec![Estate, Estate]);
+    assert_eq!(p.discard, vec![Smithy]);
+    assert_eq!(p.hand, vec![Copper]);
+}
+
+#[test]
+fn test_kings_court() {
+    let mut p = player(vec![KingsCourt, Village], vec![Copper, Copper, Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(KingsCourt, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand, vec![Copper, Copper, Copper]);
+    assert_eq!(p.actions, 6);
+    assert_eq!(p.in_play, vec![KingsCourt, Village]);
+}
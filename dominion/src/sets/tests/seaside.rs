@@ -0,0 +1,194 @@
+use std::collections::RingBuf;
+use std::rc::Rc;
+
+use super::super::test::{assert_ok, opponent, play, play_with, player, state};
+use super::super::super::{Game, Player};
+use super::super::super::card::*;
+use super::super::super::command;
+use super::super::super::decision::*;
+use super::super::super::strategy::{Strategy, Turn};
+
+#[test]
+fn test_durations_stay_in_play() {
+    let mut p = player(vec![Village, Caravan], vec![Copper, Copper, Copper, Copper, Copper, Copper, Copper, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Village, &mut p, &mut state, &mut opponents));
+    assert_ok(play(Caravan, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.get_hand_size(), 2);
+    p.cleanup();
+    assert_eq!(p.in_play, vec![Caravan]);
+    assert_eq!(p.discard, vec![Village, Copper, Copper]);
+
+    // Caravan draws at the start of the next turn, and then it's discarded.
+    p.resolve_durations(&mut state);
+    assert_eq!(p.get_hand_size(), 6);
+    assert_eq!(p.hand[5], Silver);
+    p.cleanup();
+    assert!(p.in_play.is_empty());
+}
+
+#[test]
+fn test_wharf() {
+    let mut p = player(vec![Wharf], Vec::from_elem(9, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Wharf, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.get_hand_size(), 2);
+    assert_eq!(p.buys, 2);
+    p.cleanup();
+    p.resolve_durations(&mut state);
+    assert_eq!(p.get_hand_size(), 7);
+    assert_eq!(p.buys, 1);
+}
+
+#[test]
+fn test_throne_room_stays_with_duration() {
+    let mut p = player(vec![ThroneRoom, Wharf], Vec::from_elem(20, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(ThroneRoom, &mut p, &mut state, &mut opponents, vec![Cards(vec![Wharf])]));
+    p.cleanup();
+    assert_eq!(p.in_play, vec![ThroneRoom, Wharf]);
+    p.resolve_durations(&mut state);
+    assert_eq!(p.get_hand_size(), 9);
+    assert_eq!(p.buys, 2);
+    p.cleanup();
+    assert!(p.in_play.is_empty());
+}
+
+#[test]
+fn test_fishing_village_and_merchant_ship() {
+    let mut p = player(vec![FishingVillage, MerchantShip], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(FishingVillage, &mut p, &mut state, &mut opponents));
+    assert_ok(play(MerchantShip, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.buying_power, 3);
+    p.cleanup();
+    p.resolve_durations(&mut state);
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.buying_power, 3);
+}
+
+#[test]
+fn test_tactician() {
+    let mut p = player(vec![Tactician, Copper, Copper], Vec::from_elem(10, Silver));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Tactician, &mut p, &mut state, &mut opponents));
+    assert!(p.hand.is_empty());
+    assert_eq!(p.discard, vec![Copper, Copper]);
+    p.cleanup();
+    p.resolve_durations(&mut state);
+    assert_eq!(p.get_hand_size(), 10);
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.buys, 1);
+
+    // With no cards to discard, it doesn't stay in play.
+    let mut p = player(vec![Tactician], vec![]);
+    assert_ok(play(Tactician, &mut p, &mut state, &mut opponents));
+    assert!(p.durations.is_empty());
+}
+
+#[test]
+fn test_lighthouse() {
+    let mut p = player(vec![Lighthouse], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Lighthouse, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.actions, 1);
+    assert_eq!(p.buying_power, 1);
+
+    // Attacks don't affect a player with a Lighthouse in play.
+    let mut p = player(vec![Militia], vec![]);
+    let mut o = opponent(vec![Copper, Copper, Copper, Copper, Copper], vec![]);
+    o.in_play = vec![Lighthouse];
+    opponents.push(o);
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].get_hand_size(), 5);
+}
+
+#[test]
+fn test_outpost() {
+    let mut p = player(vec![Outpost], Vec::from_elem(10, Copper));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Outpost, &mut p, &mut state, &mut opponents));
+    assert!(p.outpost);
+    p.cleanup();
+    assert_eq!(p.get_hand_size(), 3);
+    assert_eq!(p.in_play, vec![Outpost]);
+}
+
+/// Plays Outpost if it can, and buys nothing.
+struct Outposter;
+
+impl Strategy for Outposter {
+    fn take_action_phase(&self, turn: &mut Turn) {
+        turn.play(Outpost);
+    }
+
+    fn choose_buy(&self, _: &Turn) -> Option<Card> {
+        None
+    }
+}
+
+#[test]
+fn test_outpost_on_extra_turn() {
+    let mut p = player(vec![Outpost], Vec::from_elem(10, Copper));
+    p.strategy = Some(Rc::new(box Outposter as Box<Strategy + 'static>));
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    // Outpost played on the extra turn doesn't earn another one, so the
+    // next hand is a full one.
+    Game::play_turn(&mut p, &mut state, &mut opponents, 1, true);
+    assert!(!p.outpost);
+    assert_eq!(p.get_hand_size(), 5);
+}
+
+#[test]
+fn test_outpost_on_possessed_turn() {
+    let possessed = player(vec![Outpost], Vec::from_elem(10, Copper));
+    let mut p = player(vec![], vec![]);
+    p.id = 1;
+    p.strategy = Some(Rc::new(box Outposter as Box<Strategy + 'static>));
+    let (mut state, mut handles) = (state(), RingBuf::new());
+    handles.push(possessed);
+    handles.push(p);
+    Game::play_possessed_turn(&mut state, &mut handles, 1);
+    assert!(!handles[0].outpost);
+    assert_eq!(handles[0].get_hand_size(), 5);
+}
+
+#[test]
+fn test_embargo() {
+    let mut p = player(vec![Embargo], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Embargo, &mut p, &mut state, &mut opponents, vec![Pile(Smithy)]));
+    assert_eq!(p.buying_power, 2);
+    assert_eq!(state.trash, vec![Embargo]);
+    assert_eq!(state.embargo_tokens(Smithy), 1);
+
+    // Buying from the pile comes with a Curse.
+    p.buying_power = 4;
+    assert_ok(p.handle_cmd(command::Buy(Smithy), &mut state, &mut opponents));
+    assert_eq!(p.discard, vec![Smithy, Curse]);
+}
+
+#[test]
+fn test_native_village() {
+    let mut p = player(vec![NativeVillage, NativeVillage], vec![Gold, Silver]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(NativeVillage, &mut p, &mut state, &mut opponents, vec![Pick(0)]));
+    assert_eq!(p.native_village, vec![Gold]);
+    assert_eq!(p.actions, 2);
+    assert_ok(play_with(NativeVillage, &mut p, &mut state, &mut opponents, vec![Pick(1)]));
+    assert!(p.native_village.is_empty());
+    assert_eq!(p.hand, vec![Gold]);
+}
+
+#[test]
+fn test_island() {
+    let mut p = player(vec![Island, Estate, Copper], vec![]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play_with(Island, &mut p, &mut state, &mut opponents, vec![Cards(vec![Estate])]));
+    assert_eq!(p.island, vec![Island, Estate]);
+    assert!(p.in_play.is_empty());
+    assert_eq!(p.hand, vec![Copper]);
+    // Cards on the mat still count at the end of the game.
+    assert_eq!(p.score(), 3);
+}
@@ -0,0 +1,233 @@
+//! Run many games in parallel to compare strategies.
+
+use std::os;
+use std::rand::{task_rng, Rng};
+use std::sync::TaskPool;
+use std::task;
+
+use super::{Connection, Game, GameResult};
+use config::GameConfig;
+use rng::GameRng;
+
+/// The z-score for a 95% confidence interval.
+static Z_95: f64 = 1.96;
+
+/// A strategy plays a game over its connection until the game is over.
+pub type StrategyFn = fn(&Connection);
+
+/// A batch of games between the same set of strategies.
+pub struct Simulation {
+    config: GameConfig,
+    games: uint,
+    seed: Option<u64>,
+    strategies: Vec<StrategyFn>,
+    threads: uint,
+}
+
+impl Simulation {
+    /// Set up a simulation with one player per strategy. By default it
+    /// plays 1,000 games, using one thread per CPU.
+    pub fn new(strategies: Vec<StrategyFn>) -> Simulation {
+        Simulation{
+            config: GameConfig::new(),
+            games: 1000,
+            seed: None,
+            strategies: strategies,
+            threads: os::num_cpus(),
+        }
+    }
+
+    /// Set up every game according to `config`.
+    pub fn config(mut self, config: GameConfig) -> Simulation {
+        self.config = config;
+        self
+    }
+
+    /// Set the number of games to play.
+    pub fn games(mut self, games: uint) -> Simulation {
+        self.games = games;
+        self
+    }
+
+    /// Seed the simulation. Each game's seed and seating order are drawn
+    /// from this, so two simulations with the same seed play the same
+    /// sequence of deals, e.g. to compare strategies with common random
+    /// numbers.
+    pub fn seed(mut self, seed: u64) -> Simulation {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the number of games to play at once.
+    pub fn threads(mut self, threads: uint) -> Simulation {
+        self.threads = threads;
+        self
+    }
+
+    /// Play all of the games, and gather up the results. Games where a
+    /// strategy fails are counted as failures rather than results.
+    pub fn run(&self) -> SimulationResult {
+        let rng = GameRng::new(self.seed.unwrap_or_else(|| task_rng().gen()));
+        let pool = TaskPool::new(self.threads);
+        let (result_chan, result_port) = channel();
+
+        for _ in range(0, self.games) {
+            let result_chan = result_chan.clone();
+            let config = self.config.clone().seed(rng.gen());
+            let strategies = self.strategies.clone();
+            let mut seats: Vec<uint> = range(0, strategies.len()).collect();
+            rng.shuffle(seats.as_mut_slice());
+            pool.execute(proc() {
+                let result = task::try(proc() play_game(config, strategies, seats));
+                result_chan.send(result.ok());
+            });
+        }
+
+        let mut results = Vec::with_capacity(self.games);
+        let mut failures = 0u;
+        for _ in range(0, self.games) {
+            match result_port.recv() {
+                Some(result) => results.push(result),
+                None => failures += 1,
+            }
+        }
+
+        SimulationResult::new(self.strategies.len(), results.as_slice(), failures, rng.seed())
+    }
+}
+
+/// Play a single game, with `seats` giving the index of the strategy that
+/// goes in each seat. Players in the result are numbered by their index in
+/// `strategies`.
+fn play_game(config: GameConfig, strategies: Vec<StrategyFn>, seats: Vec<uint>) -> GameResult {
+    let mut game = Game::with_config(config);
+    for &i in seats.iter() {
+        let conn = game.add_player();
+        let strategy = strategies[i];
+        spawn(proc() strategy(&conn));
+    }
+
+    // A game that can't be set up fails like any other.
+    let mut result = game.play().unwrap();
+    for player in result.players.iter_mut() {
+        player.player = seats[player.player];
+    }
+    result
+}
+
+/// Aggregate statistics over a simulation.
+#[deriving(Clone, Show)]
+pub struct SimulationResult {
+    /// The number of games that finished.
+    pub games: uint,
+
+    /// The number of games that were cut short because something failed.
+    pub failures: uint,
+
+    /// The seed the simulation was run with.
+    pub seed: u64,
+
+    /// The length of each game, in rounds.
+    pub turns: Summary,
+
+    /// Statistics for each strategy, in the order they were provided.
+    pub strategies: Vec<StrategyStats>,
+}
+
+impl SimulationResult {
+    fn new(num_strategies: uint, results: &[GameResult], failures: uint, seed: u64) -> SimulationResult {
+        let games = results.len();
+        let turns: Vec<f64> = results.iter().map(|x| x.rounds as f64).collect();
+        let strategies = range(0, num_strategies).map(|i| {
+            let (mut wins, mut ties, mut losses) = (0u, 0u, 0u);
+            let mut vp = Vec::with_capacity(games);
+            for result in results.iter() {
+                let player = result.players.iter().find(|x| x.player == i).unwrap();
+                match player.place {
+                    1 if result.is_tie() => ties += 1,
+                    1 => wins += 1,
+                    _ => losses += 1,
+                }
+                vp.push(player.vp as f64);
+            }
+            StrategyStats{
+                wins: Rate::new(wins, games),
+                ties: Rate::new(ties, games),
+                losses: Rate::new(losses, games),
+                vp: Summary::new(vp.as_slice()),
+            }
+        }).collect();
+
+        SimulationResult{
+            games: games,
+            failures: failures,
+            seed: seed,
+            turns: Summary::new(turns.as_slice()),
+            strategies: strategies,
+        }
+    }
+}
+
+/// How a single strategy fared.
+#[deriving(Clone, Show)]
+pub struct StrategyStats {
+    pub wins: Rate,
+    pub ties: Rate,
+    pub losses: Rate,
+
+    /// Victory points at the end of each game.
+    pub vp: Summary,
+}
+
+/// How often something happened.
+#[deriving(Clone, Show)]
+pub struct Rate {
+    pub count: uint,
+    pub rate: f64,
+
+    /// The 95% confidence interval for `rate`, using the normal
+    /// approximation.
+    pub ci95: (f64, f64),
+}
+
+impl Rate {
+    fn new(count: uint, total: uint) -> Rate {
+        if total == 0 {
+            return Rate{count: 0, rate: 0.0, ci95: (0.0, 0.0)};
+        }
+        let rate = count as f64 / total as f64;
+        let margin = Z_95 * (rate * (1.0 - rate) / total as f64).sqrt();
+        Rate{count: count, rate: rate, ci95: ((rate - margin).max(0.0), (rate + margin).min(1.0))}
+    }
+}
+
+/// Summary statistics over a set of samples.
+#[deriving(Clone, Show)]
+pub struct Summary {
+    pub mean: f64,
+
+    /// The sample standard deviation.
+    pub std_dev: f64,
+
+    /// The 95% confidence interval for `mean`.
+    pub ci95: (f64, f64),
+}
+
+impl Summary {
+    fn new(samples: &[f64]) -> Summary {
+        let n = samples.len() as f64;
+        if samples.len() < 2 {
+            let mean = samples.iter().fold(0.0, |a, b| a + *b);
+            return Summary{mean: mean, std_dev: 0.0, ci95: (mean, mean)};
+        }
+        let mean = samples.iter().fold(0.0, |a, b| a + *b) / n;
+        let variance = samples.iter().fold(0.0, |a, b| a + (*b - mean) * (*b - mean)) / (n - 1.0);
+        let std_dev = variance.sqrt();
+        let margin = Z_95 * std_dev / n.sqrt();
+        Summary{mean: mean, std_dev: std_dev, ci95: (mean - margin, mean + margin)}
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/simulation.rs"]
+mod tests;
@@ -0,0 +1,294 @@
+//! Bots that run on the game thread.
+//!
+//! A `Strategy` is an alternative to driving a player through a
+//! `Connection`. Instead of spawning a thread and looping over
+//! notifications, the game calls the strategy directly whenever it needs
+//! something from the player, so there's no waiting on channels.
+//!
+//! ~~~ignore
+//! struct BuySilver;
+//!
+//! impl Strategy for BuySilver {
+//!     fn choose_buy(&self, turn: &Turn) -> Option<Card> {
+//!         if turn.get_buying_power() >= 3 { Some(Silver) } else { None }
+//!     }
+//! }
+//!
+//! let mut game = Game::new();
+//! game.add_strategy(box BuySilver);
+//! ~~~
+
+use std::cmp;
+use std::collections::{HashMap, RingBuf};
+
+use card::{Bureaucrat, Card, Library};
+use command;
+use decision;
+use decision::{Choice, Decision};
+use phase;
+use phase::Phase;
+use query;
+use query::{Answer, Opponent, Query, QueryResult};
+use reaction;
+use response::Response;
+use super::{GameState, Player, PlayerHandle};
+
+/// Decides what a player does. Every method is called on the game thread,
+/// and everything but `choose_buy` has a reasonable default.
+///
+/// Methods take `&self` because the game may need to ask something in the
+/// middle of another call, e.g. when Cellar is played during
+/// `take_action_phase`. Strategies that keep track of things between calls
+/// can use a `Cell` or `RefCell`.
+pub trait Strategy {
+    /// Play Action cards at the start of your turn.
+    ///
+    /// By default, plays nothing.
+    fn take_action_phase(&self, _turn: &mut Turn) {}
+
+    /// Play treasures once the Action phase is over.
+    ///
+    /// By default, plays all of them.
+    fn play_treasures(&self, turn: &mut Turn) {
+        turn.play_all_money();
+    }
+
+    /// Pick a card to buy, or None to stop buying. It's called again after
+    /// every successful buy until you run out of buys. Events can be picked
+    /// too.
+    fn choose_buy(&self, turn: &Turn) -> Option<Card>;
+
+    /// Called whenever a card needs you to decide something, whether it's
+    /// your own card, another player's attack, or whether to reveal a
+    /// Reaction. Illegal choices are replaced with the decision's default.
+    ///
+    /// By default, leaves every decision up to the game. Decisions left
+    /// `Undecided` that one of the methods below covers are asked of that
+    /// method instead.
+    fn decide(&self, _decision: &Decision) -> Choice {
+        decision::Undecided
+    }
+
+    /// Called when another player attacks you while you have a Reaction in
+    /// hand that can block it. Returns true to reveal it.
+    ///
+    /// By default, always blocks attacks.
+    fn react_to_attack(&self, _attack: Card, _hand: &[Card]) -> bool {
+        true
+    }
+
+    /// Called when another player's attack makes you discard down to three
+    /// cards, as Militia, Goons, Followers and Margrave do, and called
+    /// repeatedly until you're down to three. Returns the card to discard,
+    /// which must be in `hand`.
+    ///
+    /// By default, discards the least useful card.
+    fn militia_discard(&self, hand: &[Card]) -> Card {
+        decision::least_useful(hand)[0]
+    }
+
+    /// Called when another player plays Bureaucrat. Returns which of the
+    /// Victory cards in your hand to put back on your deck.
+    ///
+    /// By default, picks the first one.
+    fn bureaucrat_reveal(&self, victory_cards: &[Card]) -> Card {
+        victory_cards[0]
+    }
+
+    /// Called when Library draws an Action card. Returns true to set it
+    /// aside, or false to keep it. `actions` is the number of actions you
+    /// have left to play it with.
+    ///
+    /// By default, sets it aside if it can't be played.
+    fn library_should_discard(&self, _drawn: Card, actions: uint) -> bool {
+        actions == 0
+    }
+}
+
+/// Answer a decision that `Strategy::decide` left undecided with whichever
+/// of the strategy's other methods covers it, if any. `hand` and `actions`
+/// are the deciding player's.
+pub fn ask_hooks(strategy: &Strategy, decision: &Decision, hand: &[Card], actions: uint) -> Choice {
+    match *decision {
+        decision::YesNo{card, purpose: decision::Revealing, subject: Some(attack), ..}
+            if attack.has_type(::card::Attack) && card.reacts_to(&reaction::Attacked(attack)) => {
+            yes_or_no(strategy.react_to_attack(attack, hand))
+        },
+        decision::YesNo{card: Library, purpose: decision::SettingAside, subject: Some(drawn), ..} => {
+            yes_or_no(strategy.library_should_discard(drawn, actions))
+        },
+        decision::ChooseCards{card, purpose: decision::Discarding, ref from, min, max, ..}
+            if card.has_type(::card::Attack) && min > 0 && min == max && from.len() == min + 3 => {
+            let mut left = from.clone();
+            let mut chosen = Vec::new();
+            while chosen.len() < min {
+                let card = strategy.militia_discard(left.as_slice());
+                match left.iter().position(|x| *x == card) {
+                    Some(i) => { left.remove(i); },
+                    None => return decision::Undecided,
+                }
+                chosen.push(card);
+            }
+            decision::Cards(chosen)
+        },
+        decision::ChooseCards{card: Bureaucrat, purpose: decision::TopDecking, ref from, ..} if !from.is_empty() => {
+            decision::Cards(vec![strategy.bureaucrat_reveal(from.as_slice())])
+        },
+        _ => decision::Undecided,
+    }
+}
+
+fn yes_or_no(yes: bool) -> Choice {
+    if yes { decision::Yes } else { decision::No }
+}
+
+/// The active player's view of the game while a `Strategy` takes its turn.
+/// Commands take effect immediately.
+pub struct Turn<'a> {
+    player: &'a mut PlayerHandle,
+    state: &'a mut GameState,
+    opponents: &'a mut RingBuf<PlayerHandle>,
+}
+
+impl<'a> Turn<'a> {
+    /// Play a card. Anything it needs you to decide is asked of
+    /// `Strategy::decide()` before this returns.
+    pub fn play(&mut self, card: Card) -> Response {
+        self.player.handle_cmd(command::Play(card), self.state, self.opponents)
+    }
+
+    pub fn play_all_money(&mut self) -> Response {
+        self.player.handle_cmd(command::PlayAllMoney, self.state, self.opponents)
+    }
+
+    pub fn buy(&mut self, card: Card) -> Response {
+        self.player.handle_cmd(command::Buy(card), self.state, self.opponents)
+    }
+
+    pub fn buy_event(&mut self, card: Card) -> Response {
+        self.player.handle_cmd(command::BuyEvent(card), self.state, self.opponents)
+    }
+
+    /// Pay off `amount` of your debt, $1 each. Nothing can be bought while
+    /// you're in debt.
+    pub fn pay_debt(&mut self, amount: uint) -> Response {
+        self.player.handle_cmd(command::PayDebt(amount), self.state, self.opponents)
+    }
+
+    /// Turn Coffers into buying power, $1 each.
+    pub fn spend_coffers(&mut self, amount: uint) -> Response {
+        self.player.handle_cmd(command::SpendCoffers(amount), self.state, self.opponents)
+    }
+
+    /// Move on to the next phase of your turn.
+    pub fn end_phase(&mut self) -> Response {
+        self.player.handle_cmd(command::EndPhase, self.state, self.opponents)
+    }
+
+    pub fn get_actions(&self) -> uint {
+        self.player.get_actions()
+    }
+
+    pub fn get_all_cards(&self) -> Vec<Card> {
+        self.player.get_all_cards()
+    }
+
+    pub fn get_buys(&self) -> uint {
+        self.player.get_buys()
+    }
+
+    pub fn get_buying_power(&self) -> uint {
+        self.player.get_buying_power()
+    }
+
+    pub fn get_coffers(&self) -> uint {
+        self.player.get_coffers()
+    }
+
+    pub fn get_debt(&self) -> uint {
+        self.player.get_debt()
+    }
+
+    pub fn get_potions(&self) -> uint {
+        self.player.get_potions()
+    }
+
+    pub fn get_hand(&self) -> Vec<Card> {
+        self.player.get_hand()
+    }
+
+    pub fn get_phase(&self) -> Phase {
+        self.player.get_phase()
+    }
+
+    pub fn has_in_hand(&self, card: Card) -> bool {
+        self.player.has_in_hand(card)
+    }
+
+    pub fn number_of(&self, card: Card) -> uint {
+        self.player.number_of(card)
+    }
+
+    /// The number of copies of a card left in the supply, or None if it
+    /// isn't part of this game.
+    pub fn count(&self, card: Card) -> Option<uint> {
+        self.state.count(card)
+    }
+
+    /// The number of Embargo tokens on a card's supply pile.
+    pub fn embargo_tokens(&self, card: Card) -> uint {
+        self.state.embargo_tokens(card)
+    }
+
+    pub fn get_supply(&self) -> HashMap<Card, uint> {
+        self.state.kingdom.clone()
+    }
+
+    pub fn get_trash(&self) -> Vec<Card> {
+        self.state.trash.clone()
+    }
+
+    pub fn get_opponents(&self) -> Vec<Opponent> {
+        match self.ask(query::Opponents) {
+            Ok(query::OpponentList(opponents)) => opponents,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Ask anything a `Connection` could.
+    pub fn ask(&self, q: Query) -> QueryResult<Answer> {
+        self.player.answer_query(q, self.state, self.opponents)
+    }
+}
+
+impl PlayerHandle {
+    /// Play a turn for a player with a `Strategy`, calling it directly
+    /// instead of waiting on commands from a `Connection`.
+    pub fn take_turn(&mut self, state: &mut GameState, opponents: &mut RingBuf<PlayerHandle>) {
+        let strategy = self.strategy.clone().expect("player doesn't have a strategy");
+        let mut turn = Turn{player: self, state: state, opponents: opponents};
+
+        strategy.take_action_phase(&mut turn);
+        if turn.get_phase() == phase::ActionPhase {
+            turn.end_phase();
+        }
+        strategy.play_treasures(&mut turn);
+        while turn.get_buys() > 0 {
+            // Debt is paid off as soon as possible, since nothing can be
+            // bought until it is.
+            let owed = cmp::min(turn.get_debt(), turn.get_buying_power());
+            if owed > 0 {
+                turn.pay_debt(owed);
+            }
+            match strategy.choose_buy(&turn) {
+                Some(card) if card.has_type(::card::EventType) => if turn.buy_event(card).is_err() { break },
+                Some(card) => if turn.buy(card).is_err() { break },
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/strategy.rs"]
+mod tests;
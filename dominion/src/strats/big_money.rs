@@ -1,12 +1,10 @@
-use super::super::Player;
-
 pub fn big_money(conn: &::Connection) {
     loop {
         match conn.recv_notification() {
             ::notify::GameOver => break,
             ::notify::YourTurn(_) => {
                 conn.play_all_money();
-                let resp = match conn.get_buying_power() {
+                let resp = match conn.get_buying_power().unwrap_or(0) {
                     0...2 => ::response::NoProblem, // what are you even doing with your life?
                     3...5 => conn.buy(::card::Silver),
                     6...7 => conn.buy(::card::Gold),
@@ -21,3 +19,17 @@ pub fn big_money(conn: &::Connection) {
         }
     }
 }
+
+/// The same strategy, run on the game thread.
+pub struct BigMoney;
+
+impl ::strategy::Strategy for BigMoney {
+    fn choose_buy(&self, turn: &::strategy::Turn) -> Option<::card::Card> {
+        match turn.get_buying_power() {
+            0...2 => None,
+            3...5 => Some(::card::Silver),
+            6...7 => Some(::card::Gold),
+            _     => Some(::card::Province),
+        }
+    }
+}
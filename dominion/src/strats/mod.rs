@@ -1 +1 @@
-mod big_money;
+pub mod big_money;
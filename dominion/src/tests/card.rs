@@ -0,0 +1,29 @@
+use super::*;
+use super::super::cost::Cost;
+
+#[test]
+fn test_metadata() {
+    assert_eq!(Gold.cost(), Cost::coins(6));
+    assert_eq!(Golem.cost(), Cost::new(4, 1, 0));
+    assert_eq!(Gold.coin_value(), 3);
+    assert_eq!(Smithy.coin_value(), 0);
+    assert_eq!(Province.printed_victory_points(), 6);
+    assert_eq!(Curse.printed_victory_points(), -1);
+    assert_eq!(Moat.types(), vec![Action, Reaction]);
+    assert!(Moat.has_type(Reaction));
+    assert!(Witch.has_type(Attack));
+    assert!(!Witch.has_type(Reaction));
+    assert!(Copper.is_money() && !Copper.is_action());
+}
+
+#[test]
+fn test_victory_points() {
+    let deck = vec![Gardens, Copper, Copper, Copper, Copper, Copper, Copper, Copper, Estate, Estate, Estate];
+    assert_eq!(Gardens.victory_points(deck.as_slice()), 1);
+    assert_eq!(Gardens.victory_points(deck.slice_to(9)), 0);
+    assert_eq!(Estate.victory_points(deck.as_slice()), 1);
+    assert_eq!(Smithy.victory_points(deck.as_slice()), 0);
+
+    let deck = vec![Vineyard, Smithy, Village, GreatHall, Smithy, Village, Copper];
+    assert_eq!(Vineyard.victory_points(deck.as_slice()), 1);
+}
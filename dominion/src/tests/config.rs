@@ -0,0 +1,154 @@
+use super::{BadBane, GameConfig, KINGDOM_SIZE, MAX_PLAYERS, WrongPlayerCount};
+use super::super::card::*;
+use super::super::rng::GameRng;
+use super::super::sets;
+
+#[test]
+fn test_random_kingdom() {
+    let kingdom = GameConfig::new().build_kingdom(&GameRng::random());
+    assert_eq!(kingdom.len(), KINGDOM_SIZE);
+    for (i, card) in kingdom.iter().enumerate() {
+        assert!(sets::dominion::kingdom().contains(card));
+        assert!(!kingdom.slice_from(i + 1).contains(card));
+    }
+}
+
+#[test]
+fn test_kingdom_from_sets() {
+    let kingdom = GameConfig::new().sets(vec![sets::Intrigue]).build_kingdom(&GameRng::random());
+    assert!(kingdom.iter().all(|x| sets::intrigue::kingdom().contains(x)));
+
+    let both: Vec<Card> = sets::dominion::kingdom().into_iter().chain(sets::intrigue::kingdom().into_iter()).collect();
+    let kingdom = GameConfig::new().sets(vec![sets::Dominion, sets::Intrigue]).build_kingdom(&GameRng::random());
+    assert_eq!(kingdom.len(), KINGDOM_SIZE);
+    assert!(kingdom.iter().all(|x| both.contains(x)));
+}
+
+#[test]
+fn test_chosen_kingdom() {
+    let kingdom = GameConfig::new().kingdom(vec![Smithy, Witch, Smithy]).build_kingdom(&GameRng::random());
+    assert_eq!(kingdom.len(), KINGDOM_SIZE);
+    assert_eq!(kingdom.slice_to(2), [Smithy, Witch].as_slice());
+    assert_eq!(kingdom.iter().filter(|x| **x == Smithy).count(), 1);
+}
+
+#[test]
+fn test_two_player_supply() {
+    let supply = GameConfig::new().kingdom(vec![Gardens]).supply(2, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Copper), Some(&46));
+    assert_eq!(supply.get(&Estate), Some(&8));
+    assert_eq!(supply.get(&Province), Some(&8));
+    assert_eq!(supply.get(&Gardens), Some(&8));
+    assert_eq!(supply.get(&Curse), Some(&10));
+    assert_eq!(supply.len(), 7 + KINGDOM_SIZE);
+}
+
+#[test]
+fn test_player_count() {
+    let config = GameConfig::new().kingdom(vec![Smithy]);
+    assert_eq!(config.supply(0, &GameRng::random()).err(), Some(WrongPlayerCount(0)));
+    assert_eq!(config.supply(MAX_PLAYERS + 1, &GameRng::random()).err(), Some(WrongPlayerCount(MAX_PLAYERS + 1)));
+    let supply = config.supply(MAX_PLAYERS, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Copper), Some(&4));
+    assert_eq!(supply.get(&Curse), Some(&70));
+}
+
+#[test]
+fn test_four_player_supply() {
+    let supply = GameConfig::new().kingdom(vec![Gardens, Smithy]).supply(4, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Copper), Some(&32));
+    assert_eq!(supply.get(&Duchy), Some(&12));
+    assert_eq!(supply.get(&Gardens), Some(&12));
+    assert_eq!(supply.get(&Smithy), Some(&10));
+    assert_eq!(supply.get(&Curse), Some(&30));
+}
+
+#[test]
+fn test_colonies() {
+    let supply = GameConfig::new().kingdom(vec![Smithy]).sets(vec![sets::Dominion]).supply(3, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Colony), None);
+
+    let supply = GameConfig::new().kingdom(vec![Bank]).supply(3, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Platinum), Some(&12));
+    assert_eq!(supply.get(&Colony), Some(&12));
+
+    let supply = GameConfig::new().kingdom(vec![Bank]).colonies(false).supply(3, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Platinum), None);
+}
+
+#[test]
+fn test_potions() {
+    let supply = GameConfig::new().kingdom(vec![Smithy]).sets(vec![sets::Dominion]).supply(2, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Potion), None);
+
+    let supply = GameConfig::new().kingdom(vec![Golem]).supply(2, &GameRng::random()).unwrap();
+    assert_eq!(supply.get(&Potion), Some(&16));
+}
+
+#[test]
+fn test_seeded_kingdom() {
+    let config = GameConfig::new();
+    assert_eq!(config.build_kingdom(&GameRng::new(42)), config.build_kingdom(&GameRng::new(42)));
+}
+
+#[test]
+fn test_bane_and_prizes() {
+    let setup = GameConfig::new().kingdom(vec![YoungWitch, Tournament]).bane(Chapel).setup(2, &GameRng::random()).unwrap();
+    assert_eq!(setup.bane, Some(Chapel));
+    assert_eq!(setup.supply.get(&Chapel), Some(&10));
+    assert_eq!(setup.non_supply.len(), 5);
+    assert_eq!(setup.non_supply.get(&Princess), Some(&1));
+    assert_eq!(setup.supply.get(&Princess), None);
+
+    let setup = GameConfig::new().kingdom(vec![Smithy]).sets(vec![sets::Dominion]).setup(2, &GameRng::random()).unwrap();
+    assert_eq!(setup.bane, None);
+    assert!(setup.non_supply.is_empty());
+}
+
+#[test]
+fn test_bad_bane() {
+    let config = GameConfig::new().kingdom(vec![YoungWitch, Chapel]);
+    assert_eq!(config.clone().bane(Smithy).supply(2, &GameRng::random()).err(), Some(BadBane(Smithy)));
+    assert_eq!(config.clone().bane(Chapel).supply(2, &GameRng::random()).err(), Some(BadBane(Chapel)));
+    assert!(config.bane(Moat).supply(2, &GameRng::random()).is_ok());
+}
+
+#[test]
+fn test_dark_ages_setup() {
+    let setup = GameConfig::new().kingdom(vec![Marauder, Knights, Hermit]).sets(vec![sets::Dominion]).setup(3, &GameRng::random()).unwrap();
+    assert!(setup.shelters);
+    assert_eq!(setup.mixed_piles.len(), 2);
+    assert_eq!(setup.mixed_piles[0].len(), 20);
+    assert_eq!(setup.supply.get(&setup.mixed_piles[0][0]), Some(&20));
+    // The Knights placeholder is replaced by the pile itself.
+    assert_eq!(setup.supply.get(&Knights), None);
+    assert_eq!(setup.supply.get(&setup.mixed_piles[1][0]), Some(&10));
+    assert_eq!(setup.non_supply.get(&Spoils), Some(&15));
+    assert_eq!(setup.non_supply.get(&Madman), Some(&10));
+
+    let setup = GameConfig::new().kingdom(vec![Marauder]).shelters(false).setup(2, &GameRng::random()).unwrap();
+    assert!(!setup.shelters);
+}
+
+#[test]
+fn test_travellers_and_events() {
+    let setup = GameConfig::new().kingdom(vec![Page]).events(vec![Ball, Raid]).setup(2, &GameRng::random()).unwrap();
+    assert_eq!(setup.non_supply.get(&TreasureHunter), Some(&5));
+    assert_eq!(setup.non_supply.get(&Champion), Some(&5));
+    assert_eq!(setup.non_supply.get(&Teacher), None);
+    assert_eq!(setup.events, vec![Ball, Raid]);
+    assert_eq!(setup.supply.get(&Ball), None);
+}
+
+#[test]
+fn test_split_piles_and_landmarks() {
+    let setup = GameConfig::new().kingdom(vec![Patrician]).landmarks(vec![Wall, Obelisk]).setup(2, &GameRng::random()).unwrap();
+    assert_eq!(setup.supply.get(&Patrician), Some(&10));
+    assert_eq!(setup.supply.get(&Emporium), None);
+    let pile = setup.mixed_piles.iter().find(|x| x[0] == Patrician).unwrap();
+    assert_eq!(pile.len(), 10);
+    assert_eq!(pile[9], Emporium);
+    assert_eq!(setup.landmarks, vec![Wall, Obelisk]);
+    assert_eq!(setup.scoring.len(), 2);
+    assert_eq!(setup.supply.get(&Wall), None);
+}
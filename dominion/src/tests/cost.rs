@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn test_compare_costs() {
+    let golem = Cost::new(4, 1, 0);
+    assert!(Cost::coins(3) < Cost::coins(4));
+    assert!(Cost::coins(4) <= golem);
+    assert!(golem > Cost::new(3, 1, 0));
+    // Neither costs up to the other.
+    assert!(!(golem <= Cost::coins(5)));
+    assert!(!(Cost::coins(5) <= golem));
+    assert_eq!(golem.partial_cmp(&Cost::coins(5)), None);
+}
+
+#[test]
+fn test_change_coins() {
+    let cost = Cost::new(2, 1, 0);
+    assert_eq!(cost.plus(3), Cost::new(5, 1, 0));
+    assert_eq!(cost.minus(3), Cost::new(0, 1, 0));
+    assert!(cost.can_pay(2, 1));
+    assert!(!cost.can_pay(6, 0));
+}
+
+#[test]
+fn test_debt_costs() {
+    let engineer = Cost::new(0, 0, 4);
+    assert!(!(engineer <= Cost::coins(4)));
+    assert!(engineer.can_pay(0, 0));
+}
@@ -0,0 +1,89 @@
+use std::default::Default;
+
+use super::*;
+use super::super::GameState;
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::response::*;
+
+fn supply() -> GameState {
+    let mut state: GameState = Default::default();
+    state.kingdom.insert(Silver, 10);
+    state.kingdom.insert(Gold, 10);
+    state.kingdom.insert(Smithy, 10);
+    state.kingdom.insert(Village, 0);
+    state.kingdom.insert(Estate, 8);
+    state.kingdom.insert(Golem, 10);
+    state
+}
+
+fn is_legal(decision: &Decision, choice: Choice) -> bool {
+    !decision.check(&choice, &supply()).is_err()
+}
+
+#[test]
+fn test_choose_cards() {
+    let decision = Decision::cards(Cellar, Discarding, vec![Estate, Copper, Estate], 0, 3);
+    assert!(is_legal(&decision, Cards(vec![])));
+    assert!(is_legal(&decision, Cards(vec![Estate, Estate])));
+    assert!(!is_legal(&decision, Cards(vec![Estate, Estate, Estate])));
+    assert!(!is_legal(&decision, Cards(vec![Gold])));
+    assert!(!is_legal(&decision, Yes));
+    match decision.check(&Pile(Gold), &supply()) {
+        IllegalChoice(Pile(Gold)) => (),
+        resp => panic!("expected an illegal choice, got {}", resp),
+    }
+
+    // The limits can't be more than the cards available.
+    let decision = Decision::cards(Militia, Discarding, vec![Copper], 2, 2);
+    assert!(is_legal(&decision, Cards(vec![Copper])));
+    assert_eq!(decision.default_choice(&supply()), Cards(vec![Copper]));
+}
+
+#[test]
+fn test_default_discard() {
+    let decision = Decision::cards(Militia, Discarding, vec![Gold, Copper, Province, Smithy, Curse], 3, 3);
+    assert_eq!(decision.default_choice(&supply()), Cards(vec![Curse, Province, Copper]));
+}
+
+#[test]
+fn test_choose_pile() {
+    let decision = Decision::pile(Workshop, Gaining, Cost::coins(4));
+    assert!(is_legal(&decision, Pile(Smithy)));
+    assert!(!is_legal(&decision, Pile(Gold)));
+    assert!(!is_legal(&decision, Pile(Village)));
+    assert!(!is_legal(&decision, Pile(Feast)));
+    assert_eq!(decision.default_choice(&supply()), Pile(Smithy));
+
+    let decision = Decision::pile(Mine, Gaining, Cost::coins(6)).only(Money);
+    assert!(is_legal(&decision, Pile(Gold)));
+    assert!(!is_legal(&decision, Pile(Smithy)));
+
+    // Nothing is cheap enough.
+    let decision = Decision::pile(Workshop, Gaining, Cost::coins(1));
+    assert_eq!(decision.default_choice(&supply()), NoChoice);
+    assert!(is_legal(&decision, NoChoice));
+    assert!(!is_legal(&Decision::pile(Workshop, Gaining, Cost::coins(4)), NoChoice));
+
+    // Golem costs a potion, so it's never "up to" a cost in coins.
+    assert!(!is_legal(&Decision::pile(Remodel, Gaining, Cost::coins(6)), Pile(Golem)));
+    let decision = Decision::pile(Remodel, Gaining, Cost::new(6, 1, 0));
+    assert!(is_legal(&decision, Pile(Golem)));
+    assert_eq!(decision.default_choice(&supply()), Pile(Gold));
+}
+
+#[test]
+fn test_other_decisions() {
+    let decision = Decision::yes_no(Library, SettingAside, Some(Smithy), true);
+    assert!(is_legal(&decision, No));
+    assert_eq!(decision.default_choice(&supply()), Yes);
+
+    let decision = Decision::option(Chancellor, vec!["one", "two"]);
+    assert!(is_legal(&decision, Pick(1)));
+    assert!(!is_legal(&decision, Pick(2)));
+
+    let decision = Decision::order(Spy, TopDecking, vec![Copper, Estate, Copper]);
+    assert!(is_legal(&decision, Order(vec![Estate, Copper, Copper])));
+    assert!(!is_legal(&decision, Order(vec![Estate, Estate, Copper])));
+    assert!(is_legal(&decision, Undecided));
+}
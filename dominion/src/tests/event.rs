@@ -0,0 +1,137 @@
+use std::cell::RefCell;
+use std::collections::RingBuf;
+use std::io::{BufReader, MemWriter};
+use std::rc::Rc;
+
+use super::*;
+use super::super::Game;
+use super::super::card::*;
+use super::super::config::GameConfig;
+use super::super::sets::test::*;
+use super::super::strats::big_money::big_money;
+
+/// Play a seeded game between two Big Money bots, logging to `sink`.
+fn logged_game(seed: u64, sink: Box<EventSink + 'static>) -> super::super::GameResult {
+    let mut game = Game::with_config(GameConfig::new().seed(seed));
+    game.log_to(sink);
+    for _ in range(0u, 2) {
+        let conn = game.add_player();
+        spawn(proc() big_money(&conn));
+    }
+    game.play().unwrap()
+}
+
+#[test]
+fn test_json_round_trip() {
+    let event = Gained{player: 1, card: Silver, from: Supply, to: Deck};
+    assert_eq!(Event::from_json(event.to_json().as_slice()), Ok(event));
+    assert!(Event::from_json("{\"nonsense\": true}").is_err());
+}
+
+#[test]
+fn test_moves_are_logged() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut p = player(vec![Thief, Copper], vec![]);
+    p.log.set_sink(box events.clone());
+    let mut o = opponent(vec![], vec![Gold, Estate]);
+    o.id = 1;
+    o.log = p.log.clone();
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    opponents.push(o);
+
+    assert_ok(play(Thief, &mut p, &mut state, &mut opponents));
+    assert_eq!(*events.borrow(), vec![
+        Played{player: 0, card: Thief},
+        Revealed{player: 1, card: Gold},
+        Moved{player: 1, card: Gold, from: Deck, to: SetAside},
+        Revealed{player: 1, card: Estate},
+        Moved{player: 1, card: Estate, from: Deck, to: SetAside},
+        Trashed{player: 1, card: Gold, from: SetAside},
+        Gained{player: 0, card: Gold, from: TrashPile, to: DiscardPile},
+        Discarded{player: 1, card: Estate, from: SetAside},
+    ]);
+}
+
+#[test]
+fn test_face_down_cards_are_not_named() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let mut p = player(vec![NativeVillage], vec![Gold]);
+    p.log.set_sink(box events.clone());
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(NativeVillage, &mut p, &mut state, &mut opponents));
+    assert_eq!(*events.borrow(), vec![
+        Played{player: 0, card: NativeVillage},
+        SetAsideFaceDown{player: 0, to: NativeVillageMat},
+    ]);
+
+    // A replay still knows it's the Gold.
+    let mut replay = Replay::new();
+    replay.apply(&GameStarted{seed: 1, players: 1, supply: vec![]}).unwrap();
+    replay.apply(&Shuffled{player: 0, deck: vec![Gold]}).unwrap();
+    replay.apply(&SetAsideFaceDown{player: 0, to: NativeVillageMat}).unwrap();
+    assert_eq!(replay.players[0].native_village, vec![Gold]);
+}
+
+#[test]
+fn test_replay_matches_game() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let result = logged_game(99, box events.clone());
+    let replay = Replay::from_events(events.borrow().as_slice()).unwrap();
+
+    assert_eq!(replay.seed, 99);
+    assert_eq!(replay.players.len(), 2);
+    let scores = replay.scores();
+    for player in result.players.iter() {
+        assert_eq!(scores[player.player], player.vp);
+        assert_eq!(replay.players[player.player].turns, player.turns);
+    }
+    // Big Money never trashes anything, and the game ended on some pile.
+    assert!(replay.trash.is_empty());
+    assert!(replay.supply.values().any(|x| *x == 0));
+}
+
+#[test]
+fn test_json_lines_replay() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    logged_game(7, box events.clone());
+
+    let mut sink = JsonLines::new(MemWriter::new());
+    for event in events.borrow().iter() {
+        sink.record(event);
+    }
+    let bytes = sink.unwrap().unwrap();
+    let replay = Replay::from_json_lines(&mut BufReader::new(bytes.as_slice())).unwrap();
+    assert_eq!(replay, Replay::from_events(events.borrow().as_slice()).unwrap());
+}
+
+#[test]
+fn test_broken_log() {
+    // A log missing its start doesn't know about any players.
+    let events = Rc::new(RefCell::new(Vec::new()));
+    logged_game(5, box events.clone());
+    match Replay::from_events(events.borrow().slice_from(1)) {
+        Err(NoSuchPlayer(_)) => (),
+        result => panic!("expected a missing player, got {}", result),
+    }
+
+    let mut replay = Replay::new();
+    replay.apply(&GameStarted{seed: 1, players: 1, supply: vec![(Copper, 1)]}).unwrap();
+    match replay.apply(&Drew{player: 0, card: Gold}) {
+        Err(NotThere(Gold, Deck)) => (),
+        result => panic!("expected a missing card, got {}", result),
+    }
+    replay.apply(&TookFromSupply{player: 0, card: Copper}).unwrap();
+    match replay.apply(&Trashed{player: 0, card: Copper, from: Supply}) {
+        Err(NotThere(Copper, Supply)) => (),
+        result => panic!("expected an empty pile, got {}", result),
+    }
+    assert!(replay.trash.is_empty());
+}
+
+#[test]
+fn test_same_seed_same_log() {
+    let (first, second) = (Rc::new(RefCell::new(Vec::new())), Rc::new(RefCell::new(Vec::new())));
+    logged_game(1234, box first.clone());
+    logged_game(1234, box second.clone());
+    assert_eq!(*first.borrow(), *second.borrow());
+}
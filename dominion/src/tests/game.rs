@@ -0,0 +1,255 @@
+use std::collections::RingBuf;
+use std::default::Default;
+
+use super::{GameResult, GameState, PlayerHandle, PlayerResult};
+use super::card::*;
+use super::command;
+use super::cost::Cost;
+use super::decision;
+use super::phase::*;
+use super::query;
+use super::query::*;
+use super::response::*;
+
+fn result(player: uint, vp: int, turns: uint) -> PlayerResult {
+    PlayerResult{player: player, place: 0, vp: vp, turns: turns}
+}
+
+#[test]
+fn test_most_vp_wins() {
+    let result = GameResult::new(vec![result(0, 20, 15), result(1, 31, 15), result(2, 12, 14)], 0);
+    assert_eq!(result.players.iter().map(|x| x.player).collect::<Vec<uint>>(), vec![1, 0, 2]);
+    assert_eq!(result.players.iter().map(|x| x.place).collect::<Vec<uint>>(), vec![1, 2, 3]);
+    assert_eq!(result.winners(), vec![1]);
+    assert!(!result.is_tie());
+    assert_eq!(result.rounds, 15);
+}
+
+#[test]
+fn test_tie_goes_to_fewer_turns() {
+    let result = GameResult::new(vec![result(0, 30, 16), result(1, 30, 15)], 0);
+    assert_eq!(result.winners(), vec![1]);
+    assert!(!result.is_tie());
+}
+
+#[test]
+fn test_shared_place() {
+    let result = GameResult::new(vec![result(0, 30, 15), result(1, 30, 15), result(2, 10, 15)], 0);
+    assert_eq!(result.players.iter().map(|x| x.place).collect::<Vec<uint>>(), vec![1, 1, 3]);
+    assert!(result.is_tie());
+}
+
+#[test]
+fn test_game_over() {
+    let mut state: GameState = Default::default();
+    state.kingdom.insert(Province, 1);
+    state.kingdom.insert(Smithy, 0);
+    state.kingdom.insert(Village, 0);
+    state.kingdom.insert(Market, 1);
+    assert!(!state.is_over(3));
+
+    // Three empty piles.
+    state.kingdom.insert(Market, 0);
+    assert!(state.is_over(3));
+    assert!(!state.is_over(4));
+
+    // No more Provinces.
+    state.kingdom.insert(Market, 1);
+    state.kingdom.insert(Province, 0);
+    assert!(state.is_over(3));
+
+    // No more Colonies.
+    state.kingdom.insert(Province, 1);
+    state.kingdom.insert(Colony, 0);
+    assert!(state.is_over(3));
+}
+
+#[test]
+fn test_gardens() {
+    let deck = vec![Gardens, Copper, Copper, Copper, Copper, Copper, Copper, Copper, Estate, Estate, Estate];
+    assert_eq!(Gardens.victory_points(deck.as_slice()), 1);
+    assert_eq!(Gardens.victory_points(deck.slice_to(9)), 0);
+    assert_eq!(Curse.victory_points(deck.as_slice()), -1);
+}
+
+fn buyer(buys: uint, buying_power: uint) -> PlayerHandle {
+    let (mut player, _) = PlayerHandle::new();
+    player.actions = 1;
+    player.buys = buys;
+    player.buying_power = buying_power;
+    player
+}
+
+fn supply() -> GameState {
+    let mut state: GameState = Default::default();
+    state.kingdom.insert(Silver, 10);
+    state.kingdom.insert(Province, 8);
+    state.kingdom.insert(Smithy, 0);
+    state
+}
+
+#[test]
+fn test_buy() {
+    let (mut player, mut state, mut opponents) = (buyer(2, 7), supply(), RingBuf::new());
+    assert!(!player.handle_cmd(command::Buy(Silver), &mut state, &mut opponents).is_err());
+    assert_eq!(player.buys, 1);
+    assert_eq!(player.buying_power, 4);
+    assert_eq!(player.phase, BuyPhase);
+    assert_eq!(player.discard, vec![Silver]);
+    assert_eq!(state.count(Silver), Some(9));
+}
+
+#[test]
+fn test_buy_not_enough_money() {
+    let (mut player, mut state, mut opponents) = (buyer(1, 0), supply(), RingBuf::new());
+    match player.handle_cmd(command::Buy(Province), &mut state, &mut opponents) {
+        NotEnoughMoney{need, have} => assert_eq!((need, have), (Cost::coins(8), Cost::coins(0))),
+        _ => panic!("bought a Province with no money!"),
+    }
+    assert!(player.discard.is_empty());
+    assert_eq!(state.count(Province), Some(8));
+}
+
+#[test]
+fn test_buy_no_buys() {
+    let (mut player, mut state, mut opponents) = (buyer(1, 6), supply(), RingBuf::new());
+    assert!(!player.handle_cmd(command::Buy(Silver), &mut state, &mut opponents).is_err());
+    match player.handle_cmd(command::Buy(Silver), &mut state, &mut opponents) {
+        NoBuys => (),
+        _ => panic!("bought two cards with only one buy!"),
+    }
+    assert_eq!(player.discard, vec![Silver]);
+}
+
+#[test]
+fn test_buy_missing_pile() {
+    let (mut player, mut state, mut opponents) = (buyer(1, 10), supply(), RingBuf::new());
+    match player.handle_cmd(command::Buy(Gold), &mut state, &mut opponents) {
+        NotInKingdom(Gold) => (),
+        _ => panic!("bought a card that isn't in the kingdom!"),
+    }
+    match player.handle_cmd(command::Buy(Smithy), &mut state, &mut opponents) {
+        PileEmpty(Smithy) => (),
+        _ => panic!("bought a card from an empty pile!"),
+    }
+}
+
+#[test]
+fn test_money_starts_buy_phase() {
+    let (mut player, mut state, mut opponents) = (buyer(1, 0), supply(), RingBuf::new());
+    player.hand = vec![Copper, Smithy];
+    assert!(!player.handle_cmd(command::Play(Copper), &mut state, &mut opponents).is_err());
+    assert_eq!(player.phase, BuyPhase);
+    match player.handle_cmd(command::Play(Smithy), &mut state, &mut opponents) {
+        WrongPhase(BuyPhase) => (),
+        _ => panic!("played an Action card during the Buy phase!"),
+    }
+    assert_eq!(player.hand, vec![Smithy]);
+    assert_eq!(player.actions, 1);
+}
+
+#[test]
+fn test_no_actions_after_buying() {
+    let (mut player, mut state, mut opponents) = (buyer(1, 3), supply(), RingBuf::new());
+    player.hand = vec![Smithy];
+    assert!(!player.handle_cmd(command::Buy(Silver), &mut state, &mut opponents).is_err());
+    match player.handle_cmd(command::Play(Smithy), &mut state, &mut opponents) {
+        WrongPhase(BuyPhase) => (),
+        _ => panic!("played an Action card after buying!"),
+    }
+}
+
+#[test]
+fn test_end_phase() {
+    let (mut player, mut state, mut opponents) = (buyer(1, 3), supply(), RingBuf::new());
+    player.hand = vec![Copper];
+    assert!(!player.handle_cmd(command::EndPhase, &mut state, &mut opponents).is_err());
+    assert_eq!(player.phase, BuyPhase);
+    assert!(!player.handle_cmd(command::EndPhase, &mut state, &mut opponents).is_err());
+    assert_eq!(player.phase, NightPhase);
+    assert!(player.handle_cmd(command::EndPhase, &mut state, &mut opponents).is_err());
+
+    // Nothing can be bought or played at night.
+    assert!(player.handle_cmd(command::Buy(Silver), &mut state, &mut opponents).is_err());
+    assert!(player.handle_cmd(command::Play(Copper), &mut state, &mut opponents).is_err());
+    assert_eq!(player.hand, vec![Copper]);
+}
+
+#[test]
+fn test_cleanup() {
+    let (mut player, _) = PlayerHandle::new();
+    player.hand = vec![Estate];
+    player.in_play = vec![Smithy, Copper];
+    player.deck = vec![Copper, Copper, Copper, Copper, Copper, Silver];
+    player.cleanup();
+    assert_eq!(player.discard, vec![Smithy, Copper, Estate]);
+    assert_eq!(player.hand.len(), 5);
+    assert!(player.in_play.is_empty());
+}
+
+#[test]
+fn test_connection_decides() {
+    let (mut player, conn) = PlayerHandle::new();
+    player.hand = vec![Cellar, Estate, Copper];
+    player.deck = vec![Gold];
+    player.actions = 1;
+    player.taking_turn = true;
+    let (mut state, mut opponents) = (supply(), RingBuf::new());
+    spawn(proc() {
+        // There's nothing to answer yet.
+        assert_eq!(conn.choose(decision::Cards(vec![Estate])), NotDeciding);
+        match conn.play(Cellar) {
+            Decide(_) => (),
+            resp => panic!("expected a decision, got {}", resp),
+        }
+        // Gold isn't in hand, so the game asks again.
+        match conn.choose(decision::Cards(vec![Gold])) {
+            IllegalChoice(decision::Cards(_)) => (),
+            resp => panic!("expected an illegal choice, got {}", resp),
+        }
+        assert_eq!(conn.choose(decision::Cards(vec![Estate])), NoProblem);
+    });
+    let cmd = player.cmd_port.recv();
+    let resp = player.handle_cmd(cmd, &mut state, &mut opponents);
+    player.resp_chan.send(resp);
+    assert_eq!(player.hand, vec![Copper, Gold]);
+    assert_eq!(player.discard, vec![Estate]);
+}
+
+fn ask(player: &PlayerHandle, q: Query, state: &GameState, opponents: &RingBuf<PlayerHandle>) -> QueryResult<Answer> {
+    player.answer_query(q, state, opponents)
+}
+
+#[test]
+fn test_queries() {
+    let (mut player, mut state, mut opponents) = (buyer(2, 3), supply(), RingBuf::new());
+    player.hand = vec![Copper, Gold, Smithy];
+    player.deck = vec![Estate, Copper];
+    player.discard = vec![Province];
+    player.in_play = vec![Silver];
+    state.trash.push(Curse);
+    let mut opponent = buyer(1, 0);
+    opponent.id = 1;
+    opponent.deck = vec![Duchy, Copper, Copper];
+    opponents.push(opponent);
+
+    assert_eq!(ask(&player, Actions, &state, &opponents), Ok(Number(1)));
+    assert_eq!(ask(&player, Buys, &state, &opponents), Ok(Number(2)));
+    assert_eq!(ask(&player, AvailableMoney, &state, &opponents), Ok(Number(4)));
+    assert_eq!(ask(&player, query::Discard, &state, &opponents), Ok(CardList(vec![Province])));
+    assert_eq!(ask(&player, InPlay, &state, &opponents), Ok(CardList(vec![Silver])));
+    assert_eq!(ask(&player, NumberOf(Copper), &state, &opponents), Ok(Number(2)));
+    assert_eq!(ask(&player, TotalPoints, &state, &opponents), Ok(Points(7)));
+    assert_eq!(ask(&player, HasInHand(Gold), &state, &opponents), Ok(Truth(true)));
+    assert_eq!(ask(&player, CurrentPhase, &state, &opponents), Ok(PhaseIs(ActionPhase)));
+    assert_eq!(ask(&player, Count(Province), &state, &opponents), Ok(Number(8)));
+    assert_eq!(ask(&player, Count(Gold), &state, &opponents), Err(NotInSupply(Gold)));
+    assert_eq!(ask(&player, query::Trash, &state, &opponents), Ok(CardList(vec![Curse])));
+    assert_eq!(ask(&player, Supply, &state, &opponents), Ok(SupplyPiles(state.kingdom.clone())));
+    assert_eq!(ask(&player, Opponents, &state, &opponents),
+               Ok(OpponentList(vec![Opponent{player: 1, score: 3, deck_size: 3}])));
+    match ask(&player, AllCards, &state, &opponents) {
+        Ok(CardList(cards)) => assert_eq!(cards.len(), 7),
+        answer => panic!("expected a list of cards, got {}", answer),
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::RingBuf;
+
+use super::*;
+use super::super::card::*;
+use super::super::cost::Cost;
+use super::super::sets::test::{assert_ok, play, player, state};
+
+#[test]
+fn test_lookup() {
+    assert_eq!(from_str::<Card>("Smithy"), Some(Smithy));
+    assert_eq!(from_str::<Card>("throne room"), Some(ThroneRoom));
+    assert_eq!(from_str::<Card>("Nonsense"), None);
+}
+
+#[test]
+fn test_load_vanilla_card() {
+    let cards = load_json(r#"{"cards": [
+        {"name": "Hamlet Lite", "cost": 2, "types": ["Action"], "set": "Playtest", "cards": 1, "actions": 1, "buys": 1},
+        {"name": "Platinum Lite", "cost": 8, "types": ["Money"], "coins": 4},
+        {"name": "Golem Lite", "cost": 4, "potions": 1, "types": ["Action"], "cards": 2}
+    ]}"#).unwrap();
+    assert_eq!(cards.len(), 3);
+    let (hamlet, platinum) = (cards[0], cards[1]);
+    assert_eq!(cards[2].cost(), Cost::new(4, 1, 0));
+    assert_eq!(from_str::<Card>("hamlet lite"), Some(hamlet));
+    assert_eq!(hamlet.name(), "Hamlet Lite");
+    assert_eq!(hamlet.cost(), Cost::coins(2));
+    assert!(hamlet.is_action());
+    assert_eq!(get_def(hamlet).set, Some("Playtest".to_string()));
+    assert_eq!(platinum.coin_value(), 4);
+
+    let mut p = player(vec![hamlet, platinum], vec![Copper]);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(hamlet, &mut p, &mut state, &mut opponents));
+    assert_eq!((p.actions, p.buys), (1, 2));
+    assert_eq!(p.hand, vec![platinum, Copper]);
+    assert_ok(play(platinum, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.buying_power, 4);
+}
+
+fn get_def(card: Card) -> &'static CardDef {
+    match card {
+        Custom(id) => get(id),
+        _ => panic!("{} isn't a custom card", card),
+    }
+}
+
+#[test]
+fn test_bad_files() {
+    match load_json(r#"{"cards": [{"name": "Smithy", "cost": 4, "types": ["Action"]}]}"#) {
+        Err(DuplicateName(name)) => assert_eq!(name.as_slice(), "Smithy"),
+        result => panic!("expected a duplicate name, got {}", result),
+    }
+    match load_json(r#"{"cards": [{"name": "Typeless", "cost": 4, "types": []}]}"#) {
+        Err(NoTypes(_)) => (),
+        result => panic!("expected a card with no types, got {}", result),
+    }
+    assert!(load_json("{\"cards\": 3}").is_err());
+    assert_eq!(from_str::<Card>("Typeless"), None);
+}
+
+#[test]
+fn test_load_kingdom() {
+    load_json(r#"{
+        "cards": [{"name": "Village Lite", "cost": 2, "types": ["Action"], "actions": 2}],
+        "kingdoms": [{"name": "Lite Game", "cards": ["Village Lite", "Smithy", "throne room"]}]
+    }"#).unwrap();
+    let village = from_str::<Card>("Village Lite").unwrap();
+    assert_eq!(kingdom("lite game"), Some(vec![village, Smithy, ThroneRoom]));
+    assert_eq!(kingdom("Nonsense"), None);
+
+    match load_json(r#"{"kingdoms": [{"name": "Broken", "cards": ["Smithy", "Not A Card"]}]}"#) {
+        Err(UnknownCard(name)) => assert_eq!(name.as_slice(), "Not A Card"),
+        result => panic!("expected an unknown card, got {}", result),
+    }
+    assert_eq!(kingdom("Broken"), None);
+    match load_json(r#"{"kingdoms": [{"name": "Lite Game", "cards": []}]}"#) {
+        Err(DuplicateName(_)) => (),
+        result => panic!("expected a duplicate name, got {}", result),
+    }
+}
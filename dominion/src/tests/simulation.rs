@@ -0,0 +1,45 @@
+use super::{Rate, Simulation, Summary};
+use super::super::config::GameConfig;
+use super::super::card::*;
+use super::super::strats::big_money::big_money;
+
+#[test]
+fn test_summary() {
+    let summary = Summary::new([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].as_slice());
+    assert_eq!(summary.mean, 5.0);
+    assert!((summary.std_dev - 2.138).abs() < 0.001);
+    let (low, high) = summary.ci95;
+    assert!(low < 5.0 && high > 5.0);
+}
+
+#[test]
+fn test_rate() {
+    let rate = Rate::new(50, 100);
+    assert_eq!(rate.rate, 0.5);
+    let (low, high) = rate.ci95;
+    assert!((low - 0.402).abs() < 0.001);
+    assert!((high - 0.598).abs() < 0.001);
+    assert_eq!(Rate::new(0, 0).rate, 0.0);
+}
+
+#[test]
+fn test_simulation() {
+    let config = GameConfig::new().kingdom(vec![Cellar, Chapel, Moat, Village, Woodcutter, Smithy, Festival, Laboratory, Market, Witch]);
+    let result = Simulation::new(vec![big_money, big_money]).config(config).games(4).threads(2).run();
+    assert_eq!(result.games + result.failures, 4);
+    for stats in result.strategies.iter() {
+        assert_eq!(stats.wins.count + stats.ties.count + stats.losses.count, result.games);
+    }
+}
+
+#[test]
+fn test_seeded_simulation() {
+    let simulation = Simulation::new(vec![big_money, big_money]).games(4).threads(2).seed(1234);
+    let (first, second) = (simulation.run(), simulation.run());
+    assert_eq!(first.seed, 1234);
+    assert_eq!(first.turns.mean, second.turns.mean);
+    for (a, b) in first.strategies.iter().zip(second.strategies.iter()) {
+        assert_eq!(a.wins.count, b.wins.count);
+        assert_eq!(a.vp.mean, b.vp.mean);
+    }
+}
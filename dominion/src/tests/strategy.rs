@@ -0,0 +1,168 @@
+use std::collections::RingBuf;
+use std::rc::Rc;
+
+use super::{Strategy, Turn};
+use super::super::{Game, PlayerHandle};
+use super::super::card::*;
+use super::super::decision::*;
+use super::super::phase::*;
+use super::super::response::*;
+use super::super::sets::test::*;
+use super::super::strats::big_money::{big_money, BigMoney};
+
+/// Never buys anything, discards Estates whenever it can, and never sets
+/// aside or reveals anything.
+struct Stubborn;
+
+impl Strategy for Stubborn {
+    fn choose_buy(&self, _: &Turn) -> Option<Card> {
+        None
+    }
+
+    fn decide(&self, decision: &Decision) -> Choice {
+        match *decision {
+            ChooseCards{purpose: Discarding, ref from, max, ..} => {
+                Cards(from.iter().filter(|x| **x == Estate).take(max).map(|x| *x).collect())
+            },
+            YesNo{purpose: SettingAside, ..} | YesNo{purpose: Revealing, ..} => No,
+            _ => Undecided,
+        }
+    }
+}
+
+/// Only uses the older single-purpose methods: discards Coppers first,
+/// never reveals Moat, tops the last Victory card for Bureaucrat, and keeps
+/// every Action card Library draws.
+struct Hooks;
+
+impl Strategy for Hooks {
+    fn choose_buy(&self, _: &Turn) -> Option<Card> {
+        None
+    }
+
+    fn react_to_attack(&self, _: Card, _: &[Card]) -> bool {
+        false
+    }
+
+    fn militia_discard(&self, hand: &[Card]) -> Card {
+        *hand.iter().find(|x| **x == Copper).unwrap_or(&hand[0])
+    }
+
+    fn bureaucrat_reveal(&self, victory_cards: &[Card]) -> Card {
+        victory_cards[victory_cards.len() - 1]
+    }
+
+    fn library_should_discard(&self, _: Card, _: uint) -> bool {
+        false
+    }
+}
+
+fn with_strategy(hand: Vec<Card>, deck: Vec<Card>, strategy: Box<Strategy + 'static>) -> PlayerHandle {
+    let mut p = player(hand, deck);
+    p.strategy = Some(Rc::new(strategy));
+    p
+}
+
+#[test]
+fn test_strategies_play_a_game() {
+    let mut game = Game::new();
+    game.add_strategy(box BigMoney);
+    game.add_strategy(box BigMoney);
+    let result = game.play().unwrap();
+    assert_eq!(result.players.len(), 2);
+    assert!(result.rounds > 0);
+}
+
+#[test]
+fn test_strategy_against_connection() {
+    let mut game = Game::new();
+    assert_eq!(game.add_strategy(box BigMoney), 0);
+    let conn = game.add_player();
+    spawn(proc() big_money(&conn));
+    let result = game.play().unwrap();
+    assert_eq!(result.players.len(), 2);
+}
+
+#[test]
+fn test_take_turn() {
+    let mut p = with_strategy(vec![Copper, Copper, Copper, Estate, Estate], vec![], box BigMoney);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    p.take_turn(&mut state, &mut opponents);
+    assert_eq!(p.phase, BuyPhase);
+    assert_eq!(p.in_play, vec![Copper, Copper, Copper]);
+    assert_eq!(p.discard, vec![Silver]);
+    assert_eq!(p.buys, 0);
+}
+
+#[test]
+fn test_play_asks_strategy() {
+    let mut p = with_strategy(vec![Cellar, Estate, Estate, Copper], vec![Gold, Gold], box Stubborn);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    let mut turn = Turn{player: &mut p, state: &mut state, opponents: &mut opponents};
+    match turn.play(Cellar) {
+        NoProblem => (),
+        _ => panic!("Cellar wasn't played"),
+    }
+    assert_eq!(turn.get_hand(), vec![Copper, Gold, Gold]);
+    match turn.play(Cellar) {
+        NotInHand(Cellar) => (),
+        _ => panic!("Cellar was played twice"),
+    }
+}
+
+#[test]
+fn test_militia_asks_strategy() {
+    let mut p = player(vec![Militia], vec![]);
+    let o = with_strategy(vec![Copper, Estate, Copper, Estate, Copper], vec![], box Stubborn);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    opponents.push(o);
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].hand, vec![Copper, Copper, Copper]);
+}
+
+#[test]
+fn test_library_asks_strategy() {
+    let mut p = with_strategy(vec![Library], vec![Smithy, Copper, Copper, Copper, Copper, Copper, Copper], box Stubborn);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    assert_ok(play(Library, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand.len(), 7);
+    assert_eq!(p.hand[0], Smithy);
+    assert!(p.discard.is_empty());
+}
+
+#[test]
+fn test_strategy_can_keep_moat_hidden() {
+    let mut p = player(vec![Witch], vec![]);
+    let o = with_strategy(vec![Moat], vec![], box Stubborn);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    opponents.push(o);
+    assert_ok(play(Witch, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].discard, vec![Curse]);
+}
+
+#[test]
+fn test_decide_falls_back_to_hooks() {
+    let mut p = player(vec![Militia, Bureaucrat, Witch], vec![]);
+    p.actions = 3;
+    let o = with_strategy(vec![Moat, Copper, Estate, Copper, Duchy], vec![], box Hooks);
+    let (mut state, mut opponents) = (state(), RingBuf::new());
+    opponents.push(o);
+    assert_ok(play(Militia, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].hand, vec![Moat, Estate, Duchy]);
+    assert_ok(play(Bureaucrat, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].deck, vec![Duchy]);
+    assert_ok(play(Witch, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].discard, vec![Copper, Copper, Curse]);
+
+    // Goons asks the same question as Militia.
+    let mut p = player(vec![Goons], vec![]);
+    opponents.clear();
+    opponents.push(with_strategy(vec![Estate, Copper, Duchy, Copper, Estate], vec![], box Hooks));
+    assert_ok(play(Goons, &mut p, &mut state, &mut opponents));
+    assert_eq!(opponents[0].hand, vec![Estate, Duchy, Estate]);
+
+    // Library is the last action, so Smithy would be set aside by default.
+    let mut p = with_strategy(vec![Library], vec![Smithy, Copper, Copper, Copper, Copper, Copper, Copper], box Hooks);
+    assert_ok(play(Library, &mut p, &mut state, &mut opponents));
+    assert_eq!(p.hand[0], Smithy);
+}
@@ -0,0 +1,18 @@
+use super::*;
+use super::super::card::*;
+
+#[test]
+fn test_place() {
+    let mut tokens = PileTokens::new();
+    tokens.place(0, PlusCard, Smithy);
+    tokens.place(1, PlusCard, Smithy);
+    tokens.place(0, PlusBuy, Smithy);
+    assert_eq!(tokens.on(0, Smithy), vec![PlusCard, PlusBuy]);
+    assert_eq!(tokens.on(1, Smithy), vec![PlusCard]);
+
+    // Each player only has one of each token, so it moves.
+    tokens.place(0, PlusCard, Village);
+    assert_eq!(tokens.on(0, Smithy), vec![PlusBuy]);
+    assert_eq!(tokens.pile_of(0, PlusCard), Some(Village));
+    assert_eq!(tokens.pile_of(1, PlusBuy), None);
+}
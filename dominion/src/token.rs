@@ -0,0 +1,67 @@
+//! Tokens that players move onto supply piles, e.g. with Adventures'
+//! Events. Each player has one of each kind, and a token changes how that
+//! pile's cards behave for its owner only.
+//!
+//! The −1 Card, −$1 and Journey tokens are never on a pile, so they're kept
+//! on the player instead.
+
+use card::Card;
+
+#[deriving(Clone, Show, PartialEq, Eq, Hash)]
+pub enum Token {
+    /// +1 Card when the owner plays a card from the pile.
+    PlusCard,
+
+    /// +1 Action when the owner plays a card from the pile.
+    PlusAction,
+
+    /// +1 Buy when the owner plays a card from the pile.
+    PlusBuy,
+
+    /// +$1 when the owner plays a card from the pile.
+    PlusCoin,
+
+    /// Cards from the pile cost $2 less on the owner's turns.
+    MinusCost,
+
+    /// When the owner buys a card from the pile, they may trash a card
+    /// from their hand.
+    Trashing,
+
+    /// The owner's Estates are copies of the card on this pile, set aside by
+    /// Inheritance.
+    EstateToken,
+}
+
+/// Where every player's tokens are.
+#[deriving(Clone, Default)]
+pub struct PileTokens {
+    placed: Vec<(uint, Token, Card)>,
+}
+
+impl PileTokens {
+    pub fn new() -> PileTokens {
+        PileTokens{placed: Vec::new()}
+    }
+
+    /// Move `player`'s token onto `card`'s pile, taking it off whichever
+    /// pile it was on before.
+    pub fn place(&mut self, player: uint, token: Token, card: Card) {
+        self.placed.retain(|&(p, t, _)| p != player || t != token);
+        self.placed.push((player, token, card));
+    }
+
+    /// The tokens `player` has on `card`'s pile.
+    pub fn on(&self, player: uint, card: Card) -> Vec<Token> {
+        self.placed.iter().filter(|&&(p, _, c)| p == player && c == card).map(|&(_, t, _)| t).collect()
+    }
+
+    /// The pile `player`'s token is on, if it's been placed.
+    pub fn pile_of(&self, player: uint, token: Token) -> Option<Card> {
+        self.placed.iter().find(|&&(p, t, _)| p == player && t == token).map(|&(_, _, c)| c)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/token.rs"]
+mod tests;
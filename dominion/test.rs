@@ -23,5 +23,5 @@ fn main() {
     spawn(proc() {
         damien_fn(damien);
     });
-    game.play();
+    game.play().unwrap();
 }